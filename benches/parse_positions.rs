@@ -0,0 +1,49 @@
+//! Parsing a large positions payload the way `Client::get_response` used to, through a
+//! `String`, against parsing the received bytes directly.
+//!
+//! Run with `cargo bench --bench parse_positions`.
+
+use avanza::portfolio::positions::PositionsResponse;
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use serde_json::Value;
+
+const POSITION: &str = r#"{"accountId":"1234567","accountName":"ISK","accountType":"Investeringssparkonto","acquiredValue":15230.5,"averageAcquiredPrice":152.305,"change":-1.25,"changePercent":-0.58,"currency":"SEK","depositable":true,"flagCode":"SE","lastPrice":214.3,"lastPriceUpdated":"2026-10-15T15:29:59.000+0200","name":"Volvo B","orderbookId":"5269","profit":6199.5,"profitPercent":40.7,"tradable":true,"value":21430.0,"volume":100}"#;
+
+/// A positions response with `groups` instrument types of `per_group` positions each, shaped
+/// like a captured response from a large portfolio.
+fn fixture(groups: usize, per_group: usize) -> Vec<u8> {
+    let positions = vec![POSITION; per_group].join(",");
+    let group = format!(
+        r#"{{"instrumentType":"STOCK","positions":[{}],"todaysProfitPercent":-0.58,"totalProfitPercent":40.7,"totalProfitValue":6199.5,"totalValue":21430.0}}"#,
+        positions
+    );
+    let groups = vec![group.as_str(); groups].join(",");
+    format!(
+        r#"{{"instrumentPositions":[{}],"totalProfit":40000,"totalProfitPercent":10,"totalBalance":4000,"totalOwnCapital":100000,"totalBuyingPower":4000}}"#,
+        groups
+    )
+    .into_bytes()
+}
+
+fn parse_positions(c: &mut Criterion) {
+    let body = fixture(8, 250);
+    let mut group = c.benchmark_group("parse_positions");
+    group.throughput(Throughput::Bytes(body.len() as u64));
+
+    group.bench_function("text_from_str", |b| {
+        b.iter(|| {
+            let text = String::from_utf8_lossy(black_box(&body)).into_owned();
+            serde_json::from_str::<PositionsResponse>(&text).unwrap()
+        })
+    });
+    group.bench_function("bytes_from_slice", |b| {
+        b.iter(|| serde_json::from_slice::<PositionsResponse>(black_box(&body)).unwrap())
+    });
+    group.bench_function("raw_value", |b| {
+        b.iter(|| serde_json::from_slice::<Value>(black_box(&body)).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse_positions);
+criterion_main!(benches);
@@ -0,0 +1,163 @@
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::types::AccountId;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AccountType {
+    #[serde(rename = "Investeringssparkonto")]
+    Isk,
+    #[serde(rename = "Kapitalforsakring")]
+    Kf,
+    #[serde(rename = "AktieFondkonto")]
+    Af,
+    #[serde(rename = "Pensionsforsakring")]
+    Pension,
+    #[serde(rename = "Tjanstepension")]
+    OccupationalPension,
+    #[serde(rename = "Sparkonto")]
+    Savings,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AccountOwnership {
+    Owned,
+    Attorney,
+    Child,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountsResponse {
+    accounts: Vec<Account>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Account {
+    pub id: AccountId,
+    pub name: String,
+    pub account_type: AccountType,
+    pub tradable: bool,
+    pub depositable: bool,
+    pub ownership: AccountOwnership,
+    #[serde(default)]
+    pub clearing_number: Option<String>,
+    #[serde(default)]
+    pub account_number: Option<String>,
+}
+
+impl Client {
+    /// Lists every account available to the logged in user. This is where the `AccountId`
+    /// taken by the account endpoints comes from.
+    pub async fn get_accounts(&self) -> Result<Vec<Account>, RequestError> {
+        let resp = self
+            .fetch_spec::<AccountsResponse>(&endpoints::accounts(), Idempotency::Idempotent)
+            .await?;
+        Ok(resp.accounts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.get_accounts().await);
+    }
+
+    #[tokio::test]
+    async fn can_get_accounts() {
+        let mock_server = MockServer::start().await;
+
+        let responder = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{
+                "accounts": [
+                    {
+                        "id": "1234567",
+                        "name": "ISK",
+                        "accountType": "Investeringssparkonto",
+                        "tradable": true,
+                        "depositable": true,
+                        "ownership": "OWNED",
+                        "clearingNumber": "9550",
+                        "accountNumber": "1234567"
+                    },
+                    {
+                        "id": "2345678",
+                        "name": "Pensionssparande",
+                        "accountType": "Kapitalforsakring",
+                        "tradable": true,
+                        "depositable": true,
+                        "ownership": "OWNED",
+                        "clearingNumber": "9550",
+                        "accountNumber": "2345678"
+                    },
+                    {
+                        "id": "3456789",
+                        "name": "Barnens ISK",
+                        "accountType": "Investeringssparkonto",
+                        "tradable": true,
+                        "depositable": false,
+                        "ownership": "ATTORNEY",
+                        "clearingNumber": null
+                    }
+                ]
+            }"#,
+        ));
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/accounts"))
+            .respond_with(responder)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let accounts = assert_ok!(client.get_accounts().await);
+
+        assert_eq!(accounts.len(), 3);
+
+        assert_eq!(accounts[0].id, AccountId::from("1234567"));
+        assert_eq!(accounts[0].account_type, AccountType::Isk);
+        assert_eq!(accounts[0].ownership, AccountOwnership::Owned);
+        assert_eq!(accounts[0].clearing_number.as_deref(), Some("9550"));
+
+        assert_eq!(accounts[1].account_type, AccountType::Kf);
+
+        assert_eq!(accounts[2].ownership, AccountOwnership::Attorney);
+        assert!(!accounts[2].depositable);
+        assert_eq!(accounts[2].clearing_number, None);
+        assert_eq!(accounts[2].account_number, None);
+    }
+
+    #[test]
+    fn unknown_account_type_is_other() {
+        let account_type: AccountType =
+            serde_json::from_str("\"SparkontoPlus\"").expect("failed to parse");
+        assert_eq!(account_type, AccountType::Other);
+    }
+
+    #[test]
+    fn unknown_ownership_is_unknown() {
+        let ownership: AccountOwnership =
+            serde_json::from_str("\"GUARDIAN\"").expect("failed to parse");
+        assert_eq!(ownership, AccountOwnership::Unknown);
+    }
+}
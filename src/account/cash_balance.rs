@@ -0,0 +1,136 @@
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::types::AccountId;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CashBalanceResponse {
+    available_cash: f64,
+    #[serde(default)]
+    reserved_amount: f64,
+    buying_power: f64,
+    credit_limit: Option<f64>,
+    credit_used: Option<f64>,
+    buying_power_without_credit: Option<f64>,
+}
+
+/// Avanza only returns the credit figures for accounts with a credit facility. For other
+/// accounts `credit_limit` is `None`, `credit_used` is zero and both buying power figures
+/// are the same.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CashBalance {
+    pub available_cash: f64,
+    /// Cash reserved for open buy orders.
+    pub reserved_amount: f64,
+    pub credit_limit: Option<f64>,
+    pub credit_used: f64,
+    pub buying_power: f64,
+    pub buying_power_without_credit: f64,
+}
+
+impl CashBalance {
+    pub fn has_credit(&self) -> bool {
+        self.credit_limit.is_some()
+    }
+}
+
+impl From<CashBalanceResponse> for CashBalance {
+    fn from(response: CashBalanceResponse) -> Self {
+        CashBalance {
+            available_cash: response.available_cash,
+            reserved_amount: response.reserved_amount,
+            credit_limit: response.credit_limit,
+            credit_used: response.credit_used.unwrap_or_default(),
+            buying_power: response.buying_power,
+            buying_power_without_credit: response
+                .buying_power_without_credit
+                .unwrap_or(response.buying_power),
+        }
+    }
+}
+
+impl Client {
+    pub async fn get_cash_balance(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<CashBalance, RequestError> {
+        let resp = self
+            .fetch_spec::<CashBalanceResponse>(
+                &endpoints::cash_balance(account_id),
+                Idempotency::Idempotent,
+            )
+            .await?;
+        Ok(resp.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.get_cash_balance(&AccountId::from("1234")).await);
+    }
+
+    #[tokio::test]
+    async fn can_get_cash_balance() {
+        let mock_server = MockServer::start().await;
+
+        let credit = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{
+                "availableCash": 12000.5,
+                "reservedAmount": 2000,
+                "buyingPower": 60000.5,
+                "creditLimit": 50000,
+                "creditUsed": 2000,
+                "buyingPowerWithoutCredit": 10000.5
+            }"#,
+        ));
+
+        let no_credit = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{"availableCash": 4000, "buyingPower": 4000}"#,
+        ));
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/1234/cash"))
+            .respond_with(credit)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/5678/cash"))
+            .respond_with(no_credit)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let balance = assert_ok!(client.get_cash_balance(&AccountId::from("1234")).await);
+        assert!(balance.has_credit());
+        assert_eq!(balance.available_cash, 12000.5);
+        assert_eq!(balance.reserved_amount, 2000.0);
+        assert_eq!(balance.credit_limit, Some(50000.0));
+        assert_eq!(balance.credit_used, 2000.0);
+        assert_eq!(balance.buying_power, 60000.5);
+        assert_eq!(balance.buying_power_without_credit, 10000.5);
+
+        let balance = assert_ok!(client.get_cash_balance(&AccountId::from("5678")).await);
+        assert!(!balance.has_credit());
+        assert_eq!(balance.reserved_amount, 0.0);
+        assert_eq!(balance.credit_used, 0.0);
+        assert_eq!(balance.buying_power, 4000.0);
+        assert_eq!(balance.buying_power_without_credit, 4000.0);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::types::{AccountId, Percent};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CourtageClass {
+    Mini,
+    Small,
+    Medium,
+    Fixed,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CourtageResponse {
+    courtage_class: Option<CourtageClass>,
+    minimum_fee: Option<f64>,
+    percentage_fee: Option<Percent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CourtageInfo {
+    pub class: CourtageClass,
+    pub minimum_fee: f64,
+    pub percentage_fee: Percent,
+}
+
+impl CourtageInfo {
+    /// Estimated courtage in the account currency for an order of `order_value`,
+    /// which is the percentage fee but never less than the minimum fee.
+    pub fn estimate_fee(&self, order_value: f64) -> f64 {
+        (order_value.abs() * self.percentage_fee.as_fraction()).max(self.minimum_fee)
+    }
+}
+
+impl From<CourtageResponse> for Option<CourtageInfo> {
+    fn from(response: CourtageResponse) -> Self {
+        Some(CourtageInfo {
+            class: response.courtage_class?,
+            minimum_fee: response.minimum_fee.unwrap_or_default(),
+            percentage_fee: response.percentage_fee.unwrap_or_default(),
+        })
+    }
+}
+
+impl Client {
+    /// Returns `None` for accounts that can not trade, e.g. savings accounts.
+    pub async fn get_courtage_info(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Option<CourtageInfo>, RequestError> {
+        let resp = self
+            .fetch_spec::<CourtageResponse>(
+                &endpoints::courtage(account_id),
+                Idempotency::Idempotent,
+            )
+            .await?;
+        Ok(resp.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.get_courtage_info(&AccountId::from("1234")).await);
+    }
+
+    #[tokio::test]
+    async fn can_get_mini_courtage() {
+        let mock_server = MockServer::start().await;
+
+        let responder = ResponseTemplate::new(200).set_body_string(String::from(
+            "{\"courtageClass\":\"MINI\",\"minimumFee\":1,\"percentageFee\":0.25}",
+        ));
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/1234/courtage"))
+            .respond_with(responder)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let info = assert_ok!(client.get_courtage_info(&AccountId::from("1234")).await)
+            .expect("expected courtage info");
+
+        assert_eq!(info.class, CourtageClass::Mini);
+        assert_eq!(info.minimum_fee, 1.0);
+        assert_eq!(info.percentage_fee, Percent(0.25));
+    }
+
+    #[tokio::test]
+    async fn can_get_fixed_courtage() {
+        let mock_server = MockServer::start().await;
+
+        let responder = ResponseTemplate::new(200).set_body_string(String::from(
+            "{\"courtageClass\":\"FIXED\",\"minimumFee\":99,\"percentageFee\":0}",
+        ));
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/5678/courtage"))
+            .respond_with(responder)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let info = assert_ok!(client.get_courtage_info(&AccountId::from("5678")).await)
+            .expect("expected courtage info");
+
+        assert_eq!(info.class, CourtageClass::Fixed);
+        assert_eq!(info.estimate_fee(1_000_000.0), 99.0);
+    }
+
+    #[tokio::test]
+    async fn savings_account_has_no_courtage() {
+        let mock_server = MockServer::start().await;
+
+        let responder =
+            ResponseTemplate::new(200).set_body_string(String::from("{\"courtageClass\":null}"));
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/9999/courtage"))
+            .respond_with(responder)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let info = assert_ok!(client.get_courtage_info(&AccountId::from("9999")).await);
+
+        assert_eq!(info, None);
+    }
+
+    #[test]
+    fn estimate_fee_uses_percentage_above_minimum() {
+        let info = CourtageInfo {
+            class: CourtageClass::Small,
+            minimum_fee: 39.0,
+            percentage_fee: Percent(0.15),
+        };
+
+        assert_eq!(info.estimate_fee(100_000.0), 150.0);
+    }
+
+    #[test]
+    fn estimate_fee_never_below_minimum() {
+        let info = CourtageInfo {
+            class: CourtageClass::Mini,
+            minimum_fee: 1.0,
+            percentage_fee: Percent(0.25),
+        };
+
+        assert_eq!(info.estimate_fee(100.0), 1.0);
+        assert_eq!(info.estimate_fee(0.0), 1.0);
+    }
+}
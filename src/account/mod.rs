@@ -0,0 +1,5 @@
+pub mod accounts;
+pub mod cash_balance;
+pub mod courtage;
+pub mod transactions;
+pub mod transfers;
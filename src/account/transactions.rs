@@ -0,0 +1,284 @@
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::pagination::{paginate, Page};
+use crate::serde_helpers::lenient_f64;
+use crate::types::{AccountId, Currency, OrderbookId};
+use chrono::NaiveDate;
+use futures_util::{Stream, TryStreamExt};
+use serde::{Deserialize, Deserializer, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransactionType {
+    Buy,
+    Sell,
+    Dividend,
+    Deposit,
+    Withdrawal,
+    Interest,
+    Fee,
+    ForeignTax,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transaction {
+    pub id: String,
+    pub account_id: AccountId,
+    pub transaction_type: TransactionType,
+    pub date: NaiveDate,
+    pub description: String,
+    #[serde(default)]
+    pub orderbook_id: Option<OrderbookId>,
+    #[serde(default, deserialize_with = "lenient_volume")]
+    pub volume: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_price")]
+    pub price: Option<f64>,
+    #[serde(deserialize_with = "lenient_amount")]
+    pub amount: f64,
+    pub currency: Currency,
+}
+
+// Older transactions have these as strings with a decimal comma, e.g. "12,50".
+fn lenient_volume<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+    lenient_f64::deserialize_option_named(deserializer, "volume")
+}
+
+fn lenient_price<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+    lenient_f64::deserialize_option_named(deserializer, "price")
+}
+
+fn lenient_amount<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    lenient_f64::deserialize_named(deserializer, "amount")
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransactionsResponse {
+    #[serde(default)]
+    transactions: Vec<Transaction>,
+    #[serde(default)]
+    has_more: bool,
+}
+
+/// Filters and paging for the transaction endpoints. Dates are inclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionOptions {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    /// Transactions fetched per request.
+    pub page_size: usize,
+}
+
+impl Default for TransactionOptions {
+    fn default() -> Self {
+        Self {
+            from: None,
+            to: None,
+            page_size: 100,
+        }
+    }
+}
+
+impl Client {
+    /// Every transaction matching `options`, newest first. Fetches all pages before
+    /// returning, use `transactions_stream` to process them as they arrive.
+    pub async fn get_transactions(
+        &self,
+        account_id: &AccountId,
+        options: &TransactionOptions,
+    ) -> Result<Vec<Transaction>, RequestError> {
+        self.transactions_stream(account_id, options.clone())
+            .try_collect()
+            .await
+    }
+
+    /// Transactions matching `options`, newest first. Pages are requested as the stream is
+    /// polled, and it ends after the last page or the first error.
+    pub fn transactions_stream<'a>(
+        &'a self,
+        account_id: &AccountId,
+        options: TransactionOptions,
+    ) -> impl Stream<Item = Result<Transaction, RequestError>> + 'a {
+        let account_id = account_id.clone();
+        paginate(move |offset| {
+            let spec = endpoints::transactions(&account_id, &options, offset);
+            async move {
+                let resp = self
+                    .fetch_spec::<TransactionsResponse>(&spec, Idempotency::Idempotent)
+                    .await?;
+                Ok(Page {
+                    items: resp.transactions,
+                    has_more: resp.has_more,
+                })
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config};
+    use futures_util::StreamExt;
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn transaction(id: &str) -> String {
+        format!(
+            r#"{{"id": "{}", "accountId": "1234", "transactionType": "BUY", "date": "2026-10-01", "description": "Köp", "orderbookId": "5269", "volume": 10, "price": 150.5, "amount": -1505, "currency": "SEK"}}"#,
+            id
+        )
+    }
+
+    async fn mock_page(mock_server: &MockServer, offset: &str, ids: &[&str], has_more: bool) {
+        let transactions: Vec<String> = ids.iter().map(|id| transaction(id)).collect();
+        let responder = ResponseTemplate::new(200).set_body_string(format!(
+            r#"{{"transactions": [{}], "hasMore": {}}}"#,
+            transactions.join(","),
+            has_more
+        ));
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/1234/transactions"))
+            .and(query_param("offset", offset))
+            .and(query_param("limit", "2"))
+            .respond_with(responder)
+            .expect(1)
+            .mount(mock_server)
+            .await;
+    }
+
+    fn two_per_page() -> TransactionOptions {
+        TransactionOptions {
+            page_size: 2,
+            ..TransactionOptions::default()
+        }
+    }
+
+    #[test]
+    fn parses_comma_decimal_strings() {
+        let transaction: Transaction = serde_json::from_str(
+            r#"{"id": "t-1", "accountId": "1234", "transactionType": "BUY", "date": "2019-03-01", "description": "Köp", "orderbookId": "5269", "volume": "10", "price": "150,50", "amount": "-1 505,00", "currency": "SEK"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(transaction.volume, Some(10.0));
+        assert_eq!(transaction.price, Some(150.5));
+        assert_eq!(transaction.amount, -1505.0);
+    }
+
+    #[test]
+    fn malformed_amount_names_the_field() {
+        let error = serde_json::from_str::<Transaction>(
+            r#"{"id": "t-1", "accountId": "1234", "transactionType": "FEE", "date": "2019-03-01", "description": "Avgift", "amount": "-", "currency": "SEK"}"#,
+        )
+        .unwrap_err();
+
+        assert!(error.to_string().contains("`amount`"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(
+            client
+                .get_transactions(&AccountId::from("1234"), &TransactionOptions::default())
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn streams_every_page_in_order() {
+        let mock_server = MockServer::start().await;
+        mock_page(&mock_server, "0", &["t-1", "t-2"], true).await;
+        mock_page(&mock_server, "2", &["t-3", "t-4"], true).await;
+        mock_page(&mock_server, "4", &["t-5"], false).await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let transactions: Vec<Transaction> = assert_ok!(
+            client
+                .transactions_stream(&AccountId::from("1234"), two_per_page())
+                .try_collect()
+                .await
+        );
+
+        let ids: Vec<&str> = transactions.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, ["t-1", "t-2", "t-3", "t-4", "t-5"]);
+        assert_eq!(transactions[0].transaction_type, TransactionType::Buy);
+        assert_eq!(transactions[0].currency, Currency::SEK);
+        assert_eq!(
+            transactions[0].orderbook_id,
+            Some(OrderbookId::from("5269"))
+        );
+    }
+
+    #[tokio::test]
+    async fn fetches_pages_lazily() {
+        let mock_server = MockServer::start().await;
+        mock_page(&mock_server, "0", &["t-1", "t-2"], true).await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/1234/transactions"))
+            .and(query_param("offset", "2"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let first: Vec<_> = client
+            .transactions_stream(&AccountId::from("1234"), two_per_page())
+            .take(2)
+            .collect()
+            .await;
+        assert_eq!(first.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn stops_after_error_mid_stream() {
+        let mock_server = MockServer::start().await;
+        mock_page(&mock_server, "0", &["t-1", "t-2"], true).await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/1234/transactions"))
+            .and(query_param("offset", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("not json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let items: Vec<_> = client
+            .transactions_stream(&AccountId::from("1234"), two_per_page())
+            .collect()
+            .await;
+        assert_eq!(items.len(), 3);
+        assert_ok!(&items[1]);
+        assert_err!(&items[2]);
+    }
+
+    #[tokio::test]
+    async fn get_transactions_collects_all_pages() {
+        let mock_server = MockServer::start().await;
+        mock_page(&mock_server, "0", &["t-1", "t-2"], true).await;
+        mock_page(&mock_server, "2", &[], false).await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let transactions = assert_ok!(
+            client
+                .get_transactions(&AccountId::from("1234"), &two_per_page())
+                .await
+        );
+        assert_eq!(transactions.len(), 2);
+    }
+}
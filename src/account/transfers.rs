@@ -0,0 +1,139 @@
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::types::AccountId;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransferDirection {
+    Deposit,
+    Withdrawal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransferStatus {
+    Pending,
+    Completed,
+    Cancelled,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransfersResponse {
+    #[serde(default)]
+    transfers: Vec<Transfer>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transfer {
+    pub id: String,
+    pub amount: f64,
+    pub direction: TransferDirection,
+    pub status: TransferStatus,
+    pub initiated_date: NaiveDate,
+    #[serde(default)]
+    pub expected_settlement_date: Option<NaiveDate>,
+}
+
+impl Transfer {
+    pub fn is_pending(&self) -> bool {
+        self.status == TransferStatus::Pending
+    }
+}
+
+impl Client {
+    /// Deposits and withdrawals that are in flight, together with the ones completed within
+    /// Avanza's lookback window. Use `Transfer::is_pending` to tell them apart.
+    pub async fn get_pending_transfers(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Vec<Transfer>, RequestError> {
+        let resp = self
+            .fetch_spec::<TransfersResponse>(
+                &endpoints::pending_transfers(account_id),
+                Idempotency::Idempotent,
+            )
+            .await?;
+        Ok(resp.transfers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.get_pending_transfers(&AccountId::from("1234")).await);
+    }
+
+    #[tokio::test]
+    async fn can_get_pending_transfers() {
+        let mock_server = MockServer::start().await;
+
+        let responder = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{
+                "transfers": [
+                    {
+                        "id": "t-1",
+                        "amount": 5000,
+                        "direction": "DEPOSIT",
+                        "status": "PENDING",
+                        "initiatedDate": "2026-10-13",
+                        "expectedSettlementDate": "2026-10-15"
+                    },
+                    {
+                        "id": "t-2",
+                        "amount": 1200.5,
+                        "direction": "WITHDRAWAL",
+                        "status": "COMPLETED",
+                        "initiatedDate": "2026-10-08",
+                        "expectedSettlementDate": null
+                    }
+                ]
+            }"#,
+        ));
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/1234/transfers/status"))
+            .respond_with(responder)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let transfers = assert_ok!(client.get_pending_transfers(&AccountId::from("1234")).await);
+
+        assert_eq!(transfers.len(), 2);
+
+        let deposit = &transfers[0];
+        assert!(deposit.is_pending());
+        assert_eq!(deposit.direction, TransferDirection::Deposit);
+        assert_eq!(deposit.amount, 5000.0);
+        assert_eq!(
+            deposit.expected_settlement_date,
+            Some(NaiveDate::from_ymd_opt(2026, 10, 15).unwrap())
+        );
+
+        let withdrawal = &transfers[1];
+        assert!(!withdrawal.is_pending());
+        assert_eq!(withdrawal.direction, TransferDirection::Withdrawal);
+        assert_eq!(withdrawal.status, TransferStatus::Completed);
+        assert_eq!(withdrawal.expected_settlement_date, None);
+    }
+}
@@ -0,0 +1,425 @@
+//! Allocation and exposure of a portfolio, computed from [`PositionsResponse`] without any
+//! requests.
+//!
+//! All values are in SEK, as Avanza reports position values. Passing an [`Overview`] counts
+//! its `total_balance` as cash: a SEK holding that dilutes the weights of the positions.
+//! Weights of an empty or zero-valued portfolio are zero rather than undefined.
+
+use std::collections::HashMap;
+
+use crate::portfolio::overview::Overview;
+use crate::portfolio::positions::PositionsResponse;
+use crate::types::{Currency, InstrumentType, OrderbookId, Percent};
+
+/// One instrument's share of the portfolio, summed over the accounts holding it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionWeight {
+    pub orderbook_id: OrderbookId,
+    pub name: String,
+    pub value: f64,
+    pub weight: Percent,
+}
+
+/// The share of the portfolio in one currency or asset type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExposureShare<K> {
+    pub key: K,
+    pub value: f64,
+    pub weight: Percent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssetType {
+    Instrument(InstrumentType),
+    Cash,
+}
+
+/// Every instrument's share of the total, largest first.
+pub fn weights(positions: &PositionsResponse, cash: Option<&Overview>) -> Vec<PositionWeight> {
+    let mut by_orderbook: Vec<PositionWeight> = Vec::new();
+    let mut index = HashMap::new();
+    for position in positions
+        .instrument_positions
+        .iter()
+        .flat_map(|group| &group.positions)
+    {
+        let orderbook_id = OrderbookId(position.orderbook_id.clone());
+        match index.get(&orderbook_id) {
+            Some(&i) => {
+                let entry: &mut PositionWeight = &mut by_orderbook[i];
+                entry.value += position.value;
+            }
+            None => {
+                index.insert(orderbook_id.clone(), by_orderbook.len());
+                by_orderbook.push(PositionWeight {
+                    orderbook_id,
+                    name: position.name.clone(),
+                    value: position.value,
+                    weight: Percent::default(),
+                });
+            }
+        }
+    }
+
+    let total = total_value(positions, cash);
+    for entry in &mut by_orderbook {
+        entry.weight = weight(entry.value, total);
+    }
+    sort_by_value(&mut by_orderbook, |entry| entry.value);
+    by_orderbook
+}
+
+/// Value per currency the instruments are traded in, largest first. Cash counts as SEK.
+pub fn exposure_by_currency(
+    positions: &PositionsResponse,
+    cash: Option<&Overview>,
+) -> Vec<ExposureShare<Currency>> {
+    let holdings = positions
+        .instrument_positions
+        .iter()
+        .flat_map(|group| &group.positions)
+        .map(|position| (Currency::new(&position.currency), position.value))
+        .chain(cash.map(|overview| (Currency::SEK, overview.total_balance)));
+    exposure(holdings, total_value(positions, cash))
+}
+
+/// Value per instrument type, largest first, with cash as its own [`AssetType::Cash`].
+pub fn exposure_by_instrument_type(
+    positions: &PositionsResponse,
+    cash: Option<&Overview>,
+) -> Vec<ExposureShare<AssetType>> {
+    let holdings = positions
+        .instrument_positions
+        .iter()
+        .flat_map(|group| {
+            let instrument_type = AssetType::Instrument(
+                group
+                    .instrument_type
+                    .parse()
+                    .unwrap_or(InstrumentType::Other),
+            );
+            group
+                .positions
+                .iter()
+                .map(move |position| (instrument_type, position.value))
+        })
+        .chain(cash.map(|overview| (AssetType::Cash, overview.total_balance)));
+    exposure(holdings, total_value(positions, cash))
+}
+
+/// The combined weight of the `n` largest instruments.
+pub fn top_concentration(
+    n: usize,
+    positions: &PositionsResponse,
+    cash: Option<&Overview>,
+) -> Percent {
+    let total = total_value(positions, cash);
+    let top: f64 = weights(positions, cash)
+        .iter()
+        .take(n)
+        .map(|entry| entry.value)
+        .sum();
+    weight(top, total)
+}
+
+fn total_value(positions: &PositionsResponse, cash: Option<&Overview>) -> f64 {
+    let invested: f64 = positions
+        .instrument_positions
+        .iter()
+        .flat_map(|group| &group.positions)
+        .map(|position| position.value)
+        .sum();
+    invested + cash.map_or(0.0, |overview| overview.total_balance)
+}
+
+fn weight(value: f64, total: f64) -> Percent {
+    if total == 0.0 {
+        Percent(0.0)
+    } else {
+        Percent(value / total * 100.0)
+    }
+}
+
+/// Groups `holdings` by key, keeping the order keys first appear in for equal values.
+fn exposure<K: Clone + Eq + std::hash::Hash>(
+    holdings: impl Iterator<Item = (K, f64)>,
+    total: f64,
+) -> Vec<ExposureShare<K>> {
+    let mut exposures: Vec<ExposureShare<K>> = Vec::new();
+    let mut index = HashMap::new();
+    for (key, value) in holdings {
+        match index.get(&key) {
+            Some(&i) => {
+                let entry: &mut ExposureShare<K> = &mut exposures[i];
+                entry.value += value;
+            }
+            None => {
+                index.insert(key.clone(), exposures.len());
+                exposures.push(ExposureShare {
+                    key,
+                    value,
+                    weight: Percent::default(),
+                });
+            }
+        }
+    }
+    for entry in &mut exposures {
+        entry.weight = weight(entry.value, total);
+    }
+    sort_by_value(&mut exposures, |entry| entry.value);
+    exposures
+}
+
+fn sort_by_value<T>(entries: &mut [T], value: impl Fn(&T) -> f64) {
+    entries.sort_by(|a, b| value(b).total_cmp(&value(a)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::portfolio::positions::{InstrumentPositions, Positions};
+
+    fn position(orderbook_id: &str, account_id: &str, currency: &str, value: f64) -> Positions {
+        Positions {
+            account_id: String::from(account_id),
+            account_name: String::from("Konto"),
+            account_type: String::from("Investeringssparkonto"),
+            acquired_value: value,
+            average_acquired_price: 1.0,
+            change: 0.0,
+            change_percent: 0.0,
+            currency: String::from(currency),
+            depositable: true,
+            flag_code: String::from("SE"),
+            last_price: 1.0,
+            last_price_updated: String::from("2026-10-15T09:00:00"),
+            name: format!("Instrument {}", orderbook_id),
+            orderbook_id: String::from(orderbook_id),
+            profit: 0.0,
+            profit_percent: 0.0,
+            tradable: true,
+            value,
+            volume: 1,
+        }
+    }
+
+    fn group(instrument_type: &str, positions: Vec<Positions>) -> InstrumentPositions {
+        InstrumentPositions {
+            instrument_type: String::from(instrument_type),
+            total_value: positions.iter().map(|p| p.value).sum(),
+            positions,
+            todays_profit_percent: 0.0,
+            total_profit_percent: 0.0,
+            total_profit_value: 0.0,
+        }
+    }
+
+    fn portfolio(instrument_positions: Vec<InstrumentPositions>) -> PositionsResponse {
+        PositionsResponse {
+            instrument_positions,
+            total_profit: 0.0,
+            total_profit_percent: 0.0,
+            total_balance: 0.0,
+            total_own_capital: 0.0,
+            total_buying_power: 0.0,
+        }
+    }
+
+    fn cash(amount: f64) -> Overview {
+        Overview {
+            accounts: Vec::new(),
+            total_own_capital: 0.0,
+            total_buying_power: amount,
+            total_balance: amount,
+            total_performance_percent: None,
+        }
+    }
+
+    // 6000 + 2000 SEK in stocks, one of them held in two accounts, and 1500 USD + 500 SEK in
+    // funds: 10000 SEK in total.
+    fn mixed() -> PositionsResponse {
+        portfolio(vec![
+            group(
+                "STOCK",
+                vec![
+                    position("1", "a", "SEK", 4000.0),
+                    position("2", "a", "USD", 1000.0),
+                    position("1", "b", "SEK", 2000.0),
+                ],
+            ),
+            group(
+                "FUND",
+                vec![
+                    position("3", "a", "USD", 1500.0),
+                    position("4", "a", "SEK", 500.0),
+                    position("5", "b", "SEK", 1000.0),
+                ],
+            ),
+        ])
+    }
+
+    fn assert_close(actual: Percent, expected: f64) {
+        assert!(
+            (actual.value() - expected).abs() < 1e-9,
+            "expected {} %, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn weights_sum_instruments_over_accounts() {
+        let weights = weights(&mixed(), None);
+
+        let ids: Vec<&str> = weights.iter().map(|w| w.orderbook_id.0.as_str()).collect();
+        assert_eq!(ids, ["1", "3", "2", "5", "4"]);
+        assert_eq!(weights[0].value, 6000.0);
+        assert_eq!(weights[0].name, "Instrument 1");
+        assert_close(weights[0].weight, 60.0);
+        assert_close(weights[1].weight, 15.0);
+        assert_close(weights[2].weight, 10.0);
+        assert_close(weights[3].weight, 10.0);
+        assert_close(weights[4].weight, 5.0);
+    }
+
+    #[test]
+    fn cash_dilutes_weights() {
+        // 10000 invested plus 2500 cash.
+        let weights = weights(&mixed(), Some(&cash(2500.0)));
+
+        assert_close(weights[0].weight, 48.0);
+        assert_close(weights[4].weight, 4.0);
+        let total: f64 = weights.iter().map(|w| w.weight.value()).sum();
+        assert!((total - 80.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn exposure_by_currency_groups_positions() {
+        let exposure = exposure_by_currency(&mixed(), None);
+
+        assert_eq!(exposure.len(), 2);
+        assert_eq!(exposure[0].key, Currency::SEK);
+        assert_eq!(exposure[0].value, 7500.0);
+        assert_close(exposure[0].weight, 75.0);
+        assert_eq!(exposure[1].key, Currency::USD);
+        assert_eq!(exposure[1].value, 2500.0);
+        assert_close(exposure[1].weight, 25.0);
+    }
+
+    #[test]
+    fn cash_is_sek_exposure() {
+        // SEK: 7500 + 2500 of 12500.
+        let exposure = exposure_by_currency(&mixed(), Some(&cash(2500.0)));
+
+        assert_eq!(exposure[0].key, Currency::SEK);
+        assert_eq!(exposure[0].value, 10000.0);
+        assert_close(exposure[0].weight, 80.0);
+        assert_close(exposure[1].weight, 20.0);
+    }
+
+    #[test]
+    fn exposure_by_instrument_type_groups_positions() {
+        let exposure = exposure_by_instrument_type(&mixed(), Some(&cash(2500.0)));
+
+        assert_eq!(
+            exposure.iter().map(|e| e.key).collect::<Vec<_>>(),
+            [
+                AssetType::Instrument(InstrumentType::Stock),
+                AssetType::Instrument(InstrumentType::Fund),
+                AssetType::Cash,
+            ]
+        );
+        // 7000, 3000 and 2500 of 12500.
+        assert_close(exposure[0].weight, 56.0);
+        assert_close(exposure[1].weight, 24.0);
+        assert_close(exposure[2].weight, 20.0);
+    }
+
+    #[test]
+    fn unknown_instrument_type_is_other() {
+        let positions = portfolio(vec![group(
+            "PREMIUM_BOND",
+            vec![position("1", "a", "SEK", 10.0)],
+        )]);
+
+        let exposure = exposure_by_instrument_type(&positions, None);
+
+        assert_eq!(
+            exposure[0].key,
+            AssetType::Instrument(InstrumentType::Other)
+        );
+    }
+
+    #[test]
+    fn instrument_type_is_case_insensitive() {
+        let positions = portfolio(vec![group(
+            "exchange_traded_fund",
+            vec![position("1", "a", "SEK", 10.0)],
+        )]);
+
+        let exposure = exposure_by_instrument_type(&positions, None);
+
+        assert_eq!(
+            exposure[0].key,
+            AssetType::Instrument(InstrumentType::ExchangeTradedFund)
+        );
+    }
+
+    #[test]
+    fn top_concentration_takes_largest_instruments() {
+        let positions = mixed();
+
+        assert_close(top_concentration(0, &positions, None), 0.0);
+        assert_close(top_concentration(1, &positions, None), 60.0);
+        assert_close(top_concentration(3, &positions, None), 85.0);
+        assert_close(top_concentration(10, &positions, None), 100.0);
+        assert_close(top_concentration(2, &positions, Some(&cash(2500.0))), 60.0);
+    }
+
+    #[test]
+    fn single_position_is_everything() {
+        let positions = portfolio(vec![group(
+            "STOCK",
+            vec![position("1", "a", "SEK", 1234.5)],
+        )]);
+
+        let weights = weights(&positions, None);
+        assert_eq!(weights.len(), 1);
+        assert_close(weights[0].weight, 100.0);
+        assert_close(top_concentration(1, &positions, None), 100.0);
+        assert_close(exposure_by_currency(&positions, None)[0].weight, 100.0);
+    }
+
+    #[test]
+    fn zero_value_portfolio_has_zero_weights() {
+        let positions = portfolio(vec![group(
+            "STOCK",
+            vec![
+                position("1", "a", "SEK", 0.0),
+                position("2", "a", "SEK", 0.0),
+            ],
+        )]);
+
+        assert!(weights(&positions, None)
+            .iter()
+            .all(|w| w.weight == Percent(0.0)));
+        assert_eq!(top_concentration(1, &positions, None), Percent(0.0));
+        assert_eq!(
+            exposure_by_currency(&positions, None)[0].weight,
+            Percent(0.0)
+        );
+        assert_eq!(
+            exposure_by_instrument_type(&positions, Some(&cash(0.0))).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn empty_portfolio_has_no_weights() {
+        let positions = portfolio(Vec::new());
+
+        assert!(weights(&positions, None).is_empty());
+        assert!(exposure_by_currency(&positions, None).is_empty());
+        assert_eq!(top_concentration(3, &positions, None), Percent(0.0));
+    }
+}
@@ -0,0 +1,418 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use crate::account::accounts::Account;
+use crate::account::cash_balance::CashBalance;
+use crate::account::courtage::CourtageInfo;
+use crate::account::transactions::{Transaction, TransactionOptions};
+use crate::account::transfers::Transfer;
+use crate::batch::BatchResult;
+use crate::client::{AuthenticateResponse, CallOptions, Client};
+use crate::error::RequestError;
+use crate::fund::holdings::{FundHoldings, LookThrough};
+use crate::market::chart::ChartData;
+use crate::market::exchange_rates::ExchangeRate;
+use crate::market::markets::Market;
+use crate::market::offers::Offer;
+use crate::market::order_depth::OrderDepth;
+use crate::market::orderbook::Orderbook;
+use crate::market::ownership::Ownership;
+use crate::market::price_history::{Candle, Resolution};
+use crate::market::quotes::Quote;
+use crate::market::suggest::Suggestion;
+use crate::market::trades::Trade;
+use crate::market::watchlists::{Watchlist, WatchlistId, WatchlistQuote};
+use crate::order::new_order::NewOrder;
+use crate::order::place::OrderResponse;
+use crate::portfolio::deals_and_orders::DealsAndOrdersResponse;
+use crate::portfolio::overview::Overview;
+use crate::portfolio::positions::PositionsResponse;
+use crate::portfolio::snapshot::Snapshot;
+use crate::stock::dividends::Dividend;
+use crate::stock::key_ratios::KeyRatios;
+use crate::stock::short_positions::ShortPositions;
+use crate::types::{AccountId, OrderbookId};
+
+/// The REST endpoints of [`Client`]. Take this instead of `Client` to be able to pass an
+/// in-memory fake in tests, e.g. `test_util::MockClient` from the `test-util` feature.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait AvanzaApi: Send + Sync {
+    async fn authenticate(&self) -> Result<AuthenticateResponse, RequestError>;
+
+    async fn get_positions(&self) -> Result<PositionsResponse, RequestError>;
+
+    async fn get_overview(&self) -> Result<Overview, RequestError>;
+
+    async fn get_deals_and_orders(&self) -> Result<DealsAndOrdersResponse, RequestError>;
+
+    /// Overview, positions and deals and orders, requested concurrently.
+    async fn get_snapshot(&self) -> Result<Snapshot, RequestError> {
+        let (overview, positions, deals_and_orders) = tokio::try_join!(
+            self.get_overview(),
+            self.get_positions(),
+            self.get_deals_and_orders()
+        )?;
+        Ok(Snapshot {
+            overview,
+            positions,
+            deals_and_orders,
+        })
+    }
+
+    async fn get_accounts(&self) -> Result<Vec<Account>, RequestError>;
+
+    async fn get_cash_balance(&self, account_id: &AccountId) -> Result<CashBalance, RequestError>;
+
+    async fn get_courtage_info(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Option<CourtageInfo>, RequestError>;
+
+    async fn get_pending_transfers(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Vec<Transfer>, RequestError>;
+
+    async fn get_transactions(
+        &self,
+        account_id: &AccountId,
+        options: &TransactionOptions,
+    ) -> Result<Vec<Transaction>, RequestError>;
+
+    async fn get_fund_holdings(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<FundHoldings, RequestError>;
+
+    async fn portfolio_look_through(
+        &self,
+        positions: &PositionsResponse,
+    ) -> Result<LookThrough, RequestError>;
+
+    async fn get_markets(&self) -> Result<Vec<Market>, RequestError>;
+
+    async fn get_offers(&self) -> Result<Vec<Offer>, RequestError>;
+
+    async fn get_exchange_rates(&self) -> Result<Vec<ExchangeRate>, RequestError>;
+
+    async fn get_orderbooks(
+        &self,
+        orderbook_ids: &[OrderbookId],
+    ) -> Result<BatchResult<Quote>, RequestError>;
+
+    async fn get_orderbook(&self, orderbook_id: &OrderbookId) -> Result<Orderbook, RequestError>;
+
+    async fn get_order_depth(&self, orderbook_id: &OrderbookId)
+        -> Result<OrderDepth, RequestError>;
+
+    async fn get_trades(&self, orderbook_id: &OrderbookId) -> Result<Vec<Trade>, RequestError>;
+
+    async fn get_ownership(&self, orderbook_id: &OrderbookId) -> Result<Ownership, RequestError>;
+
+    async fn get_price_history(
+        &self,
+        orderbook_id: &OrderbookId,
+        from: NaiveDate,
+        to: NaiveDate,
+        resolution: Resolution,
+    ) -> Result<Vec<Candle>, RequestError>;
+
+    async fn get_chart_data(
+        &self,
+        orderbook_id: &OrderbookId,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<ChartData, RequestError>;
+
+    async fn suggest(&self, prefix: &str, limit: usize) -> Result<Vec<Suggestion>, RequestError>;
+
+    async fn get_watchlists(&self) -> Result<Vec<Watchlist>, RequestError>;
+
+    async fn get_watchlist(&self, watchlist_id: &WatchlistId) -> Result<Watchlist, RequestError>;
+
+    async fn get_watchlist_quotes(
+        &self,
+        watchlist_id: &WatchlistId,
+    ) -> Result<Vec<WatchlistQuote>, RequestError>;
+
+    async fn get_dividend_history(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<Vec<Dividend>, RequestError>;
+
+    async fn get_key_ratios(&self, orderbook_id: &OrderbookId) -> Result<KeyRatios, RequestError>;
+
+    async fn get_short_positions(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<ShortPositions, RequestError>;
+
+    async fn place_order(
+        &self,
+        order: &NewOrder,
+        options: CallOptions,
+    ) -> Result<OrderResponse, RequestError>;
+
+    async fn delete_order(
+        &self,
+        account_id: &AccountId,
+        order_id: &str,
+    ) -> Result<OrderResponse, RequestError>;
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl AvanzaApi for Client {
+    async fn authenticate(&self) -> Result<AuthenticateResponse, RequestError> {
+        Client::authenticate(self).await
+    }
+
+    async fn get_positions(&self) -> Result<PositionsResponse, RequestError> {
+        Client::get_positions(self).await
+    }
+
+    async fn get_overview(&self) -> Result<Overview, RequestError> {
+        Client::get_overview(self).await
+    }
+
+    async fn get_deals_and_orders(&self) -> Result<DealsAndOrdersResponse, RequestError> {
+        Client::get_deals_and_orders(self).await
+    }
+
+    async fn get_snapshot(&self) -> Result<Snapshot, RequestError> {
+        Client::get_snapshot(self).await
+    }
+
+    async fn get_accounts(&self) -> Result<Vec<Account>, RequestError> {
+        Client::get_accounts(self).await
+    }
+
+    async fn get_cash_balance(&self, account_id: &AccountId) -> Result<CashBalance, RequestError> {
+        Client::get_cash_balance(self, account_id).await
+    }
+
+    async fn get_courtage_info(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Option<CourtageInfo>, RequestError> {
+        Client::get_courtage_info(self, account_id).await
+    }
+
+    async fn get_pending_transfers(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Vec<Transfer>, RequestError> {
+        Client::get_pending_transfers(self, account_id).await
+    }
+
+    async fn get_transactions(
+        &self,
+        account_id: &AccountId,
+        options: &TransactionOptions,
+    ) -> Result<Vec<Transaction>, RequestError> {
+        Client::get_transactions(self, account_id, options).await
+    }
+
+    async fn get_fund_holdings(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<FundHoldings, RequestError> {
+        Client::get_fund_holdings(self, orderbook_id).await
+    }
+
+    async fn portfolio_look_through(
+        &self,
+        positions: &PositionsResponse,
+    ) -> Result<LookThrough, RequestError> {
+        Client::portfolio_look_through(self, positions).await
+    }
+
+    async fn get_markets(&self) -> Result<Vec<Market>, RequestError> {
+        Client::get_markets(self).await
+    }
+
+    async fn get_offers(&self) -> Result<Vec<Offer>, RequestError> {
+        Client::get_offers(self).await
+    }
+
+    async fn get_exchange_rates(&self) -> Result<Vec<ExchangeRate>, RequestError> {
+        Client::get_exchange_rates(self).await
+    }
+
+    async fn get_orderbooks(
+        &self,
+        orderbook_ids: &[OrderbookId],
+    ) -> Result<BatchResult<Quote>, RequestError> {
+        Client::get_orderbooks(self, orderbook_ids).await
+    }
+
+    async fn get_orderbook(&self, orderbook_id: &OrderbookId) -> Result<Orderbook, RequestError> {
+        Client::get_orderbook(self, orderbook_id).await
+    }
+
+    async fn get_order_depth(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<OrderDepth, RequestError> {
+        Client::get_order_depth(self, orderbook_id).await
+    }
+
+    async fn get_trades(&self, orderbook_id: &OrderbookId) -> Result<Vec<Trade>, RequestError> {
+        Client::get_trades(self, orderbook_id).await
+    }
+
+    async fn get_ownership(&self, orderbook_id: &OrderbookId) -> Result<Ownership, RequestError> {
+        Client::get_ownership(self, orderbook_id).await
+    }
+
+    async fn get_price_history(
+        &self,
+        orderbook_id: &OrderbookId,
+        from: NaiveDate,
+        to: NaiveDate,
+        resolution: Resolution,
+    ) -> Result<Vec<Candle>, RequestError> {
+        Client::get_price_history(self, orderbook_id, from, to, resolution).await
+    }
+
+    async fn get_chart_data(
+        &self,
+        orderbook_id: &OrderbookId,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<ChartData, RequestError> {
+        Client::get_chart_data(self, orderbook_id, from, to).await
+    }
+
+    async fn suggest(&self, prefix: &str, limit: usize) -> Result<Vec<Suggestion>, RequestError> {
+        Client::suggest(self, prefix, limit).await
+    }
+
+    async fn get_watchlists(&self) -> Result<Vec<Watchlist>, RequestError> {
+        Client::get_watchlists(self).await
+    }
+
+    async fn get_watchlist(&self, watchlist_id: &WatchlistId) -> Result<Watchlist, RequestError> {
+        Client::get_watchlist(self, watchlist_id).await
+    }
+
+    async fn get_watchlist_quotes(
+        &self,
+        watchlist_id: &WatchlistId,
+    ) -> Result<Vec<WatchlistQuote>, RequestError> {
+        Client::get_watchlist_quotes(self, watchlist_id).await
+    }
+
+    async fn get_dividend_history(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<Vec<Dividend>, RequestError> {
+        Client::get_dividend_history(self, orderbook_id).await
+    }
+
+    async fn get_key_ratios(&self, orderbook_id: &OrderbookId) -> Result<KeyRatios, RequestError> {
+        Client::get_key_ratios(self, orderbook_id).await
+    }
+
+    async fn get_short_positions(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<ShortPositions, RequestError> {
+        Client::get_short_positions(self, orderbook_id).await
+    }
+
+    async fn place_order(
+        &self,
+        order: &NewOrder,
+        options: CallOptions,
+    ) -> Result<OrderResponse, RequestError> {
+        Client::place_order(self, order, options).await
+    }
+
+    async fn delete_order(
+        &self,
+        account_id: &AccountId,
+        order_id: &str,
+    ) -> Result<OrderResponse, RequestError> {
+        Client::delete_order(self, account_id, order_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::MockClient;
+
+    use super::*;
+    use crate::test_support::config;
+    use tokio_test::{assert_err, assert_ok};
+
+    async fn total_buying_power(api: &impl AvanzaApi) -> Result<f64, RequestError> {
+        api.authenticate().await?;
+        Ok(api.get_overview().await?.total_buying_power)
+    }
+
+    #[tokio::test]
+    async fn client_requires_auth_through_trait() {
+        let client: Box<dyn AvanzaApi> = Box::new(
+            Client::builder()
+                .config(config())
+                .api_url(String::from("http://fake-url.com"))
+                .build()
+                .unwrap(),
+        );
+        assert_err!(client.get_positions().await);
+        assert_err!(client.get_overview().await);
+    }
+
+    #[tokio::test]
+    async fn generic_code_runs_against_mock() {
+        let mock = MockClient::new().on_get_overview(|| {
+            Ok(Overview {
+                accounts: Vec::new(),
+                total_own_capital: 1000.0,
+                total_buying_power: 250.0,
+                total_balance: 1000.0,
+                total_performance_percent: None,
+            })
+        });
+
+        assert_eq!(assert_ok!(total_buying_power(&mock).await), 250.0);
+        assert_eq!(mock.methods(), ["authenticate", "get_overview"]);
+    }
+
+    #[tokio::test]
+    async fn snapshot_is_composed_from_endpoints() {
+        let mock = MockClient::new()
+            .on_get_overview(|| {
+                Ok(Overview {
+                    accounts: Vec::new(),
+                    total_own_capital: 1000.0,
+                    total_buying_power: 250.0,
+                    total_balance: 1000.0,
+                    total_performance_percent: None,
+                })
+            })
+            .on_get_positions(|| {
+                Ok(PositionsResponse {
+                    instrument_positions: Vec::new(),
+                    total_profit: 0.0,
+                    total_profit_percent: 0.0,
+                    total_balance: 1000.0,
+                    total_own_capital: 1000.0,
+                    total_buying_power: 250.0,
+                })
+            })
+            .on_get_deals_and_orders(|| Ok(DealsAndOrdersResponse::default()));
+
+        let snapshot = assert_ok!(mock.get_snapshot().await);
+        assert_eq!(snapshot.positions.total_balance, 1000.0);
+
+        let mut calls = mock.methods();
+        calls.sort();
+        assert_eq!(
+            calls,
+            ["get_deals_and_orders", "get_overview", "get_positions"]
+        );
+    }
+}
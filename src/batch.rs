@@ -0,0 +1,193 @@
+//! Fetching many things at once with bounded concurrency.
+
+use std::future::Future;
+
+use futures_util::{stream, StreamExt};
+
+use crate::error::RequestError;
+use crate::types::OrderbookId;
+
+/// Calls `fetch` for every input with at most `concurrency` calls in flight, and returns the
+/// results in the order of the inputs. A failed call only fails its own slot.
+///
+/// Calls through a [`Client`](crate::client::Client) still wait for its rate limit, so a
+/// concurrency above what the rate limit allows only queues the extra calls there.
+pub async fn fetch_many<I, T, F, Fut>(
+    inputs: I,
+    concurrency: usize,
+    mut fetch: F,
+) -> Vec<Result<T, RequestError>>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future<Output = Result<T, RequestError>>,
+{
+    let mut results: Vec<(usize, Result<T, RequestError>)> =
+        stream::iter(inputs.into_iter().enumerate())
+            .map(|(index, input)| {
+                let result = fetch(input);
+                async move { (index, result.await) }
+            })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await;
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// What a batched request returned for one id, see [`BatchResult::get`].
+#[derive(Debug)]
+pub enum BatchOutcome<'a, T> {
+    Found(&'a T),
+    /// The request for the id went through, but Avanza doesn't know it.
+    NotFound,
+    /// The request for the id failed, together with the other ids sent with it.
+    Failed(&'a RequestError),
+}
+
+/// The ids sent in one failed request and why it failed.
+#[derive(Debug)]
+pub struct BatchFailure {
+    pub ids: Vec<OrderbookId>,
+    pub error: RequestError,
+}
+
+/// The results of a request that was split into several, e.g.
+/// [`Client::get_orderbooks`](crate::client::Client::get_orderbooks). A failed request only
+/// fails the ids that were sent in it.
+#[derive(Debug)]
+pub struct BatchResult<T> {
+    found: Vec<(OrderbookId, T)>,
+    failures: Vec<BatchFailure>,
+}
+
+impl<T> Default for BatchResult<T> {
+    fn default() -> Self {
+        Self {
+            found: Vec::new(),
+            failures: Vec::new(),
+        }
+    }
+}
+
+impl<T> BatchResult<T> {
+    pub(crate) fn push_found(&mut self, id: OrderbookId, value: T) {
+        self.found.push((id, value));
+    }
+
+    pub(crate) fn push_failure(&mut self, ids: Vec<OrderbookId>, error: RequestError) {
+        self.failures.push(BatchFailure { ids, error });
+    }
+
+    pub fn get(&self, id: &OrderbookId) -> BatchOutcome<'_, T> {
+        if let Some((_, value)) = self.found.iter().find(|(found, _)| found == id) {
+            return BatchOutcome::Found(value);
+        }
+        match self
+            .failures
+            .iter()
+            .find(|failure| failure.ids.contains(id))
+        {
+            Some(failure) => BatchOutcome::Failed(&failure.error),
+            None => BatchOutcome::NotFound,
+        }
+    }
+
+    /// The values found, in the order of the requested ids.
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.found.iter().map(|(_, value)| value)
+    }
+
+    pub fn into_values(self) -> Vec<T> {
+        self.found.into_iter().map(|(_, value)| value).collect()
+    }
+
+    pub fn failures(&self) -> &[BatchFailure] {
+        &self.failures
+    }
+
+    /// Whether every request went through, whether or not every id was found.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// The values found, or the first failure's error if any request failed.
+    pub fn into_result(mut self) -> Result<Vec<T>, RequestError> {
+        if self.failures.is_empty() {
+            Ok(self.into_values())
+        } else {
+            Err(self.failures.swap_remove(0).error)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::test_support::authenticated_client;
+    use crate::types::OrderbookId;
+    use wiremock::matchers::{method, path_regex};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test(start_paused = true)]
+    async fn keeps_input_order() {
+        // Later inputs finish first.
+        let results = fetch_many(1..=5u64, 5, |n| async move {
+            tokio::time::sleep(Duration::from_millis(100 - n * 10)).await;
+            Ok(n)
+        })
+        .await;
+
+        let results: Vec<u64> = results.into_iter().map(Result::unwrap).collect();
+        assert_eq!(results, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn zero_concurrency_still_makes_progress() {
+        let results = fetch_many(["a", "b"], 0, |s| async move { Ok(s.len()) }).await;
+        assert_eq!(results.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn bounds_requests_in_flight() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex("^/_mobile/market/orderbook/[0-9]+$"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(
+                        r#"{"id":"1","name":"A","instrumentType":"STOCK","currency":"SEK"}"#,
+                    )
+                    .set_delay(Duration::from_millis(50)),
+            )
+            .expect(10)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let ids: Vec<OrderbookId> = (1..=10).map(|n| OrderbookId(n.to_string())).collect();
+        let results = fetch_many(&ids, 3, |id| {
+            let client = &client;
+            let in_flight = in_flight.clone();
+            let max_in_flight = max_in_flight.clone();
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(now, Ordering::SeqCst);
+                let result = client.get_orderbook(id).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                result
+            }
+        })
+        .await;
+
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,414 @@
+//! Synchronous client for callers that don't run an async runtime of their own.
+//!
+//! Every call drives the async [`crate::client::Client`] to completion on a runtime owned by
+//! the blocking client, so models and errors are the same as on the async side. Don't use it
+//! from within an async context, blocking on the inner runtime there panics. Push streaming
+//! is async only.
+//!
+//! To configure more than the credentials, build an async client with
+//! `client::Client::builder()` and convert it with `From`.
+
+use chrono::NaiveDate;
+use tokio::runtime::{Builder, Runtime};
+
+use crate::account::accounts::Account;
+use crate::account::cash_balance::CashBalance;
+use crate::account::courtage::CourtageInfo;
+use crate::account::transactions::{Transaction, TransactionOptions};
+use crate::account::transfers::Transfer;
+use crate::batch::BatchResult;
+use crate::client::{AuthenticateResponse, CallOptions, Config};
+use crate::currency::CurrencyConverter;
+use crate::error::RequestError;
+use crate::fund::holdings::{FundHoldings, LookThrough};
+use crate::market::chart::ChartData;
+use crate::market::exchange_rates::ExchangeRate;
+use crate::market::markets::Market;
+use crate::market::offers::Offer;
+use crate::market::order_depth::OrderDepth;
+use crate::market::orderbook::Orderbook;
+use crate::market::ownership::Ownership;
+use crate::market::price_history::{Candle, Resolution};
+use crate::market::quotes::Quote;
+use crate::market::suggest::Suggestion;
+use crate::market::trades::Trade;
+use crate::market::watchlists::{Watchlist, WatchlistId, WatchlistQuote};
+use crate::order::new_order::NewOrder;
+use crate::order::place::OrderResponse;
+use crate::order::precheck::OrderPrecheck;
+use crate::portfolio::deals_and_orders::DealsAndOrdersResponse;
+use crate::portfolio::overview::Overview;
+use crate::portfolio::positions::PositionsResponse;
+use crate::portfolio::snapshot::Snapshot;
+use crate::portfolio::wealth::WealthSummary;
+use crate::stock::dividends::Dividend;
+use crate::stock::key_ratios::KeyRatios;
+use crate::stock::short_positions::ShortPositions;
+use crate::types::{AccountId, OrderbookId};
+
+pub struct Client {
+    inner: crate::client::Client,
+    runtime: Runtime,
+}
+
+impl From<crate::client::Client> for Client {
+    fn from(inner: crate::client::Client) -> Self {
+        Client::from_async(inner)
+    }
+}
+
+impl Client {
+    pub fn new(config: Config) -> Self {
+        Self::from_async(crate::client::Client::new(config))
+    }
+
+    pub fn new_from_env() -> Self {
+        Self::from_async(crate::client::Client::new_from_env())
+    }
+
+    fn from_async(inner: crate::client::Client) -> Self {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to start runtime for blocking client");
+        Self { inner, runtime }
+    }
+
+    #[deprecated(note = "build the async client with `Client::builder()` and convert it")]
+    #[allow(deprecated)]
+    pub fn api_url(self, value: String) -> Self {
+        Self {
+            inner: self.inner.api_url(value),
+            ..self
+        }
+    }
+
+    #[deprecated(note = "build the async client with `Client::builder()` and convert it")]
+    #[allow(deprecated)]
+    pub fn user_agent(self, value: String) -> Self {
+        Self {
+            inner: self.inner.user_agent(value),
+            ..self
+        }
+    }
+
+    #[deprecated(note = "build the async client with `Client::builder()` and convert it")]
+    #[allow(deprecated)]
+    pub fn push_url(self, value: String) -> Self {
+        Self {
+            inner: self.inner.push_url(value),
+            ..self
+        }
+    }
+
+    pub fn warm_up(&self) -> Result<(), RequestError> {
+        self.runtime.block_on(self.inner.warm_up())
+    }
+
+    pub fn authenticate(&self) -> Result<AuthenticateResponse, RequestError> {
+        self.runtime.block_on(self.inner.authenticate())
+    }
+
+    pub fn get_positions(&self) -> Result<PositionsResponse, RequestError> {
+        self.runtime.block_on(self.inner.get_positions())
+    }
+
+    pub fn get_overview(&self) -> Result<Overview, RequestError> {
+        self.runtime.block_on(self.inner.get_overview())
+    }
+
+    pub fn get_deals_and_orders(&self) -> Result<DealsAndOrdersResponse, RequestError> {
+        self.runtime.block_on(self.inner.get_deals_and_orders())
+    }
+
+    pub fn get_snapshot(&self) -> Result<Snapshot, RequestError> {
+        self.runtime.block_on(self.inner.get_snapshot())
+    }
+
+    pub fn get_wealth_summary(&self) -> Result<WealthSummary, RequestError> {
+        self.runtime.block_on(self.inner.get_wealth_summary())
+    }
+
+    pub fn get_accounts(&self) -> Result<Vec<Account>, RequestError> {
+        self.runtime.block_on(self.inner.get_accounts())
+    }
+
+    pub fn get_cash_balance(&self, account_id: &AccountId) -> Result<CashBalance, RequestError> {
+        self.runtime
+            .block_on(self.inner.get_cash_balance(account_id))
+    }
+
+    pub fn get_courtage_info(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Option<CourtageInfo>, RequestError> {
+        self.runtime
+            .block_on(self.inner.get_courtage_info(account_id))
+    }
+
+    pub fn get_pending_transfers(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Vec<Transfer>, RequestError> {
+        self.runtime
+            .block_on(self.inner.get_pending_transfers(account_id))
+    }
+
+    pub fn get_transactions(
+        &self,
+        account_id: &AccountId,
+        options: &TransactionOptions,
+    ) -> Result<Vec<Transaction>, RequestError> {
+        self.runtime
+            .block_on(self.inner.get_transactions(account_id, options))
+    }
+
+    pub fn get_fund_holdings(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<FundHoldings, RequestError> {
+        self.runtime
+            .block_on(self.inner.get_fund_holdings(orderbook_id))
+    }
+
+    pub fn portfolio_look_through(
+        &self,
+        positions: &PositionsResponse,
+    ) -> Result<LookThrough, RequestError> {
+        self.runtime
+            .block_on(self.inner.portfolio_look_through(positions))
+    }
+
+    pub fn get_markets(&self) -> Result<Vec<Market>, RequestError> {
+        self.runtime.block_on(self.inner.get_markets())
+    }
+
+    pub fn get_offers(&self) -> Result<Vec<Offer>, RequestError> {
+        self.runtime.block_on(self.inner.get_offers())
+    }
+
+    pub fn get_exchange_rates(&self) -> Result<Vec<ExchangeRate>, RequestError> {
+        self.runtime.block_on(self.inner.get_exchange_rates())
+    }
+
+    pub fn currency_converter(&self) -> Result<CurrencyConverter, RequestError> {
+        self.runtime.block_on(self.inner.currency_converter())
+    }
+
+    pub fn get_orderbooks(
+        &self,
+        orderbook_ids: &[OrderbookId],
+    ) -> Result<BatchResult<Quote>, RequestError> {
+        self.runtime
+            .block_on(self.inner.get_orderbooks(orderbook_ids))
+    }
+
+    pub fn get_orderbook(&self, orderbook_id: &OrderbookId) -> Result<Orderbook, RequestError> {
+        self.runtime
+            .block_on(self.inner.get_orderbook(orderbook_id))
+    }
+
+    pub fn get_many_stock_info(
+        &self,
+        orderbook_ids: &[OrderbookId],
+        concurrency: usize,
+    ) -> Vec<Result<Orderbook, RequestError>> {
+        self.runtime
+            .block_on(self.inner.get_many_stock_info(orderbook_ids, concurrency))
+    }
+
+    pub fn get_order_depth(&self, orderbook_id: &OrderbookId) -> Result<OrderDepth, RequestError> {
+        self.runtime
+            .block_on(self.inner.get_order_depth(orderbook_id))
+    }
+
+    pub fn get_trades(&self, orderbook_id: &OrderbookId) -> Result<Vec<Trade>, RequestError> {
+        self.runtime.block_on(self.inner.get_trades(orderbook_id))
+    }
+
+    pub fn get_ownership(&self, orderbook_id: &OrderbookId) -> Result<Ownership, RequestError> {
+        self.runtime
+            .block_on(self.inner.get_ownership(orderbook_id))
+    }
+
+    pub fn get_price_history(
+        &self,
+        orderbook_id: &OrderbookId,
+        from: NaiveDate,
+        to: NaiveDate,
+        resolution: Resolution,
+    ) -> Result<Vec<Candle>, RequestError> {
+        self.runtime.block_on(
+            self.inner
+                .get_price_history(orderbook_id, from, to, resolution),
+        )
+    }
+
+    pub fn get_chart_data(
+        &self,
+        orderbook_id: &OrderbookId,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<ChartData, RequestError> {
+        self.runtime
+            .block_on(self.inner.get_chart_data(orderbook_id, from, to))
+    }
+
+    pub fn suggest(&self, prefix: &str, limit: usize) -> Result<Vec<Suggestion>, RequestError> {
+        self.runtime.block_on(self.inner.suggest(prefix, limit))
+    }
+
+    pub fn get_watchlists(&self) -> Result<Vec<Watchlist>, RequestError> {
+        self.runtime.block_on(self.inner.get_watchlists())
+    }
+
+    pub fn get_watchlist(&self, watchlist_id: &WatchlistId) -> Result<Watchlist, RequestError> {
+        self.runtime
+            .block_on(self.inner.get_watchlist(watchlist_id))
+    }
+
+    pub fn get_watchlist_quotes(
+        &self,
+        watchlist_id: &WatchlistId,
+    ) -> Result<Vec<WatchlistQuote>, RequestError> {
+        self.runtime
+            .block_on(self.inner.get_watchlist_quotes(watchlist_id))
+    }
+
+    pub fn get_dividend_history(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<Vec<Dividend>, RequestError> {
+        self.runtime
+            .block_on(self.inner.get_dividend_history(orderbook_id))
+    }
+
+    pub fn get_key_ratios(&self, orderbook_id: &OrderbookId) -> Result<KeyRatios, RequestError> {
+        self.runtime
+            .block_on(self.inner.get_key_ratios(orderbook_id))
+    }
+
+    pub fn get_short_positions(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<ShortPositions, RequestError> {
+        self.runtime
+            .block_on(self.inner.get_short_positions(orderbook_id))
+    }
+
+    pub fn place_order_dry_run(&self, order: &NewOrder) -> Result<OrderPrecheck, RequestError> {
+        self.runtime.block_on(self.inner.place_order_dry_run(order))
+    }
+
+    pub fn place_order(
+        &self,
+        order: &NewOrder,
+        options: CallOptions,
+    ) -> Result<OrderResponse, RequestError> {
+        self.runtime
+            .block_on(self.inner.place_order(order, options))
+    }
+
+    pub fn delete_order(
+        &self,
+        account_id: &AccountId,
+        order_id: &str,
+    ) -> Result<OrderResponse, RequestError> {
+        self.runtime
+            .block_on(self.inner.delete_order(account_id, order_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{config, mount_positions, mount_successful_auth, POSITIONS};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    // The mock server runs on its own thread, this runtime is only used to set it up.
+    fn mock_runtime() -> Runtime {
+        Builder::new_current_thread().enable_all().build().unwrap()
+    }
+
+    #[test]
+    fn require_auth() {
+        let client = Client::from(
+            crate::client::Client::builder()
+                .config(config())
+                .api_url(String::from("http://fake-url.com"))
+                .build()
+                .unwrap(),
+        );
+        assert_err!(client.get_positions());
+        assert_err!(client.get_overview());
+    }
+
+    #[test]
+    fn can_get_positions() {
+        let mock_runtime = mock_runtime();
+        let mock_server = mock_runtime.block_on(async {
+            let mock_server = MockServer::start().await;
+            mount_successful_auth(&mock_server).await;
+            mount_positions(&mock_server, POSITIONS).await;
+            mock_server
+        });
+
+        let client = Client::from(
+            crate::client::Client::builder()
+                .config(config())
+                .api_url(mock_server.uri())
+                .build()
+                .unwrap(),
+        );
+
+        client.authenticate().expect("failed to authenticate");
+
+        let positions = assert_ok!(client.get_positions());
+
+        assert_eq!(positions.total_balance, 4000.0)
+    }
+
+    #[test]
+    fn can_get_overview() {
+        let mock_runtime = mock_runtime();
+        let mock_server = mock_runtime.block_on(async {
+            let mock_server = MockServer::start().await;
+            let responder = ResponseTemplate::new(200).set_body_string(String::from(
+                r#"{
+                    "accounts": [
+                        {"accountId": "1234", "name": "ISK", "accountType": "Investeringssparkonto", "ownCapital": 152340.5, "buyingPower": 2300.0, "totalBalance": 152340.5}
+                    ],
+                    "totalOwnCapital": 152340.5,
+                    "totalBuyingPower": 2300.0,
+                    "totalBalance": 152340.5
+                }"#,
+            ));
+
+            mount_successful_auth(&mock_server).await;
+
+            Mock::given(method("GET"))
+                .and(path("/_mobile/account/overview"))
+                .respond_with(responder)
+                .mount(&mock_server)
+                .await;
+            mock_server
+        });
+
+        let client = Client::from(
+            crate::client::Client::builder()
+                .config(config())
+                .api_url(mock_server.uri())
+                .build()
+                .unwrap(),
+        );
+
+        client.authenticate().expect("failed to authenticate");
+
+        let overview = assert_ok!(client.get_overview());
+        assert_eq!(overview.accounts.len(), 1);
+        assert_eq!(overview.total_buying_power, 2300.0);
+        assert_eq!(overview.total_performance_percent, None);
+    }
+}
@@ -0,0 +1,226 @@
+//! Recording of HTTP interactions to a file and replaying them without the network, see
+//! [`Client::record_to`](crate::client::Client::record_to) and
+//! [`Client::replay_from`](crate::client::Client::replay_from).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use reqwest::{RequestBuilder, Response};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{ConfigError, RequestError};
+
+const REDACTED: &str = "[redacted]";
+const SECRET_FIELDS: &[&str] = &[
+    "username",
+    "password",
+    "totpCode",
+    "transactionId",
+    "authenticationSession",
+    "pushSubscriptionId",
+    "customerId",
+];
+const SECRET_HEADERS: &[&str] = &["x-securitytoken", "set-cookie", "cookie"];
+// Redaction can change the length of the body, so these would no longer be true on replay.
+const SKIPPED_HEADERS: &[&str] = &["content-length", "transfer-encoding"];
+
+/// One request and the response it got. Requests are matched on method, path and query.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Interaction {
+    method: String,
+    path: String,
+    query: Option<String>,
+    #[serde(default)]
+    body: Option<String>,
+    status: u16,
+    headers: Vec<(String, String)>,
+    response_body: String,
+}
+
+impl Interaction {
+    fn matches(&self, method: &str, path: &str, query: Option<&str>) -> bool {
+        self.method == method && self.path == path && self.query.as_deref() == query
+    }
+}
+
+pub(crate) enum Cassette {
+    Record {
+        path: PathBuf,
+        interactions: Mutex<Vec<Interaction>>,
+    },
+    Replay {
+        interactions: Vec<Interaction>,
+        // Which interactions have been served, so identical requests get the responses in
+        // the order they were recorded.
+        served: Mutex<Vec<bool>>,
+    },
+}
+
+impl Cassette {
+    pub(crate) fn record(path: PathBuf) -> Self {
+        Cassette::Record {
+            path,
+            interactions: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub(crate) fn replay(path: &Path) -> Result<Self, ConfigError> {
+        let invalid = |e: &dyn std::fmt::Display| {
+            ConfigError::InvalidCassette(format!("{}: {}", path.display(), e))
+        };
+        let contents = fs::read_to_string(path).map_err(|e| invalid(&e))?;
+        let interactions: Vec<Interaction> =
+            serde_json::from_str(&contents).map_err(|e| invalid(&e))?;
+        Ok(Cassette::Replay {
+            served: Mutex::new(vec![false; interactions.len()]),
+            interactions,
+        })
+    }
+
+    pub(crate) async fn execute(&self, request: RequestBuilder) -> Result<Response, RequestError> {
+        match self {
+            Cassette::Record { path, interactions } => {
+                let (client, request) = request.build_split();
+                let request = request?;
+                let mut interaction = Interaction {
+                    method: request.method().to_string(),
+                    path: request.url().path().to_string(),
+                    query: request.url().query().map(String::from),
+                    body: request
+                        .body()
+                        .and_then(|body| body.as_bytes())
+                        .map(|body| redact_body(&String::from_utf8_lossy(body))),
+                    status: 0,
+                    headers: Vec::new(),
+                    response_body: String::new(),
+                };
+
+                let response = client.execute(request).await?;
+                let status = response.status();
+                let headers = response.headers().clone();
+                let body = response.bytes().await?;
+
+                interaction.status = status.as_u16();
+                interaction.headers = headers
+                    .iter()
+                    .filter(|(name, _)| !SKIPPED_HEADERS.contains(&name.as_str()))
+                    .map(|(name, value)| {
+                        let value = if SECRET_HEADERS.contains(&name.as_str()) {
+                            String::from(REDACTED)
+                        } else {
+                            String::from_utf8_lossy(value.as_bytes()).into_owned()
+                        };
+                        (name.to_string(), value)
+                    })
+                    .collect();
+                interaction.response_body = redact_body(&String::from_utf8_lossy(&body));
+
+                {
+                    let mut interactions = interactions.lock().unwrap();
+                    interactions.push(interaction);
+                    let contents = serde_json::to_string_pretty(&*interactions)?;
+                    // A cassette that can't be written only fails the recording, not the call.
+                    let _ = fs::write(path, contents);
+                }
+
+                let mut rebuilt = http::Response::builder().status(status);
+                if let Some(rebuilt_headers) = rebuilt.headers_mut() {
+                    *rebuilt_headers = headers;
+                }
+                Ok(Response::from(rebuilt.body(body).unwrap()))
+            }
+            Cassette::Replay {
+                interactions,
+                served,
+            } => {
+                let request = request.build()?;
+                let method = request.method().as_str();
+                let path = request.url().path();
+                let query = request.url().query();
+
+                let interaction = {
+                    let mut served = served.lock().unwrap();
+                    let matching: Vec<usize> = (0..interactions.len())
+                        .filter(|&i| interactions[i].matches(method, path, query))
+                        .collect();
+                    // Once every match has been served the last one keeps answering.
+                    let next = matching
+                        .iter()
+                        .copied()
+                        .find(|&i| !served[i])
+                        .or_else(|| matching.last().copied())
+                        .ok_or_else(|| {
+                            RequestError::UnmatchedReplay(format!("{} {}", method, request.url()))
+                        })?;
+                    served[next] = true;
+                    &interactions[next]
+                };
+
+                let mut response = http::Response::builder().status(interaction.status);
+                for (name, value) in &interaction.headers {
+                    response = response.header(name.as_str(), value.as_str());
+                }
+                let response = response
+                    .body(interaction.response_body.clone())
+                    .map_err(|e| {
+                        RequestError::UnmatchedReplay(format!("invalid recorded response: {}", e))
+                    })?;
+                Ok(Response::from(response))
+            }
+        }
+    }
+}
+
+/// Replaces the values of secret fields anywhere in a JSON body. Bodies that aren't JSON are
+/// kept as they are.
+fn redact_body(body: &str) -> String {
+    match serde_json::from_str::<Value>(body) {
+        Ok(mut value) => {
+            redact_value(&mut value);
+            value.to_string()
+        }
+        Err(_) => String::from(body),
+    }
+}
+
+fn redact_value(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            for (name, field) in fields.iter_mut() {
+                if SECRET_FIELDS.contains(&name.as_str()) {
+                    *field = Value::String(String::from(REDACTED));
+                } else {
+                    redact_value(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_value),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_secret_fields() {
+        let body = redact_body(
+            r#"{"username":"user","password":"pass","nested":[{"customerId":"123","keep":1}]}"#,
+        );
+
+        let value: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(value["username"], REDACTED);
+        assert_eq!(value["password"], REDACTED);
+        assert_eq!(value["nested"][0]["customerId"], REDACTED);
+        assert_eq!(value["nested"][0]["keep"], 1);
+    }
+
+    #[test]
+    fn keeps_bodies_that_are_not_json() {
+        assert_eq!(redact_body("42 apples"), "42 apples");
+    }
+}
@@ -1,18 +1,61 @@
-use std::borrow::Borrow;
-use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
 
-use crate::error::RequestError;
-use crate::request::{post, post_response};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::cassette::Cassette;
+use crate::endpoints::{self, EndpointId, Endpoints, RequestSpec};
+use crate::error::{ConfigError, RequestError};
+use crate::market::instrument_cache::InstrumentCache;
+use crate::market::quotes::DEFAULT_ORDERBOOK_CHUNK_SIZE;
+use crate::metrics::{endpoint_label, Metrics, MetricsSink};
+use crate::order::dedupe::OrderLedger;
+use crate::time::Instant;
+use reqwest::header::{CONTENT_TYPE, USER_AGENT};
+use reqwest::{RequestBuilder, Response, StatusCode, Url};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 #[derive(Clone)]
 pub struct Client {
     pub api_url: String,
     pub user_agent: String,
-    x_security_token: String,
-    session: String,
+    pub push_url: String,
     config: Config,
+    // Written on (re-)authentication only, so requests can share the client behind `&self`.
+    // Clones share it too.
+    session: Arc<RwLock<Session>>,
+    http: reqwest::Client,
+    // False when an injected HTTP client brings its own user agent.
+    send_user_agent: bool,
+    strict_parsing: bool,
+    retry_policy: RetryPolicy,
+    rate_limit: Option<RateLimit>,
+    instrument_cache: Option<Arc<InstrumentCache>>,
+    // Shared between clones so they draw from the same rate limit.
+    next_request_at: Arc<Mutex<Option<Instant>>>,
+    // Requests currently held back by the rate limit, for metrics.
+    rate_limit_waiting: Arc<AtomicUsize>,
+    metrics: Option<Metrics>,
+    order_ledger: Option<Arc<OrderLedger>>,
+    pub(crate) orderbook_chunk_size: usize,
+    endpoints: Arc<Endpoints>,
+    // Set by `with_options`, applied to every request of this clone.
+    call_options: CallOptions,
+    // Held while warming up so concurrent calls log in once.
+    warm_up_lock: Arc<tokio::sync::Mutex<()>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    cassette: Option<Arc<Cassette>>,
+}
+
+#[derive(Debug, Default)]
+struct Session {
+    x_security_token: String,
+    authentication_session: String,
+    push_subscription_id: String,
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -22,6 +65,143 @@ pub struct Config {
     pub avanza_totp_secret: String,
 }
 
+/// How often an idempotent request is retried after a connection error, a 429/5xx response or
+/// an HTML maintenance page. The wait doubles after every attempt, starting at `backoff`, and
+/// is ten times as long after a maintenance page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: u32, backoff: Duration) -> Self {
+        Self {
+            max_retries,
+            backoff,
+        }
+    }
+
+    pub fn none() -> Self {
+        Self::new(0, Duration::ZERO)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// At most `max_requests` requests per `per`, spread out evenly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub max_requests: u32,
+    pub per: Duration,
+}
+
+impl RateLimit {
+    pub fn new(max_requests: u32, per: Duration) -> Self {
+        Self { max_requests, per }
+    }
+
+    pub fn per_second(max_requests: u32) -> Self {
+        Self::new(max_requests, Duration::from_secs(1))
+    }
+
+    fn interval(&self) -> Duration {
+        self.per / self.max_requests
+    }
+}
+
+/// Whether sending a request twice has the same effect as sending it once. Only idempotent
+/// requests are retried: a timed out order may still have been placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Idempotency {
+    /// Reads, logins and deleting an order by id.
+    Idempotent,
+    /// Placing orders, fund orders and transfers.
+    NonIdempotent,
+}
+
+/// Options for single calls, overriding the client's settings. Pass them to an endpoint that
+/// takes them, like `place_order`, or to [`Client::with_options`] for any other.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CallOptions {
+    force_retryable: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    timeout: Option<Duration>,
+    headers: Vec<(String, String)>,
+    retry_policy: Option<RetryPolicy>,
+}
+
+impl CallOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Retries the call under the client's [`RetryPolicy`] even though it is not idempotent.
+    /// A retry after a timeout can then execute it twice, e.g. place the same order again.
+    pub fn force_retryable(mut self) -> Self {
+        self.force_retryable = true;
+        self
+    }
+
+    /// Total time allowed for each attempt instead of [`ClientBuilder::timeout`], shorter or
+    /// longer. Not available on wasm32.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn timeout(mut self, value: Duration) -> Self {
+        self.timeout = Some(value);
+        self
+    }
+
+    /// Sends `name: value` on top of the headers the client sets.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Retries under `value` instead of the client's policy, e.g. [`RetryPolicy::none`].
+    /// Calls that aren't idempotent still aren't retried unless
+    /// [`force_retryable`](CallOptions::force_retryable) is set too.
+    pub fn retry_policy(mut self, value: RetryPolicy) -> Self {
+        self.retry_policy = Some(value);
+        self
+    }
+
+    pub(crate) fn idempotency(&self, default: Idempotency) -> Idempotency {
+        if self.force_retryable {
+            Idempotency::Idempotent
+        } else {
+            default
+        }
+    }
+
+    /// `self` over `base`: what `self` sets wins, headers add up.
+    fn merged_over(self, base: &CallOptions) -> CallOptions {
+        let mut headers = base.headers.clone();
+        headers.extend(self.headers);
+        CallOptions {
+            force_retryable: self.force_retryable || base.force_retryable,
+            #[cfg(not(target_arch = "wasm32"))]
+            timeout: self.timeout.or(base.timeout),
+            headers,
+            retry_policy: self.retry_policy.or(base.retry_policy),
+        }
+    }
+
+    fn apply(&self, mut request: RequestBuilder) -> RequestBuilder {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(timeout) = self.timeout {
+            request = request.timeout(timeout);
+        }
+        for (name, value) in &self.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        request
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthenticateTOTPResponse {
@@ -44,16 +224,299 @@ pub struct TwoFactorLogin {
     transaction_id: String,
 }
 
-const MAX_INACTIVE_MINUTES_AS_SECONDS: &str = "3600";
+const DEFAULT_API_URL: &str = "https://www.avanza.se";
+const DEFAULT_USER_AGENT: &str = "Avanza API client";
+const DEFAULT_PUSH_URL: &str = "wss://www.avanza.se/_push/cometd";
+const SECURITY_TOKEN_HEADER: &str = "x-securitytoken";
+
+#[derive(Debug, Clone, Default)]
+pub struct ClientBuilder {
+    api_url: Option<String>,
+    user_agent: Option<String>,
+    push_url: Option<String>,
+    #[cfg(not(target_arch = "wasm32"))]
+    timeout: Option<Duration>,
+    #[cfg(not(target_arch = "wasm32"))]
+    proxy: Option<String>,
+    http_client: Option<reqwest::Client>,
+    endpoint_overrides: Vec<(EndpointId, String)>,
+    strict_parsing: bool,
+    retry_policy: RetryPolicy,
+    rate_limit: Option<RateLimit>,
+    instrument_cache: Option<(Duration, usize)>,
+    order_dedupe_window: Option<Duration>,
+    orderbook_chunk_size: Option<usize>,
+    metrics: Option<Metrics>,
+    config: Option<Config>,
+    credentials: Option<Config>,
+}
+
+impl ClientBuilder {
+    pub fn api_url(mut self, value: impl Into<String>) -> Self {
+        self.api_url = Some(value.into());
+        self
+    }
+
+    pub fn user_agent(mut self, value: impl Into<String>) -> Self {
+        self.user_agent = Some(value.into());
+        self
+    }
+
+    pub fn push_url(mut self, value: impl Into<String>) -> Self {
+        self.push_url = Some(value.into());
+        self
+    }
+
+    /// Total time allowed for each request, from connecting until the body is read. Not
+    /// available on wasm32, where the browser decides.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn timeout(mut self, value: Duration) -> Self {
+        self.timeout = Some(value);
+        self
+    }
+
+    /// Sends all requests through the proxy at `url`. Not available on wasm32.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    /// Sends every request, logins included, on `client` instead of one built here, e.g. to
+    /// share its connection pool with the rest of an application.
+    ///
+    /// The client's own settings apply as they are: timeout, proxy, TLS, default headers and
+    /// its user agent. Setting [`timeout`](ClientBuilder::timeout) or
+    /// [`proxy`](ClientBuilder::proxy) as well is an error. On top, every request gets the
+    /// session's security token and a JSON content type where it has a body, and the user
+    /// agent only if [`user_agent`](ClientBuilder::user_agent) is set.
+    pub fn with_http_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    pub fn retry_policy(mut self, value: RetryPolicy) -> Self {
+        self.retry_policy = value;
+        self
+    }
+
+    /// Sends requests for `endpoint` to `path` instead of its
+    /// [default](EndpointId::default_path), e.g. after Avanza moved it. The path can use
+    /// the default's placeholders, like `{account_id}`; `build` rejects any others.
+    pub fn override_endpoint(mut self, endpoint: EndpointId, path: impl Into<String>) -> Self {
+        self.endpoint_overrides.push((endpoint, path.into()));
+        self
+    }
+
+    /// Fails every response with fields the model doesn't know with
+    /// `RequestError::UnknownFields`, listing their paths, instead of ignoring them. Meant
+    /// for a canary that should notice API changes early; off by default.
+    pub fn strict_parsing(mut self, value: bool) -> Self {
+        self.strict_parsing = value;
+        self
+    }
+
+    pub fn rate_limit(mut self, value: RateLimit) -> Self {
+        self.rate_limit = Some(value);
+        self
+    }
+
+    /// Caches `get_orderbook` responses for `ttl`, keeping at most `capacity` orderbooks.
+    pub fn instrument_cache(mut self, ttl: Duration, capacity: usize) -> Self {
+        self.instrument_cache = Some((ttl, capacity));
+        self
+    }
+
+    /// Rejects an order with `RequestError::DuplicateOrder` if an order for the same
+    /// account, orderbook, side, price and volume was placed less than `window` ago, unless
+    /// it is marked with [`NewOrder::allow_duplicate`](crate::order::new_order::NewOrder::allow_duplicate).
+    /// The placed orders are remembered in memory, shared between clones of the client.
+    pub fn order_dedupe_window(mut self, window: Duration) -> Self {
+        self.order_dedupe_window = Some(window);
+        self
+    }
+
+    /// How many orderbook ids `get_orderbooks` sends per request, by default
+    /// [`DEFAULT_ORDERBOOK_CHUNK_SIZE`]. Must be above zero.
+    pub fn orderbook_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.orderbook_chunk_size = Some(chunk_size);
+        self
+    }
+
+    /// Reports every request, error, rate limit wait and login to `sink`, e.g. an
+    /// [`InMemoryMetrics`](crate::metrics::InMemoryMetrics).
+    pub fn metrics(mut self, sink: Arc<dyn MetricsSink>) -> Self {
+        self.metrics = Some(Metrics(sink));
+        self
+    }
+
+    pub fn config(mut self, value: Config) -> Self {
+        self.config = Some(value);
+        self
+    }
+
+    /// Same as `config`, without building the `Config` by hand. Setting both is an error.
+    pub fn credentials(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+        totp_secret: impl Into<String>,
+    ) -> Self {
+        self.credentials = Some(Config {
+            avanza_username: username.into(),
+            avanza_password: password.into(),
+            avanza_totp_secret: totp_secret.into(),
+        });
+        self
+    }
+
+    pub fn build(self) -> Result<Client, ConfigError> {
+        let config = match (self.config, self.credentials) {
+            (Some(_), Some(_)) => {
+                return Err(ConfigError::ConflictingOptions("config", "credentials"))
+            }
+            (Some(config), None) | (None, Some(config)) => config,
+            (None, None) => return Err(ConfigError::MissingCredentials()),
+        };
+
+        let api_url = self
+            .api_url
+            .unwrap_or_else(|| String::from(DEFAULT_API_URL));
+        validate_url(&api_url, &["http", "https"])?;
+        let push_url = self
+            .push_url
+            .unwrap_or_else(|| String::from(DEFAULT_PUSH_URL));
+        validate_url(&push_url, &["ws", "wss"])?;
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.timeout == Some(Duration::ZERO) {
+            return Err(ConfigError::InvalidTimeout());
+        }
+        if let Some(rate_limit) = self.rate_limit {
+            if rate_limit.max_requests == 0 || rate_limit.per.is_zero() {
+                return Err(ConfigError::InvalidRateLimit(rate_limit));
+            }
+        }
+        if let Some((ttl, capacity)) = self.instrument_cache {
+            if ttl.is_zero() || capacity == 0 {
+                return Err(ConfigError::InvalidInstrumentCache());
+            }
+        }
+        if self.orderbook_chunk_size == Some(0) {
+            return Err(ConfigError::InvalidOrderbookChunkSize());
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.http_client.is_some() {
+            if self.timeout.is_some() {
+                return Err(ConfigError::ConflictingOptions("http_client", "timeout"));
+            }
+            if self.proxy.is_some() {
+                return Err(ConfigError::ConflictingOptions("http_client", "proxy"));
+            }
+        }
+        let send_user_agent = self.http_client.is_none() || self.user_agent.is_some();
+        let mut endpoints = Endpoints::default();
+        for (endpoint, path) in self.endpoint_overrides {
+            endpoints.override_path(endpoint, path)?;
+        }
+
+        #[allow(unused_mut)]
+        let mut http = reqwest::Client::builder();
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            if let Some(timeout) = self.timeout {
+                http = http.timeout(timeout);
+            }
+            if let Some(proxy) = self.proxy {
+                let proxy = reqwest::Proxy::all(proxy.as_str())
+                    .map_err(|_| ConfigError::InvalidProxy(proxy))?;
+                http = http.proxy(proxy);
+            }
+        }
+
+        Ok(Client {
+            api_url,
+            user_agent: self
+                .user_agent
+                .unwrap_or_else(|| String::from(DEFAULT_USER_AGENT)),
+            push_url,
+            retry_policy: self.retry_policy,
+            rate_limit: self.rate_limit,
+            instrument_cache: self
+                .instrument_cache
+                .map(|(ttl, capacity)| Arc::new(InstrumentCache::new(ttl, capacity))),
+            order_ledger: self
+                .order_dedupe_window
+                .map(|window| Arc::new(OrderLedger::new(window))),
+            orderbook_chunk_size: self
+                .orderbook_chunk_size
+                .unwrap_or(DEFAULT_ORDERBOOK_CHUNK_SIZE),
+            metrics: self.metrics,
+            http: match self.http_client {
+                Some(http) => http,
+                None => http.build().map_err(ConfigError::HttpClient)?,
+            },
+            send_user_agent,
+            strict_parsing: self.strict_parsing,
+            endpoints: Arc::new(endpoints),
+            ..Client::new(config)
+        })
+    }
+}
+
+/// Counts one caller waiting for the rate limit until dropped, so a caller that gives up
+/// during the wait, e.g. on a timeout, is no longer counted either.
+struct RateLimitWaiter<'a> {
+    waiting: &'a AtomicUsize,
+    /// Callers waiting, this one included, when it started.
+    count: usize,
+}
+
+impl<'a> RateLimitWaiter<'a> {
+    fn new(waiting: &'a AtomicUsize) -> Self {
+        let count = waiting.fetch_add(1, Ordering::Relaxed) + 1;
+        Self { waiting, count }
+    }
+}
+
+impl Drop for RateLimitWaiter<'_> {
+    fn drop(&mut self) {
+        self.waiting.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+fn validate_url(url: &str, schemes: &[&str]) -> Result<(), ConfigError> {
+    match Url::parse(url) {
+        Ok(parsed) if schemes.contains(&parsed.scheme()) => Ok(()),
+        _ => Err(ConfigError::InvalidUrl(String::from(url))),
+    }
+}
 
 impl Client {
     pub fn new(config: Config) -> Self {
         Self {
-            api_url: String::from("https://www.avanza.se"),
-            user_agent: String::from("Avanza API client"),
-            session: String::new(),
-            x_security_token: String::new(),
+            api_url: String::from(DEFAULT_API_URL),
+            user_agent: String::from(DEFAULT_USER_AGENT),
+            push_url: String::from(DEFAULT_PUSH_URL),
             config,
+            session: Arc::new(RwLock::new(Session::default())),
+            http: reqwest::Client::new(),
+            send_user_agent: true,
+            strict_parsing: false,
+            retry_policy: RetryPolicy::none(),
+            rate_limit: None,
+            instrument_cache: None,
+            next_request_at: Arc::new(Mutex::new(None)),
+            rate_limit_waiting: Arc::new(AtomicUsize::new(0)),
+            metrics: None,
+            order_ledger: None,
+            orderbook_chunk_size: DEFAULT_ORDERBOOK_CHUNK_SIZE,
+            endpoints: Arc::new(Endpoints::default()),
+            call_options: CallOptions::default(),
+            warm_up_lock: Arc::new(tokio::sync::Mutex::new(())),
+            #[cfg(not(target_arch = "wasm32"))]
+            cassette: None,
         }
     }
 
@@ -64,6 +527,13 @@ impl Client {
         Client::new(config)
     }
 
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::default()
+    }
+
+    /// Unlike the builder this can't fail, so a `value` that isn't an http(s) url makes
+    /// every request fail with `RequestError::InvalidConfig` instead.
+    #[deprecated(note = "use `Client::builder().api_url(..)` instead")]
     pub fn api_url(self, value: String) -> Self {
         Self {
             api_url: value,
@@ -71,6 +541,7 @@ impl Client {
         }
     }
 
+    #[deprecated(note = "use `Client::builder().user_agent(..)` instead")]
     pub fn user_agent(self, value: String) -> Self {
         Self {
             user_agent: value,
@@ -78,182 +549,1420 @@ impl Client {
         }
     }
 
-    pub async fn get_response<T: DeserializeOwned>(
-        &mut self,
-        uri: &str,
+    #[deprecated(note = "use `Client::builder().push_url(..)` instead")]
+    pub fn push_url(self, value: String) -> Self {
+        Self {
+            push_url: value,
+            ..self
+        }
+    }
+
+    /// Same as `ClientBuilder::instrument_cache`, replacing any cache the client had. A zero
+    /// `ttl` or `capacity` is `ConfigError::InvalidInstrumentCache`.
+    pub fn with_instrument_cache(
+        self,
+        ttl: Duration,
+        capacity: usize,
+    ) -> Result<Self, ConfigError> {
+        if ttl.is_zero() || capacity == 0 {
+            return Err(ConfigError::InvalidInstrumentCache());
+        }
+        Ok(Self {
+            instrument_cache: Some(Arc::new(InstrumentCache::new(ttl, capacity))),
+            ..self
+        })
+    }
+
+    pub fn instrument_cache(&self) -> Option<&InstrumentCache> {
+        self.instrument_cache.as_deref()
+    }
+
+    /// Same as `ClientBuilder::order_dedupe_window`, replacing any orders the client
+    /// remembered.
+    pub fn order_dedupe_window(self, window: Duration) -> Self {
+        Self {
+            order_ledger: Some(Arc::new(OrderLedger::new(window))),
+            ..self
+        }
+    }
+
+    pub(crate) fn order_ledger(&self) -> Option<&OrderLedger> {
+        self.order_ledger.as_deref()
+    }
+
+    /// Same as `ClientBuilder::orderbook_chunk_size`. Zero is
+    /// `ConfigError::InvalidOrderbookChunkSize`.
+    pub fn with_orderbook_chunk_size(self, chunk_size: usize) -> Result<Self, ConfigError> {
+        if chunk_size == 0 {
+            return Err(ConfigError::InvalidOrderbookChunkSize());
+        }
+        Ok(Self {
+            orderbook_chunk_size: chunk_size,
+            ..self
+        })
+    }
+
+    /// A clone that sends every request with `options` over the ones already set, e.g.
+    /// `client.with_options(CallOptions::new().timeout(Duration::from_secs(60)))
+    /// .get_transactions(..)`. It shares the session, rate limit and caches with `self`.
+    pub fn with_options(&self, options: CallOptions) -> Self {
+        Self {
+            call_options: options.merged_over(&self.call_options),
+            ..self.clone()
+        }
+    }
+
+    /// Writes every request and its response to the JSON file at `path`, rewriting it after
+    /// each one. Credentials, session ids and the security token are redacted. Not available
+    /// on wasm32.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn record_to(self, path: impl Into<PathBuf>) -> Self {
+        Self {
+            cassette: Some(Arc::new(Cassette::record(path.into()))),
+            ..self
+        }
+    }
+
+    /// Answers requests from a file written by [`Client::record_to`] instead of the network.
+    /// Requests are matched on method, path and query, and identical requests get the
+    /// recorded responses in order, the last one repeating. Anything else fails with
+    /// `RequestError::UnmatchedReplay`. Not available on wasm32.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn replay_from(self, path: impl Into<PathBuf>) -> Result<Self, ConfigError> {
+        Ok(Self {
+            cassette: Some(Arc::new(Cassette::replay(&path.into())?)),
+            ..self
+        })
+    }
+
+    /// GETs `uri` with the session and deserializes the JSON body into `T`, see
+    /// [`Client::send_authenticated`].
+    pub async fn get_response<T: DeserializeOwned>(&self, uri: &str) -> Result<T, RequestError> {
+        self.fetch_authenticated(self.http.get(uri), Idempotency::Idempotent)
+            .await
+    }
+
+    /// Like [`Client::get_response`] but into a [`Value`], for fields no model covers yet.
+    /// Parsing into a `Value` builds every map and string of the payload, so it is slower
+    /// than a typed model; see the `parse_positions` bench.
+    pub async fn get_response_raw(&self, uri: &str) -> Result<Value, RequestError> {
+        self.get_response::<Value>(uri).await
+    }
+
+    /// Sends the request `spec` describes and parses the JSON body into `T`. Requests that
+    /// require auth go through [`Client::send_authenticated`].
+    pub(crate) async fn fetch_spec<T: DeserializeOwned>(
+        &self,
+        spec: &RequestSpec,
+        idempotency: Idempotency,
     ) -> Result<T, RequestError> {
-        let response = reqwest::get(uri).await?;
-        let body = response.text().await?;
-        Ok(serde_json::from_str::<T>(&body)?)
+        let request = self.request(spec)?;
+        if spec.requires_auth {
+            self.fetch_authenticated(request, idempotency).await
+        } else {
+            self.fetch(request, idempotency).await
+        }
     }
 
-    pub(crate) fn is_authenticated(&self) -> bool {
-        return !self.x_security_token.is_empty() && !self.session.is_empty();
+    /// The request for `spec`. The builder validates `api_url`, but the deprecated setter
+    /// and the public field don't, so it is checked again here.
+    fn request(&self, spec: &RequestSpec) -> Result<RequestBuilder, RequestError> {
+        validate_url(&self.api_url, &["http", "https"]).map_err(RequestError::InvalidConfig)?;
+        let spec = spec.clone().with_endpoints(&self.endpoints);
+        let url = spec
+            .url(&self.api_url)
+            .map_err(RequestError::InvalidConfig)?;
+        let request = self.http.request(spec.method, url);
+        Ok(match spec.body {
+            Some(body) => request.header(CONTENT_TYPE, "application/json").body(body),
+            None => request,
+        })
     }
 
-    pub async fn authenticate(&mut self) -> Result<AuthenticateResponse, RequestError> {
-        let mut map = HashMap::new();
-        let username = self.config.avanza_username.as_str();
-        let password = self.config.avanza_password.as_str();
-        map.insert("username", username);
-        map.insert("password", password);
-        map.insert("maxInactiveMinutes", MAX_INACTIVE_MINUTES_AS_SECONDS);
+    /// Sends `request` without the session, for logging in, and parses the JSON body into
+    /// `T`.
+    async fn fetch<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+        idempotency: Idempotency,
+    ) -> Result<T, RequestError> {
+        let endpoint = self.endpoint(&request);
+        let response = self.send(request, idempotency).await?;
+        parse_body(response, self.strict_parsing)
+            .await
+            .inspect_err(|e| self.record_error(endpoint.as_deref(), e))
+    }
 
-        let uri = format!(
-            "{}/_api/authentication/sessions/usercredentials",
-            self.api_url
-        );
+    async fn fetch_authenticated<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+        idempotency: Idempotency,
+    ) -> Result<T, RequestError> {
+        let endpoint = self.endpoint(&request);
+        let response = self.send_authenticated(request, idempotency).await?;
+        parse_body(response, self.strict_parsing)
+            .await
+            .inspect_err(|e| self.record_error(endpoint.as_deref(), e))
+    }
+
+    /// Sends `request` with the session's security token, through the rate limit and retry
+    /// policy of [`Client::send`]. Every endpoint goes through here.
+    ///
+    /// When Avanza answers 401 the session has expired: the client logs in again, once, and
+    /// sends the request again with the new session. That holds for orders too, since a
+    /// request turned away at the door wasn't acted on. Any other status outside 2xx is an
+    /// error.
+    async fn send_authenticated(
+        &self,
+        request: RequestBuilder,
+        idempotency: Idempotency,
+    ) -> Result<Response, RequestError> {
+        if !self.is_authenticated() {
+            return Err(RequestError::NotAuthenticatedError());
+        }
+        let endpoint = self.endpoint(&request);
+        let replay = request.try_clone();
+        let token = self.security_token();
+        let mut response = self
+            .send(
+                request.header(SECURITY_TOKEN_HEADER, token.as_str()),
+                idempotency,
+            )
+            .await?;
+        if let (Some(replay), StatusCode::UNAUTHORIZED) = (replay, response.status()) {
+            self.reauthenticate(&token).await?;
+            response = self
+                .send(
+                    replay.header(SECURITY_TOKEN_HEADER, self.security_token().as_str()),
+                    idempotency,
+                )
+                .await?;
+        }
+        response
+            .error_for_status()
+            .map_err(RequestError::from)
+            .inspect_err(|e| self.record_error(endpoint.as_deref(), e))
+    }
+
+    /// Logs in again unless another request already did since `stale_token` was issued.
+    async fn reauthenticate(&self, stale_token: &str) -> Result<(), RequestError> {
+        let _guard = self.warm_up_lock.lock().await;
+        if self.security_token() != stale_token {
+            return Ok(());
+        }
+        self.authenticate().await?;
+        Ok(())
+    }
+
+    fn security_token(&self) -> String {
+        self.session.read().unwrap().x_security_token.clone()
+    }
+
+    /// Sends `request` on the shared HTTP client, honoring the rate limit and, for idempotent
+    /// requests, the retry policy.
+    async fn send(
+        &self,
+        request: RequestBuilder,
+        idempotency: Idempotency,
+    ) -> Result<Response, RequestError> {
+        let request = if self.send_user_agent {
+            request.header(USER_AGENT, self.user_agent.as_str())
+        } else {
+            request
+        };
+        let request = self.call_options.apply(request);
+        let retry_policy = self.call_options.retry_policy.unwrap_or(self.retry_policy);
+        let max_retries = match self.call_options.idempotency(idempotency) {
+            Idempotency::Idempotent => retry_policy.max_retries,
+            Idempotency::NonIdempotent => 0,
+        };
+        let mut attempt = 0;
+        loop {
+            self.wait_for_rate_limit().await;
+            let retry = match request.try_clone() {
+                Some(retry) if attempt < max_retries => retry,
+                _ => return self.execute_measured(request).await,
+            };
+            let maintenance = match self.execute_measured(retry).await {
+                Ok(response) => match sniff_maintenance(response).await? {
+                    (true, _) => true,
+                    (false, response) if !is_retryable_status(response.status()) => {
+                        return Ok(response)
+                    }
+                    _ => false,
+                },
+                Err(RequestError::WebRequestError(e)) if is_retryable_error(&e) => false,
+                Err(e) => return Err(e),
+            };
+            let backoff = retry_policy.backoff * 2u32.pow(attempt);
+            let backoff = if maintenance {
+                backoff * MAINTENANCE_BACKOFF_FACTOR
+            } else {
+                backoff
+            };
+            crate::time::sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+
+    /// Executes one attempt, reporting its status and duration or its error to the metrics.
+    async fn execute_measured(&self, request: RequestBuilder) -> Result<Response, RequestError> {
+        let Some(Metrics(metrics)) = &self.metrics else {
+            return self.execute(request).await;
+        };
+        let endpoint = self.endpoint(&request).unwrap_or_default();
+        let started = Instant::now();
+        let result = self.execute(request).await;
+        match &result {
+            Ok(response) => {
+                metrics.record_request(&endpoint, response.status().as_u16(), started.elapsed())
+            }
+            Err(e) => metrics.record_error(&endpoint, e.kind()),
+        }
+        result
+    }
+
+    /// The metrics label of `request`, if metrics are collected at all.
+    fn endpoint(&self, request: &RequestBuilder) -> Option<String> {
+        self.metrics.as_ref()?;
+        let request = request.try_clone()?.build().ok()?;
+        Some(endpoint_label(request.method(), request.url()))
+    }
+
+    fn record_error(&self, endpoint: Option<&str>, error: &RequestError) {
+        if let (Some(Metrics(metrics)), Some(endpoint)) = (&self.metrics, endpoint) {
+            metrics.record_error(endpoint, error.kind());
+        }
+    }
+
+    async fn execute(&self, request: RequestBuilder) -> Result<Response, RequestError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(cassette) = &self.cassette {
+            return cassette.execute(request).await;
+        }
+        Ok(request.send().await?)
+    }
+
+    async fn wait_for_rate_limit(&self) {
+        let Some(rate_limit) = self.rate_limit else {
+            return;
+        };
+        let send_at = {
+            let mut next_request_at = self.next_request_at.lock().unwrap();
+            let now = Instant::now();
+            let send_at = next_request_at.map_or(now, |at| at.max(now));
+            *next_request_at = Some(send_at + rate_limit.interval());
+            send_at
+        };
+        let delay = send_at.saturating_duration_since(Instant::now());
+        if delay.is_zero() {
+            if let Some(Metrics(metrics)) = &self.metrics {
+                metrics.record_rate_limit(self.rate_limit_waiting.load(Ordering::Relaxed), delay);
+            }
+            return;
+        }
+        let waiting = RateLimitWaiter::new(&self.rate_limit_waiting);
+        if let Some(Metrics(metrics)) = &self.metrics {
+            metrics.record_rate_limit(waiting.count, delay);
+        }
+        crate::time::sleep_until(send_at).await;
+    }
+
+    #[cfg_attr(not(feature = "push"), allow(dead_code))]
+    pub(crate) fn push_subscription_id(&self) -> String {
+        self.session.read().unwrap().push_subscription_id.clone()
+    }
+
+    pub(crate) fn is_authenticated(&self) -> bool {
+        let session = self.session.read().unwrap();
+        !session.x_security_token.is_empty() && !session.authentication_session.is_empty()
+    }
+
+    /// Opens a pooled connection to the API host and logs in unless the session already is,
+    /// so the next request only pays for itself. Safe to call repeatedly and concurrently:
+    /// calls wait for the one logging in instead of logging in again.
+    pub async fn warm_up(&self) -> Result<(), RequestError> {
+        let _guard = self.warm_up_lock.lock().await;
+        // Any response will do, the point is the DNS lookup and the TLS handshake. A failure
+        // here shows up again, with a better error, in the login below.
+        let _ = self
+            .send(self.http.head(&self.api_url), Idempotency::Idempotent)
+            .await;
+        if !self.is_authenticated() {
+            self.authenticate().await?;
+        }
+        Ok(())
+    }
 
-        let response = post_response::<AuthenticateResponse>(&uri, &map).await?;
+    pub async fn authenticate(&self) -> Result<AuthenticateResponse, RequestError> {
+        // Straight to `fetch`, since logging in never needs the session, and a login that
+        // could log in again would be a recursive future.
+        let response: AuthenticateResponse = self
+            .fetch(
+                self.request(&endpoints::user_credentials(&self.config))?,
+                Idempotency::Idempotent,
+            )
+            .await?;
 
         if response.two_factor_login.method != "TOTP" {
             return Err(RequestError::UnknownAuthenticationMethod());
         }
 
-        self.authenticate_totp(response.two_factor_login.transaction_id.clone())
+        self.authenticate_totp(&response.two_factor_login.transaction_id)
             .await?;
 
         Ok(response)
     }
 
-    async fn authenticate_totp(&mut self, transaction_id: String) -> Result<(), RequestError> {
-        let uri = format!("{}/_api/authentication/sessions/totp", self.api_url);
-        let mut map = HashMap::new();
-        map.insert("totpCode", transaction_id.as_str());
-        map.insert("method", "TOTP");
-
-        let response = post(&uri, &map).await?;
+    async fn authenticate_totp(&self, transaction_id: &str) -> Result<(), RequestError> {
+        let response = self
+            .send(
+                self.request(&endpoints::totp(transaction_id))?,
+                Idempotency::Idempotent,
+            )
+            .await?
+            .error_for_status()?;
 
-        let x_token = String::from_utf8_lossy(
-            response
-                .borrow()
-                .headers()
-                .get("x-securitytoken")
-                .expect("failed to get x-securitytoken")
-                .as_bytes(),
-        )
-        .to_string();
+        // Without a token there is no session to use, whatever the body says.
+        let x_token = response
+            .headers()
+            .get(SECURITY_TOKEN_HEADER)
+            .and_then(|token| token.to_str().ok())
+            .map(String::from)
+            .ok_or(RequestError::NotAuthenticatedError())?;
 
-        let totp_response = response.json::<AuthenticateTOTPResponse>().await?;
+        let totp_response: AuthenticateTOTPResponse =
+            parse_body(response, self.strict_parsing).await?;
 
-        self.x_security_token = x_token;
-        self.session = totp_response.authentication_session;
+        *self.session.write().unwrap() = Session {
+            x_security_token: x_token,
+            authentication_session: totp_response.authentication_session,
+            push_subscription_id: totp_response.push_subscription_id,
+        };
+        if let Some(Metrics(metrics)) = &self.metrics {
+            metrics.record_authentication();
+        }
 
         Ok(())
     }
 }
 
+/// Parses the JSON body into `T` straight from the received bytes, without first copying
+/// them into a `String`. An empty body, e.g. of a 204, parses as `null`, so into an
+/// `Option` it is `None`. An HTML body is a maintenance page, not a parse error. With
+/// `strict`, fields `T` doesn't have are an error too.
+async fn parse_body<T: DeserializeOwned>(
+    response: Response,
+    strict: bool,
+) -> Result<T, RequestError> {
+    let html = is_html(&response);
+    let body = response.bytes().await?;
+    if body.is_empty() {
+        return Ok(serde_json::from_slice::<T>(b"null")?);
+    }
+    if html || body.trim_ascii_start().starts_with(b"<") {
+        let snippet = &body[..body.len().min(MAINTENANCE_SNIPPET_LENGTH)];
+        return Err(RequestError::ServiceUnavailable {
+            body_snippet: String::from_utf8_lossy(snippet).trim().to_string(),
+        });
+    }
+    if strict {
+        return crate::strict::from_slice::<T>(&body);
+    }
+    Ok(serde_json::from_slice::<T>(&body)?)
+}
+
+/// How much longer than the retry policy's backoff to wait after a maintenance page.
+const MAINTENANCE_BACKOFF_FACTOR: u32 = 10;
+const MAINTENANCE_SNIPPET_LENGTH: usize = 200;
+
+fn is_html(response: &Response) -> bool {
+    response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/html"))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn is_json(response: &Response) -> bool {
+    response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.contains("json"))
+}
+
+/// Whether `response` is a maintenance page, and the response to use in its place. Without
+/// an HTML content type a page is only told apart by its body, like in `parse_body`, so a
+/// response that doesn't say it is JSON is read and rebuilt around the read body.
+#[cfg(not(target_arch = "wasm32"))]
+async fn sniff_maintenance(response: Response) -> Result<(bool, Response), RequestError> {
+    if is_html(&response) {
+        return Ok((true, response));
+    }
+    if is_json(&response) {
+        return Ok((false, response));
+    }
+    let mut rebuilt = http::Response::builder()
+        .status(response.status())
+        .version(response.version());
+    if let Some(headers) = rebuilt.headers_mut() {
+        *headers = response.headers().clone();
+    }
+    let body = response.bytes().await?;
+    let html = body.trim_ascii_start().starts_with(b"<");
+    Ok((html, Response::from(rebuilt.body(body).unwrap())))
+}
+
+// A browser response can't be rebuilt, so only the content type is looked at.
+#[cfg(target_arch = "wasm32")]
+async fn sniff_maintenance(response: Response) -> Result<(bool, Response), RequestError> {
+    Ok((is_html(&response), response))
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn is_retryable_error(error: &reqwest::Error) -> bool {
+    error.is_connect() || error.is_timeout()
+}
+
+// The browser's fetch does not tell connection failures apart from other errors.
+#[cfg(target_arch = "wasm32")]
+fn is_retryable_error(error: &reqwest::Error) -> bool {
+    error.is_timeout()
+}
+
 #[cfg(test)]
 mod tests {
     use std::borrow::BorrowMut;
+    use std::collections::BTreeMap;
 
     use super::*;
+    use crate::error::ErrorKind;
+    use crate::metrics::InMemoryMetrics;
+    use crate::test_support::{
+        authenticated_client_with, config, mount_get, mount_overview, mount_positions,
+        mount_successful_auth, CREDENTIALS_RESPONSE, OVERVIEW, POSITIONS, SECURITY_TOKEN,
+        TOTP_RESPONSE,
+    };
     use tokio_test::{assert_err, assert_ok};
-    use wiremock::matchers::{any, method, path};
+    use wiremock::http::HeaderName;
+    use wiremock::matchers::{any, header, method, path};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     #[test]
     fn correct_default_values() {
-        let client = Client::new(Config {
-            avanza_username: String::from("user"),
-            avanza_password: String::from("pass"),
-            avanza_totp_secret: String::from("secret"),
-        });
+        let client = Client::new(config());
+
+        assert_eq!(client.api_url, String::from("https://www.avanza.se"));
+        assert_eq!(client.user_agent, String::from("Avanza API client"));
+        assert_eq!(
+            client.push_url,
+            String::from("wss://www.avanza.se/_push/cometd")
+        );
+    }
+    #[test]
+    fn builder_defaults_match_new() {
+        let client = assert_ok!(Client::builder().config(config()).build());
 
         assert_eq!(client.api_url, String::from("https://www.avanza.se"));
         assert_eq!(client.user_agent, String::from("Avanza API client"));
+        assert_eq!(
+            client.push_url,
+            String::from("wss://www.avanza.se/_push/cometd")
+        );
+        assert_eq!(client.retry_policy, RetryPolicy::none());
+        assert_eq!(client.rate_limit, None);
     }
     #[test]
     fn can_set_api_url() {
-        let client = Client::new(Config {
-            avanza_username: String::from("user"),
-            avanza_password: String::from("pass"),
-            avanza_totp_secret: String::from("secret"),
-        })
-        .api_url(String::from("https://avanza-new.se"));
+        let client = Client::builder()
+            .config(config())
+            .api_url("https://avanza-new.se")
+            .build()
+            .unwrap();
 
         assert_eq!(client.api_url, String::from("https://avanza-new.se"));
     }
     #[test]
     fn can_set_user_agent() {
-        let client = Client::new(Config {
-            avanza_username: String::from("user"),
-            avanza_password: String::from("pass"),
-            avanza_totp_secret: String::from("secret"),
-        })
-        .user_agent(String::from("My custom user agent"));
+        let client = Client::builder()
+            .config(config())
+            .user_agent(String::from("My custom user agent"))
+            .build()
+            .unwrap();
 
         assert_eq!(client.user_agent, String::from("My custom user agent"));
     }
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_setters_still_apply() {
+        let client = Client::new(config())
+            .api_url(String::from("https://avanza-new.se"))
+            .user_agent(String::from("My custom user agent"))
+            .push_url(String::from("wss://push.avanza-new.se"));
 
+        assert_eq!(client.api_url, String::from("https://avanza-new.se"));
+        assert_eq!(client.user_agent, String::from("My custom user agent"));
+        assert_eq!(client.push_url, String::from("wss://push.avanza-new.se"));
+    }
     #[tokio::test]
-    async fn raises_error_on_unknown_authentication_method() {
-        let mock_server = MockServer::start().await;
+    #[allow(deprecated)]
+    async fn invalid_api_url_from_deprecated_setter_is_an_error() {
+        let client = Client::new(config()).api_url(String::from("not a url"));
 
-        let responder = ResponseTemplate::new(200).set_body_string(
-            String::from("{\"twoFactorLogin\":{\"transactionId\":\"4530ff65-a4d3-4af0-9e9b-22729a6157c9\",\"method\":\"BANKID\"}}")
+        let error = assert_err!(client.authenticate().await);
+        assert!(
+            matches!(
+                error,
+                RequestError::InvalidConfig(ConfigError::InvalidUrl(ref url)) if url == "not a url"
+            ),
+            "{:?}",
+            error
         );
+        assert_eq!(error.kind(), ErrorKind::Config);
+    }
 
-        Mock::given(method("POST"))
-            .and(path("/_api/authentication/sessions/usercredentials"))
-            .respond_with(responder)
-            .mount(&mock_server)
-            .await;
+    #[test]
+    fn can_use_credentials_instead_of_config() {
+        let client = assert_ok!(Client::builder()
+            .credentials("user", "pass", "secret")
+            .build());
 
-        let mut client = Client::new(Config {
-            avanza_username: String::from("user"),
-            avanza_password: String::from("pass"),
-            avanza_totp_secret: String::from("secret"),
-        })
-        .api_url(mock_server.uri());
+        assert_eq!(client.config.avanza_username, String::from("user"));
+    }
+    #[test]
+    fn rejects_missing_credentials() {
+        assert!(matches!(
+            Client::builder().build(),
+            Err(ConfigError::MissingCredentials())
+        ));
+    }
+    #[test]
+    fn rejects_config_and_credentials() {
+        assert!(matches!(
+            Client::builder()
+                .config(config())
+                .credentials("user", "pass", "secret")
+                .build(),
+            Err(ConfigError::ConflictingOptions("config", "credentials"))
+        ));
+    }
+    #[test]
+    fn rejects_bad_urls() {
+        assert!(matches!(
+            Client::builder()
+                .config(config())
+                .api_url("not a url")
+                .build(),
+            Err(ConfigError::InvalidUrl(_))
+        ));
+        assert!(matches!(
+            Client::builder()
+                .config(config())
+                .api_url("wss://www.avanza.se")
+                .build(),
+            Err(ConfigError::InvalidUrl(_))
+        ));
+        assert!(matches!(
+            Client::builder()
+                .config(config())
+                .push_url("https://www.avanza.se/_push/cometd")
+                .build(),
+            Err(ConfigError::InvalidUrl(_))
+        ));
+    }
+    #[test]
+    fn rejects_bad_proxy_timeout_and_rate_limit() {
+        assert!(matches!(
+            Client::builder().config(config()).proxy("::").build(),
+            Err(ConfigError::InvalidProxy(_))
+        ));
+        assert!(matches!(
+            Client::builder()
+                .config(config())
+                .timeout(Duration::ZERO)
+                .build(),
+            Err(ConfigError::InvalidTimeout())
+        ));
+        assert!(matches!(
+            Client::builder()
+                .config(config())
+                .rate_limit(RateLimit::per_second(0))
+                .build(),
+            Err(ConfigError::InvalidRateLimit(_))
+        ));
+    }
 
-        assert_err!(client.authenticate().await);
+    #[test]
+    fn rejects_http_client_with_timeout_or_proxy() {
+        assert!(matches!(
+            Client::builder()
+                .config(config())
+                .with_http_client(reqwest::Client::new())
+                .timeout(Duration::from_secs(10))
+                .build(),
+            Err(ConfigError::ConflictingOptions("http_client", "timeout"))
+        ));
+        assert!(matches!(
+            Client::builder()
+                .config(config())
+                .with_http_client(reqwest::Client::new())
+                .proxy("http://localhost:3128")
+                .build(),
+            Err(ConfigError::ConflictingOptions("http_client", "proxy"))
+        ));
     }
 
     #[tokio::test]
-    async fn authentication_success() {
+    async fn sends_everything_on_the_injected_http_client() {
         let mock_server = MockServer::start().await;
+        mount_positions(&mock_server, POSITIONS).await;
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("x-tuned", "yes".parse().unwrap());
+        let http = reqwest::Client::builder()
+            .default_headers(headers)
+            .user_agent("my-app/1.0")
+            .build()
+            .unwrap();
+        let client =
+            authenticated_client_with(&mock_server, Client::builder().with_http_client(http)).await;
+        client
+            .get_positions()
+            .await
+            .expect("failed to get positions");
 
-        let responder = ResponseTemplate::new(200).set_body_string(
-            String::from("{\"twoFactorLogin\":{\"transactionId\":\"4530ff65-a4d3-4af0-9e9b-22729a6157c9\",\"method\":\"TOTP\"}}")
+        let requests = mock_server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 3, "both logins and the positions");
+        for request in requests {
+            assert_eq!(
+                request
+                    .headers
+                    .get(&HeaderName::from("x-tuned"))
+                    .map(|value| value.as_str()),
+                Some("yes"),
+                "{}",
+                request.url
+            );
+            assert_eq!(
+                request
+                    .headers
+                    .get(&HeaderName::from("user-agent"))
+                    .map(|value| value.as_str()),
+                Some("my-app/1.0"),
+                "{}",
+                request.url
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn user_agent_overrides_the_injected_http_clients() {
+        let mock_server = MockServer::start().await;
+        let http = reqwest::Client::builder()
+            .user_agent("my-app/1.0")
+            .build()
+            .unwrap();
+        let _client = authenticated_client_with(
+            &mock_server,
+            Client::builder()
+                .with_http_client(http)
+                .user_agent("avanza-bot"),
+        )
+        .await;
+
+        let requests = mock_server.received_requests().await.unwrap();
+        assert_eq!(
+            requests[0]
+                .headers
+                .get(&HeaderName::from("user-agent"))
+                .map(|value| value.as_str()),
+            Some("avanza-bot")
+        );
+    }
+
+    #[test]
+    fn rejects_empty_instrument_cache() {
+        assert!(matches!(
+            Client::builder()
+                .config(config())
+                .instrument_cache(Duration::from_secs(60), 0)
+                .build(),
+            Err(ConfigError::InvalidInstrumentCache())
+        ));
+        assert!(matches!(
+            Client::builder()
+                .config(config())
+                .instrument_cache(Duration::ZERO, 100)
+                .build(),
+            Err(ConfigError::InvalidInstrumentCache())
+        ));
+        let client = Client::builder().config(config()).build().unwrap();
+        assert!(matches!(
+            client.with_instrument_cache(Duration::ZERO, 100),
+            Err(ConfigError::InvalidInstrumentCache())
+        ));
+    }
+
+    #[test]
+    fn rejects_empty_orderbook_chunks() {
+        assert!(matches!(
+            Client::builder()
+                .config(config())
+                .orderbook_chunk_size(0)
+                .build(),
+            Err(ConfigError::InvalidOrderbookChunkSize())
+        ));
+        let client = assert_ok!(Client::builder()
+            .config(config())
+            .orderbook_chunk_size(10)
+            .build());
+        assert_eq!(client.orderbook_chunk_size, 10);
+        assert!(matches!(
+            client.clone().with_orderbook_chunk_size(0),
+            Err(ConfigError::InvalidOrderbookChunkSize())
+        ));
+        let client = assert_ok!(client.with_orderbook_chunk_size(5));
+        assert_eq!(client.orderbook_chunk_size, 5);
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/flaky"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/flaky"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("42"))
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client_with(
+            &mock_server,
+            Client::builder().retry_policy(RetryPolicy::new(2, Duration::from_millis(1))),
+        )
+        .await;
+
+        let uri = format!("{}/flaky", mock_server.uri());
+        assert_eq!(assert_ok!(client.get_response::<u32>(&uri).await), 42);
+    }
+
+    #[tokio::test]
+    async fn call_timeout_overrides_the_clients() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/slow"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("42")
+                    .set_delay(Duration::from_millis(300)),
+            )
+            .mount(&mock_server)
+            .await;
+        let uri = format!("{}/slow", mock_server.uri());
+        let client =
+            |timeout| authenticated_client_with(&mock_server, Client::builder().timeout(timeout));
+
+        let impatient = client(Duration::from_millis(100)).await;
+        assert_err!(impatient.get_response::<u32>(&uri).await);
+        let patient = impatient.with_options(CallOptions::new().timeout(Duration::from_secs(5)));
+        assert_eq!(assert_ok!(patient.get_response::<u32>(&uri).await), 42);
+
+        let patient = client(Duration::from_secs(5)).await;
+        assert_ok!(patient.get_response::<u32>(&uri).await);
+        let impatient =
+            patient.with_options(CallOptions::new().timeout(Duration::from_millis(100)));
+        assert_err!(impatient.get_response::<u32>(&uri).await);
+    }
+
+    #[tokio::test]
+    async fn call_retry_policy_overrides_the_clients() {
+        async fn flaky(mock_server: &MockServer, failures: u64) -> String {
+            Mock::given(method("GET"))
+                .and(path("/flaky"))
+                .respond_with(ResponseTemplate::new(503))
+                .up_to_n_times(failures)
+                .expect(failures)
+                .mount(mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/flaky"))
+                .respond_with(ResponseTemplate::new(200).set_body_string("42"))
+                .mount(mock_server)
+                .await;
+            format!("{}/flaky", mock_server.uri())
+        }
+
+        // The client would retry past the failure, the call gives up on it.
+        let mock_server = MockServer::start().await;
+        let uri = flaky(&mock_server, 1).await;
+        let client = authenticated_client_with(
+            &mock_server,
+            Client::builder().retry_policy(RetryPolicy::new(5, Duration::from_millis(1))),
+        )
+        .await;
+        let no_retries = client.with_options(CallOptions::new().retry_policy(RetryPolicy::none()));
+        assert_err!(no_retries.get_response::<u32>(&uri).await);
+
+        // The client wouldn't retry at all, the call retries past both failures.
+        let mock_server = MockServer::start().await;
+        let uri = flaky(&mock_server, 2).await;
+        let client = authenticated_client_with(
+            &mock_server,
+            Client::builder().retry_policy(RetryPolicy::none()),
+        )
+        .await;
+        let retrying = client.with_options(
+            CallOptions::new().retry_policy(RetryPolicy::new(2, Duration::from_millis(1))),
         );
+        assert_eq!(assert_ok!(retrying.get_response::<u32>(&uri).await), 42);
+    }
 
-        let mut responder_totp = ResponseTemplate::new(200).set_body_string(
-            String::from("{\"authenticationSession\":\"4530ff65-a4d3-4af0-9e9b-22729a6157c9\",\"pushSubscriptionId\":\"54320ff65-a4d3-4af0-9e9b-22729a6157c9\",\"customerId\":\"123232\", \"registrationComplete\": true}")
+    #[tokio::test]
+    async fn call_headers_are_added() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/positions"))
+            .and(header("x-request-id", "nightly-export"))
+            .and(header("x-securitytoken", SECURITY_TOKEN))
+            .respond_with(ResponseTemplate::new(200).set_body_string(POSITIONS))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let client = authenticated_client_with(
+            &mock_server,
+            Client::builder().retry_policy(RetryPolicy::none()),
+        )
+        .await;
+
+        assert_err!(client.get_positions().await);
+        let tagged =
+            client.with_options(CallOptions::new().header("x-request-id", "nightly-export"));
+        assert_ok!(tagged.get_positions().await);
+    }
+
+    #[tokio::test]
+    async fn overridden_endpoint_moves_only_that_endpoint() {
+        let mock_server = MockServer::start().await;
+        mount_get(&mock_server, "/_api/position-data/positions", POSITIONS).await;
+        mount_overview(&mock_server, OVERVIEW).await;
+        let client = authenticated_client_with(
+            &mock_server,
+            Client::builder()
+                .override_endpoint(EndpointId::Positions, "/_api/position-data/positions"),
+        )
+        .await;
+
+        assert_ok!(client.get_positions().await);
+        assert_ok!(client.get_overview().await);
+
+        let paths: Vec<String> = mock_server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| request.url.path().to_string())
+            .collect();
+        assert_eq!(
+            paths[2..],
+            ["/_api/position-data/positions", "/_mobile/account/overview"]
         );
+    }
 
-        responder_totp = responder_totp.append_header("x-securitytoken", "mysecrettoken");
+    #[test]
+    fn rejects_invalid_endpoint_override() {
+        assert!(matches!(
+            Client::builder()
+                .config(config())
+                .override_endpoint(EndpointId::CashBalance, "/_api/cash/{orderbook_id}")
+                .build(),
+            Err(ConfigError::InvalidEndpointPath(EndpointId::CashBalance, _))
+        ));
+    }
+
+    #[tokio::test]
+    async fn strict_parsing_rejects_unknown_fields() {
+        let mock_server = MockServer::start().await;
+        let drifted = POSITIONS.replace(r#""totalBalance""#, r#""newField":true,"totalBalance""#);
+        mount_positions(&mock_server, &drifted).await;
+        let client = |strict| {
+            authenticated_client_with(&mock_server, Client::builder().strict_parsing(strict))
+        };
 
+        let lenient = client(false).await;
+        assert_ok!(lenient.get_positions().await);
+
+        let strict = client(true).await;
+        let error = assert_err!(strict.get_positions().await);
+        assert!(
+            matches!(&error, RequestError::UnknownFields(paths) if paths == &["newField"]),
+            "{:?}",
+            error
+        );
+        assert_eq!(error.kind(), ErrorKind::Parse);
+    }
+
+    const MAINTENANCE_PAGE: &str = "<!DOCTYPE html>\n<html><head><title>Avanza</title></head><body><h1>Vi uppdaterar just nu</h1></body></html>";
+
+    #[tokio::test]
+    async fn html_is_service_unavailable() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/positions"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(MAINTENANCE_PAGE, "text/html; charset=utf-8"),
+            )
+            .mount(&mock_server)
+            .await;
+        let client = authenticated_client_with(
+            &mock_server,
+            Client::builder().retry_policy(RetryPolicy::none()),
+        )
+        .await;
+
+        let error = assert_err!(client.get_positions().await);
+
+        assert_eq!(error.kind(), ErrorKind::ServiceUnavailable);
+        let RequestError::ServiceUnavailable { body_snippet } = error else {
+            panic!("expected ServiceUnavailable, got {:?}", error);
+        };
+        assert!(body_snippet.starts_with("<!DOCTYPE html>"));
+    }
+
+    #[tokio::test]
+    async fn html_without_content_type_is_service_unavailable() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/positions"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(format!("\n  {}", MAINTENANCE_PAGE)),
+            )
+            .mount(&mock_server)
+            .await;
+        let client = authenticated_client_with(
+            &mock_server,
+            Client::builder().retry_policy(RetryPolicy::none()),
+        )
+        .await;
+
+        assert!(matches!(
+            client.get_positions().await,
+            Err(RequestError::ServiceUnavailable { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn retries_maintenance_pages() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/positions"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(MAINTENANCE_PAGE, "text/html"))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        mount_positions(&mock_server, POSITIONS).await;
+        let client = authenticated_client_with(
+            &mock_server,
+            Client::builder().retry_policy(RetryPolicy::new(2, Duration::from_millis(1))),
+        )
+        .await;
+
+        assert_ok!(client.get_positions().await);
+    }
+
+    #[tokio::test]
+    async fn retries_maintenance_pages_without_content_type() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/positions"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(MAINTENANCE_PAGE))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        mount_positions(&mock_server, POSITIONS).await;
+        let client = authenticated_client_with(
+            &mock_server,
+            Client::builder().retry_policy(RetryPolicy::new(2, Duration::from_millis(1))),
+        )
+        .await;
+
+        assert_ok!(client.get_positions().await);
+    }
+
+    #[tokio::test]
+    async fn warm_up_logs_in_once() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(4)
+            .mount(&mock_server)
+            .await;
         Mock::given(method("POST"))
             .and(path("/_api/authentication/sessions/usercredentials"))
-            .respond_with(responder)
+            .respond_with(ResponseTemplate::new(200).set_body_string(CREDENTIALS_RESPONSE))
+            .expect(1)
             .mount(&mock_server)
             .await;
         Mock::given(method("POST"))
             .and(path("/_api/authentication/sessions/totp"))
-            .respond_with(responder_totp)
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(TOTP_RESPONSE)
+                    .append_header("x-securitytoken", SECURITY_TOKEN),
+            )
+            .expect(1)
             .mount(&mock_server)
             .await;
+        mount_overview(&mock_server, OVERVIEW).await;
 
-        let mut client = Client::new(Config {
-            avanza_username: String::from("user"),
-            avanza_password: String::from("pass"),
-            avanza_totp_secret: String::from("secret"),
-        })
-        .api_url(mock_server.uri());
+        let client = Client::builder()
+            .config(config())
+            .api_url(mock_server.uri())
+            .build()
+            .unwrap();
+
+        let (a, b, c) = tokio::join!(client.warm_up(), client.warm_up(), client.warm_up());
+        assert_ok!(a);
+        assert_ok!(b);
+        assert_ok!(c);
+        assert_ok!(client.warm_up().await);
+        assert!(client.is_authenticated());
+
+        assert_ok!(client.get_overview().await);
+    }
+
+    /// Logs in once per token, handing them out in order.
+    async fn mount_logins(mock_server: &MockServer, tokens: &[&str]) {
+        Mock::given(method("POST"))
+            .and(path("/_api/authentication/sessions/usercredentials"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(CREDENTIALS_RESPONSE))
+            .expect(tokens.len() as u64)
+            .mount(mock_server)
+            .await;
+        for token in tokens {
+            Mock::given(method("POST"))
+                .and(path("/_api/authentication/sessions/totp"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string(TOTP_RESPONSE)
+                        .append_header("x-securitytoken", *token),
+                )
+                .up_to_n_times(1)
+                .expect(1)
+                .mount(mock_server)
+                .await;
+        }
+    }
+
+    /// Answers 401 to the first session and `body` to the second.
+    async fn mount_expiring(mock_server: &MockServer, endpoint: &str, body: &str) {
+        Mock::given(method("GET"))
+            .and(path(endpoint))
+            .and(header("x-securitytoken", "expired"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(endpoint))
+            .and(header("x-securitytoken", "renewed"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .expect(1)
+            .mount(mock_server)
+            .await;
+    }
+
+    // Logs in through the mocks the test mounted, not `mount_successful_auth`.
+    async fn logged_in_client(mock_server: &MockServer) -> Client {
+        let client = Client::builder()
+            .config(config())
+            .api_url(mock_server.uri())
+            .build()
+            .unwrap();
+        client.authenticate().await.expect("failed to authenticate");
+        client
+    }
+
+    #[tokio::test]
+    async fn logs_in_again_and_replays_positions_on_unauthorized() {
+        let mock_server = MockServer::start().await;
+        mount_logins(&mock_server, &["expired", "renewed"]).await;
+        mount_expiring(&mock_server, "/_mobile/account/positions", POSITIONS).await;
+        let client = logged_in_client(&mock_server).await;
+
+        let positions = assert_ok!(client.get_positions().await);
+        assert_eq!(positions.total_balance, 4000.0);
+    }
+
+    #[tokio::test]
+    async fn logs_in_again_and_replays_overview_on_unauthorized() {
+        let mock_server = MockServer::start().await;
+        mount_logins(&mock_server, &["expired", "renewed"]).await;
+        mount_expiring(&mock_server, "/_mobile/account/overview", OVERVIEW).await;
+        let client = logged_in_client(&mock_server).await;
+
+        let overview = assert_ok!(client.get_overview().await);
+        assert_eq!(overview.total_buying_power, 250.0);
+    }
+
+    #[tokio::test]
+    async fn concurrent_unauthorized_requests_log_in_once() {
+        let mock_server = MockServer::start().await;
+        mount_logins(&mock_server, &["expired", "renewed"]).await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/overview"))
+            .and(header("x-securitytoken", "expired"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/overview"))
+            .and(header("x-securitytoken", "renewed"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(OVERVIEW))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+        let client = logged_in_client(&mock_server).await;
+
+        let (a, b, c) = tokio::join!(
+            client.get_overview(),
+            client.get_overview(),
+            client.get_overview()
+        );
+        assert_ok!(a);
+        assert_ok!(b);
+        assert_ok!(c);
+    }
+
+    #[tokio::test]
+    async fn gives_up_when_still_unauthorized() {
+        let mock_server = MockServer::start().await;
+        mount_logins(&mock_server, &["expired", "renewed"]).await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/overview"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        let client = logged_in_client(&mock_server).await;
+
+        assert_err!(client.get_overview().await);
+    }
+
+    #[tokio::test]
+    async fn warm_up_fails_when_login_fails() {
+        let client = Client::builder()
+            .config(config())
+            .api_url("http://127.0.0.1:1")
+            .build()
+            .unwrap();
+
+        assert_err!(client.warm_up().await);
+        assert!(!client.is_authenticated());
+    }
+
+    #[tokio::test]
+    async fn can_get_raw_response() {
+        let mock_server = MockServer::start().await;
+        mount_overview(&mock_server, OVERVIEW).await;
+
+        let client = authenticated_client_with(
+            &mock_server,
+            Client::builder().retry_policy(RetryPolicy::none()),
+        )
+        .await;
+
+        let uri = format!("{}/_mobile/account/overview", mock_server.uri());
+        let overview = assert_ok!(client.get_response_raw(&uri).await);
+        assert_eq!(overview["totalBuyingPower"], 250);
+        assert!(overview["accounts"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_retries() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/down"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client_with(
+            &mock_server,
+            Client::builder().retry_policy(RetryPolicy::new(1, Duration::from_millis(1))),
+        )
+        .await;
+
+        let uri = format!("{}/down", mock_server.uri());
+        assert_err!(client.get_response::<u32>(&uri).await);
+    }
+
+    #[tokio::test]
+    async fn collects_metrics() {
+        let mock_server = MockServer::start().await;
+        mount_successful_auth(&mock_server).await;
+        mount_overview(&mock_server, OVERVIEW).await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/123/down"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&mock_server)
+            .await;
+
+        let metrics = Arc::new(InMemoryMetrics::new());
+        let client = Client::builder()
+            .config(config())
+            .api_url(mock_server.uri())
+            .retry_policy(RetryPolicy::new(1, Duration::from_millis(1)))
+            .metrics(metrics.clone())
+            .build()
+            .unwrap();
 
         assert_ok!(client.authenticate().await);
+        assert_ok!(client.get_overview().await);
+        assert_ok!(client.get_overview().await);
+        let down = format!("{}/_mobile/account/123/down", mock_server.uri());
+        assert_err!(client.get_response::<u32>(&down).await);
+
+        let unreachable = Client::builder()
+            .config(config())
+            .api_url("http://127.0.0.1:1")
+            .metrics(metrics.clone())
+            .build()
+            .unwrap();
+        assert_err!(unreachable.authenticate().await);
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.authentications, 1);
+
+        let overview = &snapshot.endpoints["GET /_mobile/account/overview"];
+        assert_eq!(overview.responses, BTreeMap::from([(200, 2)]));
+        assert_eq!(overview.latency.count, 2);
+        assert!(overview.errors.is_empty());
+
+        // Both attempts are counted, the error status of the last one once.
+        let down = &snapshot.endpoints["GET /_mobile/account/{id}/down"];
+        assert_eq!(down.responses, BTreeMap::from([(503, 2)]));
+        assert_eq!(down.errors, BTreeMap::from([(ErrorKind::Request, 1)]));
+
+        let login = &snapshot.endpoints["POST /_api/authentication/sessions/usercredentials"];
+        assert_eq!(login.requests(), 1);
+        assert_eq!(login.errors, BTreeMap::from([(ErrorKind::Connect, 1)]));
+
+        assert_eq!(snapshot.errors(ErrorKind::Request), 1);
+        assert_eq!(snapshot.errors(ErrorKind::Connect), 1);
+        assert_eq!(snapshot.rate_limited_requests, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn cancelled_wait_is_not_counted_as_waiting() {
+        let client = Client::builder()
+            .config(config())
+            .rate_limit(RateLimit::per_second(1))
+            .build()
+            .unwrap();
+
+        client.wait_for_rate_limit().await;
+        let waited =
+            tokio::time::timeout(Duration::from_millis(10), client.wait_for_rate_limit()).await;
+
+        assert_err!(waited);
+        assert_eq!(client.rate_limit_waiting.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn collects_rate_limit_metrics() {
+        let metrics = Arc::new(InMemoryMetrics::new());
+        let client = Client::builder()
+            .config(config())
+            .rate_limit(RateLimit::per_second(4))
+            .metrics(metrics.clone())
+            .build()
+            .unwrap();
+
+        for _ in 0..3 {
+            client.wait_for_rate_limit().await;
+        }
+
+        // Everything after the first request waits its turn.
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.rate_limited_requests, 2);
+        assert_eq!(snapshot.rate_limit_delay, Duration::from_millis(500));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rate_limit_spaces_out_requests() {
+        let client = Client::builder()
+            .config(config())
+            .rate_limit(RateLimit::per_second(4))
+            .build()
+            .unwrap();
+
+        let start = Instant::now();
+        for _ in 0..3 {
+            client.wait_for_rate_limit().await;
+        }
+        assert_eq!(start.elapsed(), Duration::from_millis(500));
     }
 
     #[tokio::test]
-    async fn authentication_totp_set_auth() {
+    async fn raises_error_on_unknown_authentication_method() {
         let mock_server = MockServer::start().await;
 
-        let mut responder = ResponseTemplate::new(200).set_body_string(
-            String::from("{\"authenticationSession\":\"4530ff65-a4d3-4af0-9e9b-22729a6157c9\",\"pushSubscriptionId\":\"54320ff65-a4d3-4af0-9e9b-22729a6157c9\",\"customerId\":\"123232\", \"registrationComplete\": true}")
+        let responder = ResponseTemplate::new(200).set_body_string(
+            String::from("{\"twoFactorLogin\":{\"transactionId\":\"4530ff65-a4d3-4af0-9e9b-22729a6157c9\",\"method\":\"BANKID\"}}")
         );
 
-        responder = responder.append_header("x-securitytoken", "mysecrettoken");
+        Mock::given(method("POST"))
+            .and(path("/_api/authentication/sessions/usercredentials"))
+            .respond_with(responder)
+            .mount(&mock_server)
+            .await;
+
+        let client = Client::builder()
+            .config(config())
+            .api_url(mock_server.uri())
+            .build()
+            .unwrap();
+
+        assert_err!(client.authenticate().await);
+    }
+
+    #[tokio::test]
+    async fn authentication_success() {
+        let mock_server = MockServer::start().await;
+
+        mount_successful_auth(&mock_server).await;
+
+        let client = Client::builder()
+            .config(config())
+            .api_url(mock_server.uri())
+            .build()
+            .unwrap();
+
+        assert_ok!(client.authenticate().await);
+    }
+
+    #[tokio::test]
+    async fn authentication_totp_set_auth() {
+        let mock_server = MockServer::start().await;
+
+        let responder = ResponseTemplate::new(200)
+            .set_body_string(TOTP_RESPONSE)
+            .append_header("x-securitytoken", SECURITY_TOKEN);
 
         Mock::given(any())
             .respond_with(responder)
@@ -261,22 +1970,208 @@ mod tests {
             .mount(&mock_server)
             .await;
 
-        let mut client = Client::new(Config {
-            avanza_username: String::from("user"),
-            avanza_password: String::from("pass"),
-            avanza_totp_secret: String::from("secret"),
-        })
-        .api_url(mock_server.uri());
+        let mut client = Client::builder()
+            .config(config())
+            .api_url(mock_server.uri())
+            .build()
+            .unwrap();
 
         assert_ok!(
             client
                 .borrow_mut()
-                .authenticate_totp(String::from("4530ff65-a4d3-4af0-9e9b-22729a6157c9"))
+                .authenticate_totp("4530ff65-a4d3-4af0-9e9b-22729a6157c9")
                 .await
         );
 
-        assert_eq!("mysecrettoken", client.x_security_token);
-        assert_eq!("4530ff65-a4d3-4af0-9e9b-22729a6157c9", client.session);
-        assert_eq!(true, client.is_authenticated());
+        let session = client.session.read().unwrap();
+        assert_eq!(SECURITY_TOKEN, session.x_security_token);
+        assert_eq!(
+            "4530ff65-a4d3-4af0-9e9b-22729a6157c9",
+            session.authentication_session
+        );
+        drop(session);
+        assert_eq!(
+            "54320ff65-a4d3-4af0-9e9b-22729a6157c9",
+            client.push_subscription_id()
+        );
+        assert!(client.is_authenticated());
+    }
+
+    #[tokio::test]
+    async fn rejected_totp_is_an_error() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/_api/authentication/sessions/usercredentials"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(CREDENTIALS_RESPONSE))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/_api/authentication/sessions/totp"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&mock_server)
+            .await;
+
+        let client = Client::builder()
+            .config(config())
+            .api_url(mock_server.uri())
+            .build()
+            .unwrap();
+
+        assert_err!(client.authenticate().await);
+        assert!(!client.is_authenticated());
+    }
+
+    #[tokio::test]
+    async fn totp_without_security_token_is_an_error() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/_api/authentication/sessions/usercredentials"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(CREDENTIALS_RESPONSE))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/_api/authentication/sessions/totp"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(TOTP_RESPONSE))
+            .mount(&mock_server)
+            .await;
+
+        let client = Client::builder()
+            .config(config())
+            .api_url(mock_server.uri())
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            client.authenticate().await,
+            Err(RequestError::NotAuthenticatedError())
+        ));
+    }
+
+    #[test]
+    fn client_is_shareable() {
+        fn assert_shareable<T: Clone + Send + Sync + 'static>() {}
+        assert_shareable::<Client>();
+    }
+
+    #[tokio::test]
+    async fn concurrent_requests_on_shared_client() {
+        let mock_server = MockServer::start().await;
+
+        let responder = ResponseTemplate::new(200).set_body_string(CREDENTIALS_RESPONSE);
+        let responder_totp = ResponseTemplate::new(200)
+            .set_body_string(TOTP_RESPONSE)
+            .append_header("x-securitytoken", SECURITY_TOKEN);
+
+        Mock::given(method("POST"))
+            .and(path("/_api/authentication/sessions/usercredentials"))
+            .respond_with(responder)
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/_api/authentication/sessions/totp"))
+            .respond_with(responder_totp)
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/overview"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(OVERVIEW)
+                    .set_delay(Duration::from_millis(50)),
+            )
+            .expect(5)
+            .mount(&mock_server)
+            .await;
+
+        let client = Arc::new(
+            Client::builder()
+                .config(config())
+                .api_url(mock_server.uri())
+                .build()
+                .unwrap(),
+        );
+        client.authenticate().await.expect("failed to authenticate");
+
+        let mut tasks = Vec::new();
+        for _ in 0..5 {
+            let client = client.clone();
+            tasks.push(tokio::spawn(async move { client.get_overview().await }));
+        }
+        let reauth = {
+            let client = client.clone();
+            tokio::spawn(async move { client.authenticate().await })
+        };
+
+        let finished = tokio::time::timeout(Duration::from_secs(5), async {
+            for task in tasks {
+                let overview = assert_ok!(task.await.unwrap());
+                assert_eq!(overview.total_buying_power, 250.0);
+            }
+            assert_ok!(reauth.await.unwrap());
+        })
+        .await;
+        assert_ok!(finished);
+        assert!(client.is_authenticated());
+    }
+
+    #[tokio::test]
+    async fn records_and_replays_without_network() {
+        let cassette = std::env::temp_dir().join(format!(
+            "avanza-cassette-{}-{:?}.json",
+            std::process::id(),
+            Instant::now()
+        ));
+        let mock_server = MockServer::start().await;
+
+        mount_successful_auth(&mock_server).await;
+        mount_overview(&mock_server, OVERVIEW).await;
+
+        let recording = Client::builder()
+            .config(config())
+            .api_url(mock_server.uri())
+            .build()
+            .unwrap()
+            .record_to(&cassette);
+        assert_ok!(recording.authenticate().await);
+        let recorded = assert_ok!(recording.get_overview().await);
+
+        let api_url = mock_server.uri();
+        drop(mock_server);
+
+        let contents = std::fs::read_to_string(&cassette).unwrap();
+        assert!(!contents.contains(SECURITY_TOKEN));
+        assert!(!contents.contains("\\\"pass\\\""));
+        assert!(!contents.contains("4530ff65"));
+
+        let replaying = Client::builder()
+            .config(config())
+            .api_url(api_url)
+            .build()
+            .unwrap()
+            .replay_from(&cassette)
+            .unwrap();
+        assert_ok!(replaying.authenticate().await);
+        assert!(replaying.is_authenticated());
+        assert_eq!(assert_ok!(replaying.get_overview().await), recorded);
+        assert_eq!(assert_ok!(replaying.get_overview().await), recorded);
+        assert!(matches!(
+            replaying.get_deals_and_orders().await,
+            Err(RequestError::UnmatchedReplay(_))
+        ));
+
+        std::fs::remove_file(&cassette).unwrap();
+    }
+
+    #[test]
+    fn replay_needs_a_readable_cassette() {
+        let missing = std::env::temp_dir().join("avanza-missing-cassette.json");
+        assert!(matches!(
+            Client::new(config()).replay_from(missing),
+            Err(ConfigError::InvalidCassette(_))
+        ));
     }
 }
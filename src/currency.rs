@@ -0,0 +1,215 @@
+//! Conversion between currencies from a table of exchange rates.
+
+use std::collections::HashMap;
+
+use crate::error::ConversionError;
+use crate::market::exchange_rates::ExchangeRate;
+use crate::portfolio::positions::PositionsResponse;
+use crate::types::Currency;
+
+/// Converts amounts with a fixed set of exchange rates, e.g. from
+/// [`Client::currency_converter`](crate::client::Client::currency_converter).
+///
+/// A rate also converts the other way, by its inverse. Pairs without a rate of their own go
+/// through SEK, which Avanza quotes every currency against. Only the final amount is rounded,
+/// to two decimals, so converting through SEK is as precise as a direct rate would be.
+#[derive(Debug, Clone, Default)]
+pub struct CurrencyConverter {
+    rates: HashMap<(Currency, Currency), f64>,
+}
+
+impl CurrencyConverter {
+    pub fn from_rates(rates: impl IntoIterator<Item = ExchangeRate>) -> Self {
+        let rates = rates
+            .into_iter()
+            .map(|rate| ((rate.from_currency, rate.to_currency), rate.rate))
+            .collect();
+        Self { rates }
+    }
+
+    /// `amount` in `from` expressed in `to`, rounded to two decimals.
+    pub fn convert(
+        &self,
+        amount: f64,
+        from: &Currency,
+        to: &Currency,
+    ) -> Result<f64, ConversionError> {
+        Ok(round(amount * self.rate(from, to)?))
+    }
+
+    /// How many units of `to` one unit of `from` is worth.
+    pub fn rate(&self, from: &Currency, to: &Currency) -> Result<f64, ConversionError> {
+        if let Some(rate) = self.direct_rate(from, to) {
+            return Ok(rate);
+        }
+        let to_sek = self
+            .direct_rate(from, &Currency::SEK)
+            .ok_or_else(|| ConversionError::UnknownCurrency(from.clone()))?;
+        let from_sek = self
+            .direct_rate(&Currency::SEK, to)
+            .ok_or_else(|| ConversionError::UnknownCurrency(to.clone()))?;
+        Ok(to_sek * from_sek)
+    }
+
+    fn direct_rate(&self, from: &Currency, to: &Currency) -> Option<f64> {
+        if from == to {
+            return Some(1.0);
+        }
+        self.rates
+            .get(&(from.clone(), to.clone()))
+            .copied()
+            .or_else(|| {
+                self.rates
+                    .get(&(to.clone(), from.clone()))
+                    .map(|rate| 1.0 / rate)
+            })
+    }
+}
+
+fn round(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+impl PositionsResponse {
+    /// The value of all positions in `currency`, each converted from the currency it is
+    /// valued in. Only the total is rounded.
+    pub fn total_value_in(
+        &self,
+        converter: &CurrencyConverter,
+        currency: Currency,
+    ) -> Result<f64, ConversionError> {
+        let mut total = 0.0;
+        for position in self
+            .instrument_positions
+            .iter()
+            .flat_map(|group| &group.positions)
+        {
+            let from = Currency::from(position.currency.as_str());
+            total += position.value * converter.rate(&from, &currency)?;
+        }
+        Ok(round(total))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate(from: Currency, to: Currency, rate: f64) -> ExchangeRate {
+        ExchangeRate {
+            from_currency: from,
+            to_currency: to,
+            rate,
+        }
+    }
+
+    fn converter() -> CurrencyConverter {
+        CurrencyConverter::from_rates([
+            rate(Currency::USD, Currency::SEK, 10.87),
+            rate(Currency::NOK, Currency::SEK, 0.9731),
+            rate(Currency::EUR, Currency::USD, 1.08),
+        ])
+    }
+
+    #[test]
+    fn converts_with_direct_rate() {
+        let converter = converter();
+
+        assert_eq!(
+            converter.convert(100.0, &Currency::USD, &Currency::SEK),
+            Ok(1087.0)
+        );
+        assert_eq!(
+            converter.convert(50.0, &Currency::EUR, &Currency::USD),
+            Ok(54.0)
+        );
+    }
+
+    #[test]
+    fn converts_with_inverse_rate() {
+        // 1000 / 10.87 = 91.9963...
+        assert_eq!(
+            converter().convert(1000.0, &Currency::SEK, &Currency::USD),
+            Ok(92.0)
+        );
+    }
+
+    #[test]
+    fn converts_through_sek() {
+        // 123.45 * 10.87 / 0.9731 = 1378.9965..., while rounding the SEK leg to 1341.90 first
+        // would give 1378.99.
+        assert_eq!(
+            converter().convert(123.45, &Currency::USD, &Currency::NOK),
+            Ok(1379.0)
+        );
+    }
+
+    #[test]
+    fn same_currency_is_unchanged() {
+        assert_eq!(
+            CurrencyConverter::default().convert(12.5, &Currency::DKK, &Currency::DKK),
+            Ok(12.5)
+        );
+    }
+
+    #[test]
+    fn unknown_currency_is_an_error() {
+        let converter = converter();
+
+        assert_eq!(
+            converter.convert(1.0, &Currency::GBP, &Currency::SEK),
+            Err(ConversionError::UnknownCurrency(Currency::GBP))
+        );
+        assert_eq!(
+            converter.convert(1.0, &Currency::USD, &Currency::DKK),
+            Err(ConversionError::UnknownCurrency(Currency::DKK))
+        );
+        // EUR is only quoted against USD, so there is no way through SEK.
+        assert_eq!(
+            converter.convert(1.0, &Currency::EUR, &Currency::NOK),
+            Err(ConversionError::UnknownCurrency(Currency::EUR))
+        );
+    }
+
+    #[test]
+    fn values_positions_in_another_currency() {
+        let positions: PositionsResponse = serde_json::from_str(
+            r#"{
+                "instrumentPositions": [
+                    {
+                        "instrumentType": "STOCK",
+                        "positions": [
+                            {"accountId": "1", "accountName": "ISK", "accountType": "Investeringssparkonto", "acquiredValue": 900, "averageAcquiredPrice": 90, "change": 0, "changePercent": 0, "currency": "USD", "depositable": false, "flagCode": "US", "lastPrice": 100, "lastPriceUpdated": "2026-10-15T15:30:00", "name": "Apple", "orderbookId": "238449", "profit": 100, "profitPercent": 11.1, "tradable": true, "value": 100, "volume": 10},
+                            {"accountId": "1", "accountName": "ISK", "accountType": "Investeringssparkonto", "acquiredValue": 500, "averageAcquiredPrice": 50, "change": 0, "changePercent": 0, "currency": "SEK", "depositable": false, "flagCode": "SE", "lastPrice": 50, "lastPriceUpdated": "2026-10-15T15:30:00", "name": "Volvo B", "orderbookId": "5269", "profit": 0, "profitPercent": 0, "tradable": true, "value": 500, "volume": 10}
+                        ],
+                        "todaysProfitPercent": 0,
+                        "totalProfitPercent": 0,
+                        "totalProfitValue": 0,
+                        "totalValue": 1587.5
+                    }
+                ],
+                "totalProfit": 100,
+                "totalProfitPercent": 6.7,
+                "totalBalance": 0,
+                "totalOwnCapital": 1587.5,
+                "totalBuyingPower": 0
+            }"#,
+        )
+        .unwrap();
+        let converter = converter();
+
+        // 100 + 500 / 10.87 = 145.9981...
+        assert_eq!(
+            positions.total_value_in(&converter, Currency::USD),
+            Ok(146.0)
+        );
+        assert_eq!(
+            positions.total_value_in(&converter, Currency::SEK),
+            Ok(1587.0)
+        );
+        assert_eq!(
+            positions.total_value_in(&converter, Currency::GBP),
+            Err(ConversionError::UnknownCurrency(Currency::GBP))
+        );
+    }
+}
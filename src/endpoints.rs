@@ -0,0 +1,605 @@
+//! The requests behind the endpoints, described without sending them.
+//!
+//! Every function here is pure: it returns the [`RequestSpec`] that the matching `Client`
+//! method sends, so a custom HTTP stack can send it instead, and tests can check what would
+//! go over the wire without a server.
+//!
+//! Paths are templates per [`EndpointId`], e.g. `/_mobile/account/{account_id}/cash`. When
+//! Avanza moves an endpoint, [`Endpoints`] points it somewhere else without waiting for a
+//! release, see `ClientBuilder::override_endpoint`.
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use reqwest::{Method, Url};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::account::transactions::TransactionOptions;
+use crate::client::Config;
+use crate::error::ConfigError;
+use crate::market::price_history::Resolution;
+use crate::market::watchlists::WatchlistId;
+use crate::order::new_order::NewOrder;
+use crate::types::{AccountId, OrderbookId};
+
+const MAX_INACTIVE_MINUTES_AS_SECONDS: &str = "3600";
+
+/// The endpoints the client calls, one per path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum EndpointId {
+    UserCredentials,
+    Totp,
+    Positions,
+    Overview,
+    DealsAndOrders,
+    Accounts,
+    CashBalance,
+    Courtage,
+    Transfers,
+    Transactions,
+    PlaceOrder,
+    DeleteOrder,
+    Suggest,
+    Orderbook,
+    Orderbooks,
+    Trades,
+    Ownership,
+    OrderDepth,
+    Markets,
+    ExchangeRates,
+    Offers,
+    Watchlists,
+    Watchlist,
+    PriceChart,
+    ChartData,
+    FundHoldings,
+    Dividends,
+    KeyRatios,
+    ShortPositions,
+}
+
+impl EndpointId {
+    /// The path the client uses unless overridden. Placeholders like `{account_id}` are
+    /// filled in per request.
+    pub fn default_path(self) -> &'static str {
+        match self {
+            EndpointId::UserCredentials => "/_api/authentication/sessions/usercredentials",
+            EndpointId::Totp => "/_api/authentication/sessions/totp",
+            EndpointId::Positions => "/_mobile/account/positions",
+            EndpointId::Overview => "/_mobile/account/overview",
+            EndpointId::DealsAndOrders => "/_mobile/account/dealsandorders",
+            EndpointId::Accounts => "/_mobile/account/accounts",
+            EndpointId::CashBalance => "/_mobile/account/{account_id}/cash",
+            EndpointId::Courtage => "/_mobile/account/{account_id}/courtage",
+            EndpointId::Transfers => "/_mobile/account/{account_id}/transfers/status",
+            EndpointId::Transactions => "/_mobile/account/{account_id}/transactions",
+            EndpointId::PlaceOrder => "/_mobile/order",
+            EndpointId::DeleteOrder => "/_mobile/order/{account_id}/{order_id}",
+            EndpointId::Suggest => "/_mobile/market/search/suggest",
+            EndpointId::Orderbook => "/_mobile/market/orderbook/{orderbook_id}",
+            EndpointId::Orderbooks => "/_mobile/market/orderbooklist/{orderbook_ids}",
+            EndpointId::Trades => "/_mobile/market/orderbook/{orderbook_id}/trades",
+            EndpointId::Ownership => "/_mobile/market/orderbook/{orderbook_id}/ownership",
+            EndpointId::OrderDepth => "/_mobile/market/orderbook/{orderbook_id}/orderdepth",
+            EndpointId::Markets => "/_mobile/market/markets",
+            EndpointId::ExchangeRates => "/_mobile/market/exchange-rates",
+            EndpointId::Offers => "/_mobile/market/offers",
+            EndpointId::Watchlists => "/_mobile/usercontent/watchlist",
+            EndpointId::Watchlist => "/_mobile/usercontent/watchlist/{watchlist_id}",
+            EndpointId::PriceChart => "/_api/price-chart/{orderbook_id}",
+            EndpointId::ChartData => "/_mobile/chart/orderbook/{orderbook_id}",
+            EndpointId::FundHoldings => "/_mobile/market/fund/{orderbook_id}/holdings",
+            EndpointId::Dividends => "/_mobile/market/stock/{orderbook_id}/dividends",
+            EndpointId::KeyRatios => "/_mobile/market/stock/{orderbook_id}/keyratios",
+            EndpointId::ShortPositions => "/_mobile/market/stock/{orderbook_id}/shortpositions",
+        }
+    }
+}
+
+/// The path of every endpoint: the defaults, with any overrides.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Endpoints {
+    overrides: HashMap<EndpointId, String>,
+}
+
+impl Endpoints {
+    pub fn path(&self, endpoint: EndpointId) -> &str {
+        self.overrides
+            .get(&endpoint)
+            .map_or(endpoint.default_path(), String::as_str)
+    }
+
+    /// Sends `endpoint` to `path` instead. The path must start with `/` and can only use
+    /// the placeholders of the default path, e.g. `{account_id}`.
+    pub fn override_path(
+        &mut self,
+        endpoint: EndpointId,
+        path: impl Into<String>,
+    ) -> Result<(), ConfigError> {
+        let path = path.into();
+        let default_placeholders = placeholders(endpoint.default_path());
+        let valid = path.starts_with('/')
+            && placeholders(&path).all(|name| default_placeholders.clone().any(|d| d == name));
+        if !valid {
+            return Err(ConfigError::InvalidEndpointPath(endpoint, path));
+        }
+        self.overrides.insert(endpoint, path);
+        Ok(())
+    }
+}
+
+fn placeholders(template: &str) -> impl Iterator<Item = &str> + Clone {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+}
+
+fn render(template: &str, params: &[(&'static str, String)]) -> String {
+    let mut path = String::from(template);
+    for (name, value) in params {
+        path = path.replace(&format!("{{{}}}", name), value);
+    }
+    path
+}
+
+// Everything but the unreserved characters is escaped, so a value stays one segment and
+// can't hold a placeholder for a later parameter.
+fn encode_segment(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+// Urls treat `.` and `..` as moving in the path however they're escaped.
+fn is_dot_segment(value: &str) -> bool {
+    value == "." || value == ".."
+}
+
+/// One request, relative to the API url.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestSpec {
+    pub endpoint: EndpointId,
+    pub method: Method,
+    /// E.g. `/_mobile/account/positions`.
+    pub path: String,
+    pub query: Vec<(String, String)>,
+    /// JSON, if the request has a body.
+    pub body: Option<String>,
+    /// Whether the request needs the session's security token. Only logging in doesn't.
+    pub requires_auth: bool,
+    // Filled into the path template, kept to fill in an overridden one. Already escaped.
+    params: Vec<(&'static str, String)>,
+}
+
+impl RequestSpec {
+    fn get(endpoint: EndpointId) -> Self {
+        Self::new(endpoint, Method::GET, None)
+    }
+
+    fn post(endpoint: EndpointId, body: &(impl Serialize + ?Sized)) -> Self {
+        let body = serde_json::to_string(body).expect("failed to serialize request body");
+        Self::new(endpoint, Method::POST, Some(body))
+    }
+
+    fn delete(endpoint: EndpointId) -> Self {
+        Self::new(endpoint, Method::DELETE, None)
+    }
+
+    fn new(endpoint: EndpointId, method: Method, body: Option<String>) -> Self {
+        Self {
+            endpoint,
+            method,
+            path: String::from(endpoint.default_path()),
+            query: Vec::new(),
+            body,
+            requires_auth: true,
+            params: Vec::new(),
+        }
+    }
+
+    fn param(self, name: &'static str, value: impl ToString) -> Self {
+        let value = encode_segment(&value.to_string());
+        self.escaped_param(name, value)
+    }
+
+    /// A comma separated list, each value escaped on its own.
+    fn param_list(self, name: &'static str, values: &[impl ToString]) -> Self {
+        let values: Vec<String> = values
+            .iter()
+            .map(|value| encode_segment(&value.to_string()))
+            .collect();
+        self.escaped_param(name, values.join(","))
+    }
+
+    fn escaped_param(mut self, name: &'static str, value: String) -> Self {
+        self.params.push((name, value));
+        self.path = render(self.endpoint.default_path(), &self.params);
+        self
+    }
+
+    /// The same request on the path `endpoints` has for it.
+    pub fn with_endpoints(mut self, endpoints: &Endpoints) -> Self {
+        self.path = render(endpoints.path(self.endpoint), &self.params);
+        self
+    }
+
+    fn query(mut self, name: &str, value: impl ToString) -> Self {
+        self.query.push((String::from(name), value.to_string()));
+        self
+    }
+
+    fn without_auth(mut self) -> Self {
+        self.requires_auth = false;
+        self
+    }
+
+    /// The full url of the request against `api_url`, e.g. `https://www.avanza.se`.
+    ///
+    /// Fails for a path parameter of `.` or `..`, which no escaping keeps in its segment.
+    pub fn url(&self, api_url: &str) -> Result<Url, ConfigError> {
+        let url = format!("{}{}", api_url, self.path);
+        if self.params.iter().any(|(_, value)| is_dot_segment(value)) {
+            return Err(ConfigError::InvalidUrl(url));
+        }
+        let mut url = Url::parse(&url).map_err(|_| ConfigError::InvalidUrl(url))?;
+        if !self.query.is_empty() {
+            url.query_pairs_mut().extend_pairs(&self.query);
+        }
+        Ok(url)
+    }
+}
+
+/// The first step of logging in. The body holds the password.
+pub fn user_credentials(config: &Config) -> RequestSpec {
+    RequestSpec::post(
+        EndpointId::UserCredentials,
+        &json!({
+            "username": config.avanza_username,
+            "password": config.avanza_password,
+            "maxInactiveMinutes": MAX_INACTIVE_MINUTES_AS_SECONDS,
+        }),
+    )
+    .without_auth()
+}
+
+/// The second step of logging in, answering the two-factor challenge.
+pub fn totp(transaction_id: &str) -> RequestSpec {
+    RequestSpec::post(
+        EndpointId::Totp,
+        &json!({
+            "totpCode": transaction_id,
+            "method": "TOTP",
+        }),
+    )
+    .without_auth()
+}
+
+pub fn positions() -> RequestSpec {
+    RequestSpec::get(EndpointId::Positions)
+}
+
+pub fn overview() -> RequestSpec {
+    RequestSpec::get(EndpointId::Overview)
+}
+
+pub fn deals_and_orders() -> RequestSpec {
+    RequestSpec::get(EndpointId::DealsAndOrders)
+}
+
+pub fn accounts() -> RequestSpec {
+    RequestSpec::get(EndpointId::Accounts)
+}
+
+pub fn cash_balance(account_id: &AccountId) -> RequestSpec {
+    RequestSpec::get(EndpointId::CashBalance).param("account_id", account_id)
+}
+
+pub fn courtage(account_id: &AccountId) -> RequestSpec {
+    RequestSpec::get(EndpointId::Courtage).param("account_id", account_id)
+}
+
+pub fn pending_transfers(account_id: &AccountId) -> RequestSpec {
+    RequestSpec::get(EndpointId::Transfers).param("account_id", account_id)
+}
+
+/// The page of transactions starting at `offset`.
+pub fn transactions(
+    account_id: &AccountId,
+    options: &TransactionOptions,
+    offset: usize,
+) -> RequestSpec {
+    let mut spec = RequestSpec::get(EndpointId::Transactions)
+        .param("account_id", account_id)
+        .query("offset", offset)
+        .query("limit", options.page_size.max(1));
+    if let Some(from) = options.from {
+        spec = spec.query("from", from);
+    }
+    if let Some(to) = options.to {
+        spec = spec.query("to", to);
+    }
+    spec
+}
+
+pub fn place_order(order: &NewOrder) -> RequestSpec {
+    RequestSpec::post(EndpointId::PlaceOrder, order)
+}
+
+pub fn delete_order(account_id: &AccountId, order_id: &str) -> RequestSpec {
+    RequestSpec::delete(EndpointId::DeleteOrder)
+        .param("account_id", account_id)
+        .param("order_id", order_id)
+}
+
+pub fn suggest(prefix: &str, limit: usize) -> RequestSpec {
+    RequestSpec::get(EndpointId::Suggest)
+        .query("query", prefix)
+        .query("limit", limit)
+}
+
+pub fn orderbook(orderbook_id: &OrderbookId) -> RequestSpec {
+    RequestSpec::get(EndpointId::Orderbook).param("orderbook_id", orderbook_id)
+}
+
+/// Quotes for all of `orderbook_ids` in one request.
+pub fn orderbooks(orderbook_ids: &[OrderbookId]) -> RequestSpec {
+    let ids: Vec<&str> = orderbook_ids.iter().map(|id| id.0.as_str()).collect();
+    RequestSpec::get(EndpointId::Orderbooks).param_list("orderbook_ids", &ids)
+}
+
+pub fn trades(orderbook_id: &OrderbookId) -> RequestSpec {
+    RequestSpec::get(EndpointId::Trades).param("orderbook_id", orderbook_id)
+}
+
+pub fn ownership(orderbook_id: &OrderbookId) -> RequestSpec {
+    RequestSpec::get(EndpointId::Ownership).param("orderbook_id", orderbook_id)
+}
+
+pub fn order_depth(orderbook_id: &OrderbookId) -> RequestSpec {
+    RequestSpec::get(EndpointId::OrderDepth).param("orderbook_id", orderbook_id)
+}
+
+pub fn markets() -> RequestSpec {
+    RequestSpec::get(EndpointId::Markets)
+}
+
+pub fn exchange_rates() -> RequestSpec {
+    RequestSpec::get(EndpointId::ExchangeRates)
+}
+
+pub fn offers() -> RequestSpec {
+    RequestSpec::get(EndpointId::Offers)
+}
+
+pub fn watchlists() -> RequestSpec {
+    RequestSpec::get(EndpointId::Watchlists)
+}
+
+pub fn watchlist(watchlist_id: &WatchlistId) -> RequestSpec {
+    RequestSpec::get(EndpointId::Watchlist).param("watchlist_id", watchlist_id)
+}
+
+/// Candles from `from` to `to`, which must be within the resolution's maximum range.
+pub fn price_chart(
+    orderbook_id: &OrderbookId,
+    from: NaiveDate,
+    to: NaiveDate,
+    resolution: Resolution,
+) -> RequestSpec {
+    RequestSpec::get(EndpointId::PriceChart)
+        .param("orderbook_id", orderbook_id)
+        .query("from", from)
+        .query("to", to)
+        .query("resolution", resolution)
+}
+
+/// The chart series from `from` to `to`, with the comparison series the chart shows.
+pub fn chart_data(orderbook_id: &OrderbookId, from: NaiveDate, to: NaiveDate) -> RequestSpec {
+    RequestSpec::get(EndpointId::ChartData)
+        .param("orderbook_id", orderbook_id)
+        .query("from", from)
+        .query("to", to)
+}
+
+pub fn fund_holdings(orderbook_id: &OrderbookId) -> RequestSpec {
+    RequestSpec::get(EndpointId::FundHoldings).param("orderbook_id", orderbook_id)
+}
+
+pub fn dividends(orderbook_id: &OrderbookId) -> RequestSpec {
+    RequestSpec::get(EndpointId::Dividends).param("orderbook_id", orderbook_id)
+}
+
+pub fn key_ratios(orderbook_id: &OrderbookId) -> RequestSpec {
+    RequestSpec::get(EndpointId::KeyRatios).param("orderbook_id", orderbook_id)
+}
+
+pub fn short_positions(orderbook_id: &OrderbookId) -> RequestSpec {
+    RequestSpec::get(EndpointId::ShortPositions).param("orderbook_id", orderbook_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::test_support::config;
+    use crate::types::OrderbookId;
+
+    #[test]
+    fn user_credentials_spec() {
+        let spec = user_credentials(&config());
+
+        assert_eq!(spec.method, Method::POST);
+        assert_eq!(spec.path, "/_api/authentication/sessions/usercredentials");
+        assert!(spec.query.is_empty());
+        assert_eq!(
+            spec.body.as_deref(),
+            Some(r#"{"maxInactiveMinutes":"3600","password":"pass","username":"user"}"#)
+        );
+        assert!(!spec.requires_auth);
+    }
+
+    #[test]
+    fn totp_spec() {
+        let spec = totp("4530ff65");
+
+        assert_eq!(spec.path, "/_api/authentication/sessions/totp");
+        assert_eq!(
+            spec.body.as_deref(),
+            Some(r#"{"method":"TOTP","totpCode":"4530ff65"}"#)
+        );
+        assert!(!spec.requires_auth);
+    }
+
+    #[test]
+    fn positions_spec() {
+        assert_eq!(
+            positions(),
+            RequestSpec {
+                endpoint: EndpointId::Positions,
+                method: Method::GET,
+                path: String::from("/_mobile/account/positions"),
+                query: Vec::new(),
+                body: None,
+                requires_auth: true,
+                params: Vec::new(),
+            }
+        );
+        assert_eq!(
+            positions().url("https://www.avanza.se").unwrap().as_str(),
+            "https://www.avanza.se/_mobile/account/positions"
+        );
+    }
+
+    #[test]
+    fn place_order_spec() {
+        let order = NewOrder::buy(OrderbookId::from("5361"))
+            .account(AccountId::from("1234"))
+            .limit_price(187.5)
+            .volume(10)
+            .valid_until(NaiveDate::from_ymd_opt(2026, 10, 16).unwrap())
+            .build_on(NaiveDate::from_ymd_opt(2026, 10, 15).unwrap())
+            .unwrap();
+
+        let spec = place_order(&order);
+
+        assert_eq!(spec.method, Method::POST);
+        assert_eq!(spec.path, "/_mobile/order");
+        assert_eq!(
+            spec.body.as_deref(),
+            Some(
+                r#"{"accountId":"1234","orderbookId":"5361","side":"BUY","orderType":"LIMIT","price":187.5,"volume":10,"validUntil":"2026-10-16","condition":"NORMAL"}"#
+            )
+        );
+        assert!(spec.requires_auth);
+    }
+
+    #[test]
+    fn delete_order_spec() {
+        let spec = delete_order(&AccountId::from("1234"), "o-1");
+
+        assert_eq!(spec.method, Method::DELETE);
+        assert_eq!(spec.path, "/_mobile/order/1234/o-1");
+        assert_eq!(spec.body, None);
+    }
+
+    #[test]
+    fn path_parameters_stay_in_their_segment() {
+        let spec = delete_order(&AccountId::from("{order_id}"), "a/b?c#d");
+
+        assert_eq!(spec.path, "/_mobile/order/%7Border_id%7D/a%2Fb%3Fc%23d");
+        assert_eq!(
+            spec.url("https://www.avanza.se").unwrap().as_str(),
+            "https://www.avanza.se/_mobile/order/%7Border_id%7D/a%2Fb%3Fc%23d"
+        );
+        assert_eq!(
+            delete_order(&AccountId::from("1234"), "../..x").path,
+            "/_mobile/order/1234/..%2F..x"
+        );
+        assert!(matches!(
+            delete_order(&AccountId::from("1234"), "..").url("https://www.avanza.se"),
+            Err(ConfigError::InvalidUrl(_))
+        ));
+    }
+
+    #[test]
+    fn suggest_spec_encodes_the_query() {
+        let spec = suggest("volvo b", 5);
+
+        assert_eq!(
+            spec.query,
+            vec![
+                (String::from("query"), String::from("volvo b")),
+                (String::from("limit"), String::from("5")),
+            ]
+        );
+        assert_eq!(
+            spec.url("https://www.avanza.se").unwrap().as_str(),
+            "https://www.avanza.se/_mobile/market/search/suggest?query=volvo+b&limit=5"
+        );
+    }
+
+    #[test]
+    fn overridden_path_gets_the_same_parameters() {
+        let mut endpoints = Endpoints::default();
+        endpoints
+            .override_path(
+                EndpointId::CashBalance,
+                "/_api/account-overview/{account_id}/cash/v2",
+            )
+            .unwrap();
+
+        let spec = cash_balance(&AccountId::from("1234"));
+        assert_eq!(spec.path, "/_mobile/account/1234/cash");
+        assert_eq!(
+            spec.with_endpoints(&endpoints).path,
+            "/_api/account-overview/1234/cash/v2"
+        );
+        assert_eq!(
+            positions().with_endpoints(&endpoints).path,
+            "/_mobile/account/positions"
+        );
+    }
+
+    #[test]
+    fn rejects_override_with_unknown_placeholder() {
+        let mut endpoints = Endpoints::default();
+        assert!(matches!(
+            endpoints.override_path(EndpointId::CashBalance, "/_api/cash/{orderbook_id}"),
+            Err(ConfigError::InvalidEndpointPath(EndpointId::CashBalance, _))
+        ));
+        assert!(endpoints
+            .override_path(EndpointId::Positions, "_api/positions")
+            .is_err());
+        assert_eq!(
+            endpoints.path(EndpointId::CashBalance),
+            EndpointId::CashBalance.default_path()
+        );
+    }
+
+    #[test]
+    fn transactions_spec_has_paging_and_dates() {
+        let options = TransactionOptions {
+            from: NaiveDate::from_ymd_opt(2026, 1, 1),
+            to: None,
+            page_size: 50,
+        };
+
+        let spec = transactions(&AccountId::from("1234"), &options, 100);
+
+        assert_eq!(spec.path, "/_mobile/account/1234/transactions");
+        assert_eq!(
+            spec.url("https://www.avanza.se").unwrap().query(),
+            Some("offset=100&limit=50&from=2026-01-01")
+        );
+    }
+}
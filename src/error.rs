@@ -6,6 +6,16 @@ extern crate reqwest;
 extern crate serde;
 extern crate serde_json;
 
+#[cfg(feature = "push")]
+use tokio_tungstenite::tungstenite;
+
+use chrono::NaiveDate;
+
+use crate::client::RateLimit;
+use crate::endpoints::EndpointId;
+use crate::order::new_order::OrderCondition;
+use crate::types::Currency;
+
 #[derive(Debug, Clone)]
 pub struct UnknownAuthenticationMethod;
 
@@ -32,11 +42,114 @@ pub enum RequestError {
     ParseError(serde_json::Error),
     NotAuthenticatedError(),
     UnknownAuthenticationMethod(),
+    #[cfg(feature = "push")]
+    WebSocketError(Box<tungstenite::Error>),
+    PushProtocolError(String),
+    PushSubscriptionDenied(String),
+    /// A push subscription's buffer was full and this many updates were dropped.
+    PushLagged(u64),
+    PushConnectionClosed(),
+    /// A replaying client got a request that isn't on its cassette.
+    UnmatchedReplay(String),
+    /// The same order was placed within the client's dedupe window, see
+    /// `ClientBuilder::order_dedupe_window`. Nothing was sent.
+    DuplicateOrder(),
+    /// Avanza answered with an HTML page instead of JSON, as it does during maintenance.
+    /// Retried like a 503, with a longer wait.
+    ServiceUnavailable {
+        body_snippet: String,
+    },
+    /// With strict parsing, the response had fields the model doesn't, by path, e.g.
+    /// `instrumentPositions[0].positions[1].marketPlace`.
+    UnknownFields(Vec<String>),
+    /// The client's settings can't make the request, e.g. an `api_url` set without
+    /// `ClientBuilder` that isn't a url.
+    InvalidConfig(ConfigError),
+    /// An amount couldn't be converted for lack of an exchange rate.
+    ConversionError(ConversionError),
+}
+
+/// Coarse classification of a [`RequestError`], e.g. to count errors in metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ErrorKind {
+    Connect,
+    Timeout,
+    /// Any other failure to send the request or read the response.
+    Request,
+    /// The response body was not the JSON the model expects.
+    Parse,
+    NotAuthenticated,
+    Authentication,
+    Push,
+    Replay,
+    DuplicateOrder,
+    ServiceUnavailable,
+    Config,
+    Conversion,
+}
+
+impl RequestError {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            RequestError::WebRequestError(e) if e.is_timeout() => ErrorKind::Timeout,
+            #[cfg(not(target_arch = "wasm32"))]
+            RequestError::WebRequestError(e) if e.is_connect() => ErrorKind::Connect,
+            RequestError::WebRequestError(e) if e.is_decode() => ErrorKind::Parse,
+            RequestError::WebRequestError(_) => ErrorKind::Request,
+            RequestError::ParseError(_) | RequestError::UnknownFields(_) => ErrorKind::Parse,
+            RequestError::NotAuthenticatedError() => ErrorKind::NotAuthenticated,
+            RequestError::UnknownAuthenticationMethod() => ErrorKind::Authentication,
+            #[cfg(feature = "push")]
+            RequestError::WebSocketError(_) => ErrorKind::Push,
+            RequestError::PushProtocolError(_)
+            | RequestError::PushSubscriptionDenied(_)
+            | RequestError::PushLagged(_)
+            | RequestError::PushConnectionClosed() => ErrorKind::Push,
+            RequestError::UnmatchedReplay(_) => ErrorKind::Replay,
+            RequestError::DuplicateOrder() => ErrorKind::DuplicateOrder,
+            RequestError::InvalidConfig(_) => ErrorKind::Config,
+            RequestError::ServiceUnavailable { .. } => ErrorKind::ServiceUnavailable,
+            RequestError::ConversionError(_) => ErrorKind::Conversion,
+        }
+    }
 }
 
 impl fmt::Display for RequestError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "RequestError")
+        match self {
+            RequestError::WebRequestError(e) => write!(f, "request failed: {}", e),
+            RequestError::ParseError(e) => write!(f, "failed to parse response: {}", e),
+            RequestError::NotAuthenticatedError() => write!(f, "{}", NotAuthenticatedError),
+            RequestError::UnknownAuthenticationMethod() => {
+                write!(f, "{}", UnknownAuthenticationMethod)
+            }
+            #[cfg(feature = "push")]
+            RequestError::WebSocketError(e) => write!(f, "push connection failed: {}", e),
+            RequestError::PushProtocolError(message) => {
+                write!(f, "push protocol error: {}", message)
+            }
+            RequestError::PushSubscriptionDenied(error) => {
+                write!(f, "push subscription denied: {}", error)
+            }
+            RequestError::PushLagged(dropped) => {
+                write!(f, "push subscription lagged, dropped {} updates", dropped)
+            }
+            RequestError::PushConnectionClosed() => write!(f, "push connection closed"),
+            RequestError::UnmatchedReplay(request) => {
+                write!(f, "no recorded response for {}", request)
+            }
+            RequestError::DuplicateOrder() => {
+                write!(f, "the same order was placed within the dedupe window")
+            }
+            RequestError::ServiceUnavailable { body_snippet } => {
+                write!(f, "service unavailable: {}", body_snippet)
+            }
+            RequestError::UnknownFields(paths) => {
+                write!(f, "unknown fields in response: {}", paths.join(", "))
+            }
+            RequestError::InvalidConfig(e) => write!(f, "{}", e),
+            RequestError::ConversionError(e) => write!(f, "{}", e),
+        }
     }
 }
 
@@ -57,8 +170,193 @@ impl From<serde_json::Error> for RequestError {
     }
 }
 
+impl From<ConversionError> for RequestError {
+    fn from(e: ConversionError) -> Self {
+        RequestError::ConversionError(e)
+    }
+}
+
 impl From<reqwest::Error> for RequestError {
     fn from(e: reqwest::Error) -> Self {
         RequestError::WebRequestError(e)
     }
 }
+
+#[cfg(feature = "push")]
+impl From<tungstenite::Error> for RequestError {
+    fn from(e: tungstenite::Error) -> Self {
+        RequestError::WebSocketError(Box::new(e))
+    }
+}
+
+/// Returned by `ClientBuilder::build` when the options can't make a working client.
+#[derive(Debug)]
+pub enum ConfigError {
+    MissingCredentials(),
+    /// Two options that can't be combined were both set.
+    ConflictingOptions(&'static str, &'static str),
+    InvalidUrl(String),
+    InvalidProxy(String),
+    InvalidTimeout(),
+    InvalidRateLimit(RateLimit),
+    InvalidInstrumentCache(),
+    InvalidOrderbookChunkSize(),
+    /// An endpoint override that doesn't start with `/` or uses a placeholder the endpoint
+    /// doesn't have.
+    InvalidEndpointPath(EndpointId, String),
+    /// A cassette to replay could not be read.
+    InvalidCassette(String),
+    HttpClient(reqwest::Error),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::MissingCredentials() => write!(f, "no credentials configured"),
+            ConfigError::ConflictingOptions(a, b) => {
+                write!(f, "{} and {} can not both be set", a, b)
+            }
+            ConfigError::InvalidUrl(url) => write!(f, "invalid url {}", url),
+            ConfigError::InvalidProxy(url) => write!(f, "invalid proxy {}", url),
+            ConfigError::InvalidTimeout() => write!(f, "timeout must be greater than zero"),
+            ConfigError::InvalidRateLimit(rate_limit) => write!(
+                f,
+                "invalid rate limit of {} requests per {:?}",
+                rate_limit.max_requests, rate_limit.per
+            ),
+            ConfigError::InvalidInstrumentCache() => {
+                write!(f, "instrument cache needs a ttl and capacity above zero")
+            }
+            ConfigError::InvalidOrderbookChunkSize() => {
+                write!(f, "orderbook chunk size must be greater than zero")
+            }
+            ConfigError::InvalidEndpointPath(endpoint, path) => {
+                write!(f, "invalid path {} for {:?}", path, endpoint)
+            }
+            ConfigError::InvalidCassette(e) => write!(f, "invalid cassette {}", e),
+            ConfigError::HttpClient(e) => write!(f, "failed to build http client: {}", e),
+        }
+    }
+}
+
+impl error::Error for ConfigError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConversionError {
+    /// Neither a direct rate nor one through SEK is known for the currency.
+    UnknownCurrency(Currency),
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConversionError::UnknownCurrency(currency) => {
+                write!(f, "no exchange rate for {}", currency)
+            }
+        }
+    }
+}
+
+impl error::Error for ConversionError {}
+
+/// One reason `NewOrderBuilder::build` refused an order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvalidOrder {
+    MissingAccount(),
+    MissingPrice(),
+    MissingVolume(),
+    /// Zero, negative, or not a number at all.
+    NonPositivePrice(f64),
+    ZeroVolume(),
+    ValidUntilInPast(NaiveDate),
+    /// Later than `MAX_VALIDITY_DAYS` from today.
+    ValidUntilTooFarAhead(NaiveDate),
+    /// Both `limit_price` and `market` were set.
+    PriceOnMarketOrder(),
+    /// A market order with `OrderCondition::Normal`, which would rest in the orderbook.
+    NormalMarketOrder(),
+    /// A fill-and-kill or fill-or-kill order valid after today.
+    ValidBeyondToday(OrderCondition),
+}
+
+impl fmt::Display for InvalidOrder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidOrder::MissingAccount() => write!(f, "no account set"),
+            InvalidOrder::MissingPrice() => write!(f, "no price set"),
+            InvalidOrder::MissingVolume() => write!(f, "no volume set"),
+            InvalidOrder::NonPositivePrice(price) => write!(f, "price {} is not positive", price),
+            InvalidOrder::ZeroVolume() => write!(f, "volume is zero"),
+            InvalidOrder::ValidUntilInPast(date) => write!(f, "valid until {} has passed", date),
+            InvalidOrder::ValidUntilTooFarAhead(date) => {
+                write!(f, "valid until {} is too far ahead", date)
+            }
+            InvalidOrder::PriceOnMarketOrder() => write!(f, "market orders take no price"),
+            InvalidOrder::NormalMarketOrder() => {
+                write!(f, "market orders must fill at once or be killed")
+            }
+            InvalidOrder::ValidBeyondToday(condition) => {
+                write!(f, "{:?} orders are only valid today", condition)
+            }
+        }
+    }
+}
+
+/// Every reason an order was refused, in the order the fields are checked.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderValidationError {
+    pub violations: Vec<InvalidOrder>,
+}
+
+impl fmt::Display for OrderValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid order: ")?;
+        for (i, violation) in self.violations.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for OrderValidationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_error_shows_its_details() {
+        assert_eq!(
+            RequestError::UnknownFields(vec![
+                String::from("newField"),
+                String::from("instrumentPositions[0].marketPlace"),
+            ])
+            .to_string(),
+            "unknown fields in response: newField, instrumentPositions[0].marketPlace"
+        );
+        assert_eq!(
+            RequestError::ServiceUnavailable {
+                body_snippet: String::from("<h1>Vi uppdaterar just nu</h1>"),
+            }
+            .to_string(),
+            "service unavailable: <h1>Vi uppdaterar just nu</h1>"
+        );
+        assert_eq!(
+            RequestError::InvalidConfig(ConfigError::InvalidUrl(String::from("not a url")))
+                .to_string(),
+            "invalid url not a url"
+        );
+        assert_eq!(
+            RequestError::ConversionError(ConversionError::UnknownCurrency(Currency::from("USD")))
+                .to_string(),
+            "no exchange rate for USD"
+        );
+        assert_eq!(
+            RequestError::DuplicateOrder().to_string(),
+            "the same order was placed within the dedupe window"
+        );
+    }
+}
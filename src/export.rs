@@ -0,0 +1,309 @@
+//! CSV export of positions and transactions.
+//!
+//! Numbers are written in plain decimal notation without thousands separators, whatever
+//! the locale. Names and descriptions are quoted when they contain the delimiter, quotes
+//! or line breaks, and written as UTF-8.
+
+use std::io::Write;
+
+use serde::Serialize;
+
+use crate::account::transactions::Transaction;
+use crate::portfolio::positions::PositionsResponse;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecimalSeparator {
+    Point,
+    Comma,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvOptions {
+    pub decimal_separator: DecimalSeparator,
+    pub delimiter: u8,
+}
+
+impl CsvOptions {
+    /// Decimal comma and `;` between fields, which Excel with Swedish settings opens as is.
+    pub fn swedish_excel() -> Self {
+        Self {
+            decimal_separator: DecimalSeparator::Comma,
+            delimiter: b';',
+        }
+    }
+
+    fn number(&self, value: f64) -> String {
+        let formatted = value.to_string();
+        match self.decimal_separator {
+            DecimalSeparator::Point => formatted,
+            DecimalSeparator::Comma => formatted.replace('.', ","),
+        }
+    }
+
+    fn optional_number(&self, value: Option<f64>) -> String {
+        value.map(|v| self.number(v)).unwrap_or_default()
+    }
+
+    fn writer<W: Write>(&self, w: W) -> csv::Writer<W> {
+        csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .from_writer(w)
+    }
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            decimal_separator: DecimalSeparator::Point,
+            delimiter: b',',
+        }
+    }
+}
+
+pub const POSITION_COLUMNS: [&str; 15] = [
+    "account_id",
+    "account_name",
+    "account_type",
+    "instrument_type",
+    "orderbook_id",
+    "name",
+    "currency",
+    "volume",
+    "average_acquired_price",
+    "acquired_value",
+    "last_price",
+    "value",
+    "profit",
+    "profit_percent",
+    "change_percent",
+];
+
+pub const TRANSACTION_COLUMNS: [&str; 10] = [
+    "id",
+    "account_id",
+    "date",
+    "transaction_type",
+    "description",
+    "orderbook_id",
+    "volume",
+    "price",
+    "amount",
+    "currency",
+];
+
+impl PositionsResponse {
+    /// One row per position with the columns in `POSITION_COLUMNS`, after a header row.
+    pub fn to_csv<W: Write>(&self, w: W) -> Result<(), csv::Error> {
+        self.to_csv_with_options(w, &CsvOptions::default())
+    }
+
+    pub fn to_csv_with_options<W: Write>(
+        &self,
+        w: W,
+        options: &CsvOptions,
+    ) -> Result<(), csv::Error> {
+        let mut writer = options.writer(w);
+        writer.write_record(POSITION_COLUMNS)?;
+        for group in &self.instrument_positions {
+            for position in &group.positions {
+                writer.write_record([
+                    position.account_id.clone(),
+                    position.account_name.clone(),
+                    position.account_type.clone(),
+                    group.instrument_type.clone(),
+                    position.orderbook_id.clone(),
+                    position.name.clone(),
+                    position.currency.clone(),
+                    position.volume.to_string(),
+                    options.number(position.average_acquired_price),
+                    options.number(position.acquired_value),
+                    options.number(position.last_price),
+                    options.number(position.value),
+                    options.number(position.profit),
+                    options.number(position.profit_percent),
+                    options.number(position.change_percent),
+                ])?;
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// One row per transaction with the columns in `TRANSACTION_COLUMNS`, after a header row.
+/// Missing orderbook, volume and price are written as empty fields.
+pub fn transactions_to_csv<W: Write>(
+    transactions: &[Transaction],
+    w: W,
+    options: &CsvOptions,
+) -> Result<(), csv::Error> {
+    let mut writer = options.writer(w);
+    writer.write_record(TRANSACTION_COLUMNS)?;
+    for transaction in transactions {
+        writer.write_record([
+            transaction.id.clone(),
+            transaction.account_id.to_string(),
+            transaction.date.to_string(),
+            serde_name(&transaction.transaction_type),
+            transaction.description.clone(),
+            transaction
+                .orderbook_id
+                .as_ref()
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            options.optional_number(transaction.volume),
+            options.optional_number(transaction.price),
+            options.number(transaction.amount),
+            transaction.currency.to_string(),
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// The name a unit enum variant has in Avanza's JSON, so the column matches the API.
+fn serde_name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::transactions::TransactionType;
+
+    fn positions() -> PositionsResponse {
+        serde_json::from_str(
+            r#"{
+                "instrumentPositions": [{
+                    "instrumentType": "STOCK",
+                    "todaysProfitPercent": 0.4,
+                    "totalProfitPercent": 12.5,
+                    "totalProfitValue": 1250.25,
+                    "totalValue": 11250.25,
+                    "positions": [{
+                        "accountId": "1234",
+                        "accountName": "Sparande, långsiktigt",
+                        "accountType": "Investeringssparkonto",
+                        "acquiredValue": 10000,
+                        "averageAcquiredPrice": 250.5,
+                        "change": 1.5,
+                        "changePercent": 0.4,
+                        "currency": "SEK",
+                        "depositable": true,
+                        "flagCode": "SE",
+                        "lastPrice": 281.25,
+                        "lastPriceUpdated": "2026-10-15T15:29:59",
+                        "name": "Investor, \"B\" – Ström & Åkerö",
+                        "orderbookId": "5247",
+                        "profit": 1250.25,
+                        "profitPercent": 12.5,
+                        "tradable": true,
+                        "value": 11250.25,
+                        "volume": 40
+                    }]
+                }],
+                "totalProfit": 1250.25,
+                "totalProfitPercent": 12.5,
+                "totalBalance": 11250.25,
+                "totalOwnCapital": 11250.25,
+                "totalBuyingPower": 0
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn transactions() -> Vec<Transaction> {
+        serde_json::from_str(
+            r#"[
+                {"id": "t-1", "accountId": "1234", "transactionType": "BUY", "date": "2026-10-01", "description": "Köp, Investor B", "orderbookId": "5247", "volume": 40, "price": 250.5, "amount": -10020, "currency": "SEK"},
+                {"id": "t-2", "accountId": "1234", "transactionType": "DEPOSIT", "date": "2026-09-30", "description": "Insättning", "amount": 10000.75, "currency": "SEK"}
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    fn read(bytes: &[u8], delimiter: u8) -> (Vec<String>, Vec<csv::StringRecord>) {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .from_reader(bytes);
+        let headers = reader.headers().unwrap().iter().map(String::from).collect();
+        let records = reader.records().map(Result::unwrap).collect();
+        (headers, records)
+    }
+
+    fn parse_number(field: &str) -> f64 {
+        field.replace(',', ".").parse().unwrap()
+    }
+
+    #[test]
+    fn positions_round_trip() {
+        let positions = positions();
+        let mut out = Vec::new();
+        positions.to_csv(&mut out).unwrap();
+
+        let (headers, records) = read(&out, b',');
+        assert_eq!(headers, POSITION_COLUMNS);
+        assert_eq!(records.len(), 1);
+
+        let source = &positions.instrument_positions[0].positions[0];
+        let row = &records[0];
+        assert_eq!(&row[1], source.account_name);
+        assert_eq!(&row[3], "STOCK");
+        assert_eq!(&row[5], source.name);
+        assert_eq!(row[7].parse::<i64>().unwrap(), source.volume);
+        assert_eq!(parse_number(&row[8]), source.average_acquired_price);
+        assert_eq!(parse_number(&row[11]), source.value);
+    }
+
+    #[test]
+    fn positions_with_decimal_comma() {
+        let positions = positions();
+        let mut out = Vec::new();
+        positions
+            .to_csv_with_options(&mut out, &CsvOptions::swedish_excel())
+            .unwrap();
+
+        let (headers, records) = read(&out, b';');
+        assert_eq!(headers, POSITION_COLUMNS);
+        let row = &records[0];
+        assert_eq!(&row[8], "250,5");
+        assert_eq!(&row[11], "11250,25");
+        assert_eq!(&row[1], "Sparande, långsiktigt");
+    }
+
+    #[test]
+    fn transactions_round_trip() {
+        let transactions = transactions();
+        for options in [CsvOptions::default(), CsvOptions::swedish_excel()] {
+            let mut out = Vec::new();
+            transactions_to_csv(&transactions, &mut out, &options).unwrap();
+
+            let (headers, records) = read(&out, options.delimiter);
+            assert_eq!(headers, TRANSACTION_COLUMNS);
+            assert_eq!(records.len(), transactions.len());
+
+            for (row, source) in records.iter().zip(&transactions) {
+                assert_eq!(&row[0], source.id);
+                assert_eq!(row[2].parse::<chrono::NaiveDate>().unwrap(), source.date);
+                assert_eq!(&row[4], source.description);
+                assert_eq!(parse_number(&row[8]), source.amount);
+                assert_eq!(&row[9], "SEK");
+            }
+            assert_eq!(&records[0][3], "BUY");
+            assert_eq!(transactions[1].transaction_type, TransactionType::Deposit);
+            assert_eq!(&records[1][5], "");
+            assert_eq!(&records[1][7], "");
+        }
+    }
+
+    #[test]
+    fn numbers_are_plain_decimals() {
+        let options = CsvOptions::default();
+        assert_eq!(options.number(1234567.5), "1234567.5");
+        assert_eq!(options.number(0.0001), "0.0001");
+        assert_eq!(options.number(-10020.0), "-10020");
+    }
+}
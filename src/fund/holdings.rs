@@ -0,0 +1,298 @@
+use std::collections::HashMap;
+
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::portfolio::positions::PositionsResponse;
+use crate::serde_helpers::lenient_f64;
+use crate::types::{OrderbookId, Percent};
+use serde::{Deserialize, Deserializer, Serialize};
+
+const FUND_INSTRUMENT_TYPE: &str = "FUND";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Exposure {
+    pub name: String,
+    #[serde(deserialize_with = "lenient_weight")]
+    pub weight: Percent,
+}
+
+// Some funds report weights as strings with a decimal comma, e.g. "4,25".
+fn lenient_weight<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Percent, D::Error> {
+    lenient_f64::deserialize_named(deserializer, "weight").map(Percent)
+}
+
+/// Funds that don't publish their holdings have empty lists.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FundHoldings {
+    #[serde(default)]
+    pub holdings: Vec<Exposure>,
+    #[serde(default)]
+    pub countries: Vec<Exposure>,
+    #[serde(default)]
+    pub sectors: Vec<Exposure>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregatedExposure {
+    pub name: String,
+    pub value: f64,
+    /// Share of the total value of all funds in the look-through.
+    pub weight: Percent,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LookThrough {
+    pub total_fund_value: f64,
+    pub holdings: Vec<AggregatedExposure>,
+    pub countries: Vec<AggregatedExposure>,
+    pub sectors: Vec<AggregatedExposure>,
+}
+
+fn aggregate<'a, F>(
+    funds: &[(f64, &'a FundHoldings)],
+    total: f64,
+    field: F,
+) -> Vec<AggregatedExposure>
+where
+    F: Fn(&'a FundHoldings) -> &'a Vec<Exposure>,
+{
+    let mut values: HashMap<&str, f64> = HashMap::new();
+    for (value, holdings) in funds {
+        for exposure in field(holdings) {
+            *values.entry(exposure.name.as_str()).or_default() +=
+                value * exposure.weight.value() / 100.0;
+        }
+    }
+
+    let mut aggregated: Vec<AggregatedExposure> = values
+        .into_iter()
+        .map(|(name, value)| AggregatedExposure {
+            name: String::from(name),
+            value,
+            weight: Percent(if total > 0.0 {
+                value / total * 100.0
+            } else {
+                0.0
+            }),
+        })
+        .collect();
+    aggregated.sort_by(|a, b| {
+        b.value
+            .total_cmp(&a.value)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    aggregated
+}
+
+/// Aggregates the underlying exposure of `funds`, given as pairs of position value and holdings.
+pub fn look_through(funds: &[(f64, &FundHoldings)]) -> LookThrough {
+    let total_fund_value = funds.iter().map(|(value, _)| value).sum::<f64>();
+
+    LookThrough {
+        total_fund_value,
+        holdings: aggregate(funds, total_fund_value, |h| &h.holdings),
+        countries: aggregate(funds, total_fund_value, |h| &h.countries),
+        sectors: aggregate(funds, total_fund_value, |h| &h.sectors),
+    }
+}
+
+impl Client {
+    pub async fn get_fund_holdings(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<FundHoldings, RequestError> {
+        let resp = self
+            .fetch_spec::<FundHoldings>(
+                &endpoints::fund_holdings(orderbook_id),
+                Idempotency::Idempotent,
+            )
+            .await?;
+        Ok(resp)
+    }
+
+    /// Fetches the holdings of every fund in `positions` and aggregates them weighted by
+    /// position value. A fund held in several accounts is only fetched once.
+    pub async fn portfolio_look_through(
+        &self,
+        positions: &PositionsResponse,
+    ) -> Result<LookThrough, RequestError> {
+        let mut fund_values: Vec<(OrderbookId, f64)> = Vec::new();
+        for position in positions
+            .instrument_positions
+            .iter()
+            .filter(|p| p.instrument_type == FUND_INSTRUMENT_TYPE)
+            .flat_map(|p| p.positions.iter())
+        {
+            let orderbook_id = OrderbookId(position.orderbook_id.clone());
+            match fund_values.iter_mut().find(|(id, _)| *id == orderbook_id) {
+                Some((_, value)) => *value += position.value,
+                None => fund_values.push((orderbook_id, position.value)),
+            }
+        }
+
+        let mut holdings = Vec::with_capacity(fund_values.len());
+        for (orderbook_id, value) in &fund_values {
+            holdings.push((*value, self.get_fund_holdings(orderbook_id).await?));
+        }
+
+        let funds: Vec<(f64, &FundHoldings)> = holdings.iter().map(|(v, h)| (*v, h)).collect();
+        Ok(look_through(&funds))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn exposure(name: &str, weight: f64) -> Exposure {
+        Exposure {
+            name: String::from(name),
+            weight: Percent(weight),
+        }
+    }
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.get_fund_holdings(&OrderbookId::from("1")).await);
+    }
+
+    #[tokio::test]
+    async fn can_get_fund_holdings() {
+        let mock_server = MockServer::start().await;
+
+        let responder = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{
+                "holdings": [{"name": "Apple Inc", "weight": 6.5}, {"name": "Microsoft Corp", "weight": "5,9"}],
+                "countries": [{"name": "USA", "weight": "70.1"}],
+                "sectors": [{"name": "Teknik", "weight": 28.2}]
+            }"#,
+        ));
+
+        let unpublished = ResponseTemplate::new(200).set_body_string(String::from("{}"));
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/market/fund/377804/holdings"))
+            .respond_with(responder)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/market/fund/1933/holdings"))
+            .respond_with(unpublished)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let holdings = assert_ok!(client.get_fund_holdings(&OrderbookId::from("377804")).await);
+        assert_eq!(holdings.holdings.len(), 2);
+        assert_eq!(holdings.holdings[0], exposure("Apple Inc", 6.5));
+        assert_eq!(holdings.holdings[1], exposure("Microsoft Corp", 5.9));
+        assert_eq!(holdings.countries[0].weight, Percent(70.1));
+
+        let holdings = assert_ok!(client.get_fund_holdings(&OrderbookId::from("1933")).await);
+        assert_eq!(holdings, FundHoldings::default());
+    }
+
+    #[test]
+    fn look_through_aggregates_shared_constituent() {
+        let global = FundHoldings {
+            holdings: vec![exposure("Apple Inc", 10.0), exposure("Nestle", 5.0)],
+            countries: vec![exposure("USA", 60.0), exposure("Schweiz", 40.0)],
+            sectors: vec![],
+        };
+        let tech = FundHoldings {
+            holdings: vec![exposure("Apple Inc", 20.0), exposure("Nvidia", 15.0)],
+            countries: vec![exposure("USA", 100.0)],
+            sectors: vec![exposure("Teknik", 100.0)],
+        };
+
+        let result = look_through(&[(30_000.0, &global), (10_000.0, &tech)]);
+
+        assert_eq!(result.total_fund_value, 40_000.0);
+
+        // 30 000 * 10 % + 10 000 * 20 % = 5 000, which is 12.5 % of 40 000.
+        assert_eq!(result.holdings[0].name, "Apple Inc");
+        assert_eq!(result.holdings[0].value, 5_000.0);
+        assert_eq!(result.holdings[0].weight, Percent(12.5));
+
+        // Equal values are ordered by name.
+        assert_eq!(result.holdings[1].name, "Nestle");
+        assert_eq!(result.holdings[1].value, 1_500.0);
+        assert_eq!(result.holdings[2].name, "Nvidia");
+        assert_eq!(result.holdings[2].value, 1_500.0);
+
+        // 30 000 * 60 % + 10 000 * 100 % = 28 000.
+        assert_eq!(result.countries[0].name, "USA");
+        assert_eq!(result.countries[0].value, 28_000.0);
+        assert_eq!(result.countries[0].weight, Percent(70.0));
+        assert_eq!(result.countries[1].value, 12_000.0);
+
+        assert_eq!(result.sectors.len(), 1);
+        assert_eq!(result.sectors[0].weight, Percent(25.0));
+    }
+
+    #[test]
+    fn look_through_without_funds_is_empty() {
+        assert_eq!(look_through(&[]), LookThrough::default());
+    }
+
+    #[tokio::test]
+    async fn can_look_through_portfolio() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/market/fund/1/holdings"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(String::from(
+                r#"{"holdings": [{"name": "Apple Inc", "weight": 50}]}"#,
+            )))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let positions: PositionsResponse = serde_json::from_str(
+            r#"{
+                "instrumentPositions": [
+                    {
+                        "instrumentType": "FUND",
+                        "positions": [
+                            {"accountId": "1", "accountName": "ISK", "accountType": "Investeringssparkonto", "acquiredValue": 900, "averageAcquiredPrice": 90, "change": 0, "changePercent": 0, "currency": "SEK", "depositable": true, "flagCode": "SE", "lastPrice": 100, "lastPriceUpdated": "2026-10-14T17:00:00", "name": "Global", "orderbookId": "1", "profit": 100, "profitPercent": 11.1, "tradable": true, "value": 1000, "volume": 10},
+                            {"accountId": "2", "accountName": "KF", "accountType": "Kapitalforsakring", "acquiredValue": 900, "averageAcquiredPrice": 90, "change": 0, "changePercent": 0, "currency": "SEK", "depositable": true, "flagCode": "SE", "lastPrice": 100, "lastPriceUpdated": "2026-10-14T17:00:00", "name": "Global", "orderbookId": "1", "profit": 100, "profitPercent": 11.1, "tradable": true, "value": 3000, "volume": 30}
+                        ],
+                        "todaysProfitPercent": 0, "totalProfitPercent": 0, "totalProfitValue": 0, "totalValue": 4000
+                    },
+                    {
+                        "instrumentType": "STOCK",
+                        "positions": [
+                            {"accountId": "1", "accountName": "ISK", "accountType": "Investeringssparkonto", "acquiredValue": 900, "averageAcquiredPrice": 90, "change": 0, "changePercent": 0, "currency": "SEK", "depositable": true, "flagCode": "SE", "lastPrice": 100, "lastPriceUpdated": "2026-10-14T17:00:00", "name": "Volvo B", "orderbookId": "5269", "profit": 100, "profitPercent": 11.1, "tradable": true, "value": 5000, "volume": 50}
+                        ],
+                        "todaysProfitPercent": 0, "totalProfitPercent": 0, "totalProfitValue": 0, "totalValue": 5000
+                    }
+                ],
+                "totalOwnCapital": 9000, "totalProfit": 0, "totalBuyingPower": 0, "totalBalance": 0, "totalProfitPercent": 0
+            }"#,
+        )
+        .expect("failed to parse positions");
+
+        let client = authenticated_client(&mock_server).await;
+
+        let result = assert_ok!(client.portfolio_look_through(&positions).await);
+
+        assert_eq!(result.total_fund_value, 4_000.0);
+        assert_eq!(result.holdings[0].value, 2_000.0);
+        assert_eq!(result.holdings[0].weight, Percent(50.0));
+    }
+}
@@ -1,4 +1,69 @@
+//! Unofficial client for the Avanza API.
+//!
+//! # Features
+//!
+//! Exactly one TLS backend has to be enabled:
+//!
+//! - `native-tls` (default): the platform's TLS library, OpenSSL on Linux.
+//! - `rustls`: pure Rust TLS with the webpki root certificates, for containers without
+//!   OpenSSL. Use it with `default-features = false, features = ["rustls", "push"]`.
+//!
+//! Optional extras, combinable with either backend:
+//!
+//! - `push` (default): real-time updates over the push websocket in `avanza::stream`.
+//! - `blocking`: a synchronous client in `avanza::blocking`.
+//! - `test-util`: `test_util::MockClient`, an in-memory [`api::AvanzaApi`].
+//! - `test-support`: canned responses and `wiremock` helpers in `avanza::test_support`.
+//! - `csv`: CSV export of positions and transactions.
+//!
+//! On `wasm32-unknown-unknown` requests go through the browser's fetch, so no TLS backend is
+//! needed. Build with `default-features = false`; `push` and `blocking` are not supported
+//! there, and neither are [`ClientBuilder::timeout`] and [`ClientBuilder::proxy`].
+
+#[cfg(all(feature = "native-tls", feature = "rustls"))]
+compile_error!("the `native-tls` and `rustls` features can not both be enabled");
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    not(any(feature = "native-tls", feature = "rustls"))
+))]
+compile_error!("enable one of the `native-tls` or `rustls` features");
+
+pub mod account;
+pub mod analysis;
+pub mod api;
+pub mod batch;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(not(target_arch = "wasm32"))]
+mod cassette;
 pub mod client;
+pub mod currency;
+pub mod endpoints;
 pub mod error;
+#[cfg(feature = "csv")]
+pub mod export;
+pub mod fund;
+pub mod market;
+pub mod metrics;
+pub mod order;
+mod pagination;
 pub mod portfolio;
+pub mod prelude;
 pub mod request;
+pub mod serde_helpers;
+pub mod stock;
+mod stockholm;
+#[cfg(feature = "push")]
+pub mod stream;
+mod strict;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+mod time;
+pub mod types;
+
+pub use client::{Client, ClientBuilder, Config};
+pub use error::{
+    ConfigError, ConversionError, ErrorKind, InvalidOrder, OrderValidationError, RequestError,
+};
@@ -0,0 +1,365 @@
+//! Chart data as a typed time series, from [`Client::get_chart_data`].
+//!
+//! The chart payload has points as `[epoch millis, value or null]` pairs, with the series to
+//! compare against, e.g. an index, listed separately. [`TimeSeries`] turns a series into
+//! timestamped values in Stockholm time, which is what Avanza's charts are drawn in.
+
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::stockholm::in_stockholm;
+use crate::types::OrderbookId;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChartData {
+    pub data_series: Vec<(i64, Option<f64>)>,
+    #[serde(default)]
+    pub comparisons: Vec<ChartComparison>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChartComparison {
+    pub name: String,
+    pub data_series: Vec<(i64, Option<f64>)>,
+}
+
+impl ChartData {
+    pub fn series(&self) -> TimeSeries {
+        TimeSeries::from_epoch_millis(&self.data_series)
+    }
+
+    /// The comparison series called `name`, aligned onto the timestamps of
+    /// [`ChartData::series`], see [`TimeSeries::align_to`].
+    pub fn comparison(&self, name: &str) -> Option<TimeSeries> {
+        self.comparisons
+            .iter()
+            .find(|comparison| comparison.name == name)
+            .map(|comparison| {
+                TimeSeries::from_epoch_millis(&comparison.data_series).align_to(&self.series())
+            })
+    }
+}
+
+/// Values in time order, with timestamps in Stockholm time.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TimeSeries {
+    points: Vec<(DateTime<FixedOffset>, f64)>,
+}
+
+impl TimeSeries {
+    /// Points as the chart payload has them. Null values and timestamps out of range are
+    /// dropped.
+    pub fn from_epoch_millis(points: &[(i64, Option<f64>)]) -> Self {
+        let mut points: Vec<(DateTime<FixedOffset>, f64)> = points
+            .iter()
+            .filter_map(|(millis, value)| {
+                let time = DateTime::<Utc>::from_timestamp_millis(*millis)?;
+                Some((in_stockholm(time), (*value)?))
+            })
+            .collect();
+        points.sort_by_key(|(time, _)| *time);
+        Self { points }
+    }
+
+    pub fn points(&self) -> &[(DateTime<FixedOffset>, f64)] {
+        &self.points
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The last point of every day, with days as in Stockholm.
+    pub fn resample_daily(&self) -> TimeSeries {
+        let mut points: Vec<(DateTime<FixedOffset>, f64)> = Vec::new();
+        for point in &self.points {
+            match points.last_mut() {
+                Some(last) if last.0.date_naive() == point.0.date_naive() => *last = *point,
+                _ => points.push(*point),
+            }
+        }
+        TimeSeries { points }
+    }
+
+    /// The lowest point, the first of them if the low was hit more than once.
+    pub fn min(&self) -> Option<(DateTime<FixedOffset>, f64)> {
+        self.points
+            .iter()
+            .copied()
+            .reduce(|min, point| if point.1 < min.1 { point } else { min })
+    }
+
+    /// The highest point, the first of them if the high was hit more than once.
+    pub fn max(&self) -> Option<(DateTime<FixedOffset>, f64)> {
+        self.points
+            .iter()
+            .copied()
+            .reduce(|max, point| if point.1 > max.1 { point } else { max })
+    }
+
+    pub fn last(&self) -> Option<(DateTime<FixedOffset>, f64)> {
+        self.points.last().copied()
+    }
+
+    /// This series at the timestamps of `reference`, each taking the latest value at or
+    /// before it. Timestamps before this series starts are left out, so both series can be
+    /// plotted relative to their first common point.
+    pub fn align_to(&self, reference: &TimeSeries) -> TimeSeries {
+        let mut points = Vec::with_capacity(reference.points.len());
+        let mut latest = None;
+        let mut own = self.points.iter().peekable();
+        for (time, _) in &reference.points {
+            while let Some(point) = own.next_if(|(own_time, _)| own_time <= time) {
+                latest = Some(point.1);
+            }
+            if let Some(value) = latest {
+                points.push((*time, value));
+            }
+        }
+        TimeSeries { points }
+    }
+}
+
+impl Client {
+    /// The chart for the inclusive range `from..=to`, with its comparison series.
+    pub async fn get_chart_data(
+        &self,
+        orderbook_id: &OrderbookId,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<ChartData, RequestError> {
+        self.fetch_spec::<ChartData>(
+            &endpoints::chart_data(orderbook_id, from, to),
+            Idempotency::Idempotent,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn millis(rfc3339: &str) -> i64 {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .timestamp_millis()
+    }
+
+    fn series(points: &[(&str, Option<f64>)]) -> TimeSeries {
+        let points: Vec<(i64, Option<f64>)> = points
+            .iter()
+            .map(|(time, value)| (millis(time), *value))
+            .collect();
+        TimeSeries::from_epoch_millis(&points)
+    }
+
+    fn times(series: &TimeSeries) -> Vec<String> {
+        series
+            .points()
+            .iter()
+            .map(|(time, _)| time.to_rfc3339())
+            .collect()
+    }
+
+    #[test]
+    fn drops_leading_nulls() {
+        let series = series(&[
+            ("2026-10-12T07:00:00Z", None),
+            ("2026-10-13T07:00:00Z", None),
+            ("2026-10-14T07:00:00Z", Some(101.5)),
+            ("2026-10-15T07:00:00Z", Some(99.0)),
+        ]);
+
+        assert_eq!(
+            times(&series),
+            ["2026-10-14T09:00:00+02:00", "2026-10-15T09:00:00+02:00"]
+        );
+        assert_eq!(series.min().map(|(_, value)| value), Some(99.0));
+        assert_eq!(series.max().map(|(_, value)| value), Some(101.5));
+        assert_eq!(series.last().map(|(_, value)| value), Some(99.0));
+    }
+
+    #[test]
+    fn all_nulls_is_empty() {
+        let series = series(&[("2026-10-12T07:00:00Z", None)]);
+
+        assert!(series.is_empty());
+        assert_eq!(series.min(), None);
+        assert_eq!(series.last(), None);
+    }
+
+    #[test]
+    fn sorts_points_by_time() {
+        let series = series(&[
+            ("2026-10-15T07:00:00Z", Some(2.0)),
+            ("2026-10-14T07:00:00Z", Some(1.0)),
+        ]);
+        assert_eq!(series.points()[0].1, 1.0);
+    }
+
+    #[test]
+    fn resamples_to_stockholm_days_across_spring_forward() {
+        // Summer time starts 2026-03-29 at 01:00 UTC.
+        let series = series(&[
+            ("2026-03-28T15:00:00Z", Some(1.0)),
+            // 00:30 CET on the 29th.
+            ("2026-03-28T23:30:00Z", Some(2.0)),
+            // 23:30 CEST on the 29th.
+            ("2026-03-29T21:30:00Z", Some(3.0)),
+            // 00:30 CEST on the 30th.
+            ("2026-03-29T22:30:00Z", Some(4.0)),
+        ]);
+
+        assert_eq!(
+            times(&series.resample_daily()),
+            [
+                "2026-03-28T16:00:00+01:00",
+                "2026-03-29T23:30:00+02:00",
+                "2026-03-30T00:30:00+02:00",
+            ]
+        );
+        let closes: Vec<f64> = series
+            .resample_daily()
+            .points()
+            .iter()
+            .map(|(_, value)| *value)
+            .collect();
+        assert_eq!(closes, [1.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn resamples_to_stockholm_days_across_fall_back() {
+        // Summer time ends 2026-10-25 at 01:00 UTC, so 02:30 local happens twice.
+        let series = series(&[
+            ("2026-10-25T00:30:00Z", Some(1.0)),
+            ("2026-10-25T01:30:00Z", Some(2.0)),
+            // 00:30 CET on the 26th.
+            ("2026-10-25T23:30:00Z", Some(3.0)),
+        ]);
+
+        assert_eq!(
+            times(&series),
+            [
+                "2026-10-25T02:30:00+02:00",
+                "2026-10-25T02:30:00+01:00",
+                "2026-10-26T00:30:00+01:00",
+            ]
+        );
+        assert_eq!(
+            times(&series.resample_daily()),
+            ["2026-10-25T02:30:00+01:00", "2026-10-26T00:30:00+01:00"]
+        );
+    }
+
+    #[test]
+    fn aligns_comparison_onto_series_timestamps() {
+        let stock = series(&[
+            ("2026-10-13T07:00:00Z", Some(100.0)),
+            ("2026-10-14T07:00:00Z", Some(102.0)),
+            ("2026-10-15T07:00:00Z", Some(101.0)),
+        ]);
+        let index = series(&[
+            ("2026-10-13T07:30:00Z", Some(2400.0)),
+            ("2026-10-14T06:00:00Z", Some(2410.0)),
+            ("2026-10-14T07:00:00Z", Some(2420.0)),
+        ]);
+
+        let aligned = index.align_to(&stock);
+
+        // Nothing before the index starts, then the latest value at or before each point.
+        assert_eq!(
+            aligned.points(),
+            &[(stock.points()[1].0, 2420.0), (stock.points()[2].0, 2420.0)]
+        );
+    }
+
+    #[test]
+    fn parses_chart_payload() {
+        let chart: ChartData = serde_json::from_str(
+            r#"{
+                "dataSeries": [[1791529200000, null], [1791615600000, 253.9], [1791702000000, 255.1]],
+                "comparisons": [
+                    {"name": "OMXS30", "dataSeries": [[1791529200000, 2400.5], [1791615600000, null], [1791702000000, 2410.0]]}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(chart.series().points().len(), 2);
+        let omx = chart.comparison("OMXS30").unwrap();
+        let values: Vec<f64> = omx.points().iter().map(|(_, value)| *value).collect();
+        assert_eq!(values, [2400.5, 2410.0]);
+        assert_eq!(
+            omx.points()[0].0,
+            chart.series().points()[0].0,
+            "aligned onto the series"
+        );
+        assert!(chart.comparison("OMXSPI").is_none());
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(
+            client
+                .get_chart_data(
+                    &OrderbookId::from("5269"),
+                    date(2026, 10, 1),
+                    date(2026, 10, 2)
+                )
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn can_get_chart_data() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/chart/orderbook/5269"))
+            .and(query_param("from", "2026-10-01"))
+            .and(query_param("to", "2026-10-02"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{
+                    "dataSeries": [[1791529200000, 251.1], [1791615600000, 253.9]],
+                    "comparisons": [{"name": "OMXS30", "dataSeries": [[1791529200000, 2400.5]]}]
+                }"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let chart = assert_ok!(
+            client
+                .get_chart_data(
+                    &OrderbookId::from("5269"),
+                    date(2026, 10, 1),
+                    date(2026, 10, 2)
+                )
+                .await
+        );
+
+        assert_eq!(chart.series().points().len(), 2);
+        assert_eq!(chart.comparison("OMXS30").unwrap().points().len(), 2);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::client::{Client, Idempotency};
+use crate::currency::CurrencyConverter;
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::types::Currency;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExchangeRatesResponse {
+    exchange_rates: Vec<ExchangeRate>,
+}
+
+/// One unit of `from_currency` costs `rate` units of `to_currency`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangeRate {
+    pub from_currency: Currency,
+    pub to_currency: Currency,
+    pub rate: f64,
+}
+
+impl Client {
+    /// Current rates of the currencies Avanza trades in, mostly quoted against SEK.
+    pub async fn get_exchange_rates(&self) -> Result<Vec<ExchangeRate>, RequestError> {
+        let resp = self
+            .fetch_spec::<ExchangeRatesResponse>(
+                &endpoints::exchange_rates(),
+                Idempotency::Idempotent,
+            )
+            .await?;
+        Ok(resp.exchange_rates)
+    }
+
+    /// A [`CurrencyConverter`] over the current exchange rates.
+    pub async fn currency_converter(&self) -> Result<CurrencyConverter, RequestError> {
+        Ok(CurrencyConverter::from_rates(
+            self.get_exchange_rates().await?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.get_exchange_rates().await);
+        assert_err!(client.currency_converter().await);
+    }
+
+    #[tokio::test]
+    async fn can_get_exchange_rates() {
+        let mock_server = MockServer::start().await;
+
+        let responder = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{
+                "exchangeRates": [
+                    {"fromCurrency": "USD", "toCurrency": "SEK", "rate": 10.87},
+                    {"fromCurrency": "NOK", "toCurrency": "SEK", "rate": 0.9731}
+                ]
+            }"#,
+        ));
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/market/exchange-rates"))
+            .respond_with(responder)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let rates = assert_ok!(client.get_exchange_rates().await);
+        assert_eq!(rates.len(), 2);
+        assert_eq!(rates[0].from_currency, Currency::USD);
+        assert_eq!(rates[0].to_currency, Currency::SEK);
+        assert_eq!(rates[0].rate, 10.87);
+
+        let converter = assert_ok!(client.currency_converter().await);
+        assert_eq!(
+            converter.convert(123.45, &Currency::USD, &Currency::NOK),
+            Ok(1379.0)
+        );
+    }
+}
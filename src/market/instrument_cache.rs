@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::time::Instant;
+
+use crate::market::orderbook::Orderbook;
+use crate::types::OrderbookId;
+
+struct Entry {
+    orderbook: Orderbook,
+    /// `None` for a ttl too long to add to the current time, which never expires.
+    expires_at: Option<Instant>,
+    last_used: u64,
+}
+
+impl Entry {
+    fn is_expired(&self) -> bool {
+        matches!(self.expires_at, Some(expires_at) if expires_at <= Instant::now())
+    }
+}
+
+#[derive(Default)]
+struct Entries {
+    by_id: HashMap<OrderbookId, Entry>,
+    uses: u64,
+}
+
+/// Memoizes `Client::get_orderbook` per orderbook id. Entries expire `ttl` after they were
+/// fetched, and the least recently used one is evicted once `capacity` is reached.
+pub struct InstrumentCache {
+    ttl: Duration,
+    capacity: usize,
+    entries: Mutex<Entries>,
+}
+
+impl InstrumentCache {
+    pub(crate) fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity,
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    pub(crate) fn get(&self, orderbook_id: &OrderbookId) -> Option<Orderbook> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.uses += 1;
+        let uses = entries.uses;
+        match entries.by_id.get_mut(orderbook_id) {
+            Some(entry) if !entry.is_expired() => {
+                entry.last_used = uses;
+                Some(entry.orderbook.clone())
+            }
+            Some(_) => {
+                entries.by_id.remove(orderbook_id);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub(crate) fn insert(&self, orderbook: Orderbook) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.uses += 1;
+        let uses = entries.uses;
+        if !entries.by_id.contains_key(&orderbook.id) && entries.by_id.len() >= self.capacity {
+            let least_recently_used = entries
+                .by_id
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(id, _)| id.clone());
+            if let Some(id) = least_recently_used {
+                entries.by_id.remove(&id);
+            }
+        }
+        entries.by_id.insert(
+            orderbook.id.clone(),
+            Entry {
+                orderbook,
+                expires_at: Instant::now().checked_add(self.ttl),
+                last_used: uses,
+            },
+        );
+    }
+
+    /// Drops the cached entry, the next lookup goes to the network.
+    pub fn invalidate(&self, orderbook_id: &OrderbookId) {
+        self.entries.lock().unwrap().by_id.remove(orderbook_id);
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().by_id.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().by_id.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Currency, InstrumentType};
+
+    fn orderbook(id: &str) -> Orderbook {
+        Orderbook {
+            id: OrderbookId::from(id),
+            name: String::from("Volvo B"),
+            instrument_type: InstrumentType::Stock,
+            currency: Currency::SEK,
+            isin: Some(String::from("SE0000115446")),
+            tick_size: Some(0.05),
+            tick_size_table: Vec::new(),
+            market_place: None,
+            tradable: None,
+            lowest_allowed_price: None,
+            highest_allowed_price: None,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn expires_after_ttl() {
+        let cache = InstrumentCache::new(Duration::from_secs(60), 10);
+        cache.insert(orderbook("5269"));
+
+        tokio::time::advance(Duration::from_secs(59)).await;
+        assert!(cache.get(&OrderbookId::from("5269")).is_some());
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert!(cache.get(&OrderbookId::from("5269")).is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[tokio::test]
+    async fn overlong_ttl_never_expires() {
+        let cache = InstrumentCache::new(Duration::MAX, 10);
+        cache.insert(orderbook("5269"));
+        assert!(cache.get(&OrderbookId::from("5269")).is_some());
+    }
+
+    #[tokio::test]
+    async fn evicts_least_recently_used() {
+        let cache = InstrumentCache::new(Duration::from_secs(60), 2);
+        cache.insert(orderbook("1"));
+        cache.insert(orderbook("2"));
+        assert!(cache.get(&OrderbookId::from("1")).is_some());
+
+        cache.insert(orderbook("3"));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&OrderbookId::from("1")).is_some());
+        assert!(cache.get(&OrderbookId::from("2")).is_none());
+        assert!(cache.get(&OrderbookId::from("3")).is_some());
+    }
+
+    #[tokio::test]
+    async fn invalidate_drops_entry() {
+        let cache = InstrumentCache::new(Duration::from_secs(60), 2);
+        cache.insert(orderbook("1"));
+        cache.invalidate(&OrderbookId::from("1"));
+        assert!(cache.get(&OrderbookId::from("1")).is_none());
+    }
+}
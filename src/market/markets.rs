@@ -0,0 +1,127 @@
+use std::fmt;
+
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::types::{CountryCode, Currency};
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MarketId(pub String);
+
+impl fmt::Display for MarketId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<&str> for MarketId {
+    fn from(value: &str) -> Self {
+        MarketId(String::from(value))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MarketsResponse {
+    markets: Vec<Market>,
+}
+
+/// Trading hours are local time on the marketplace, see `time_zone`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Market {
+    pub id: MarketId,
+    pub name: String,
+    pub country: CountryCode,
+    pub currency: Currency,
+    pub time_zone: String,
+    pub opening_time: NaiveTime,
+    pub closing_time: NaiveTime,
+}
+
+impl Client {
+    pub async fn get_markets(&self) -> Result<Vec<Market>, RequestError> {
+        let resp = self
+            .fetch_spec::<MarketsResponse>(&endpoints::markets(), Idempotency::Idempotent)
+            .await?;
+        Ok(resp.markets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.get_markets().await);
+    }
+
+    #[tokio::test]
+    async fn can_get_markets() {
+        let mock_server = MockServer::start().await;
+
+        let responder = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{
+                "markets": [
+                    {
+                        "id": "XSTO",
+                        "name": "Nasdaq Stockholm",
+                        "country": "SE",
+                        "currency": "SEK",
+                        "timeZone": "Europe/Stockholm",
+                        "openingTime": "09:00:00",
+                        "closingTime": "17:30:00"
+                    },
+                    {
+                        "id": "XNYS",
+                        "name": "NYSE",
+                        "country": "US",
+                        "currency": "USD",
+                        "timeZone": "America/New_York",
+                        "openingTime": "09:30:00",
+                        "closingTime": "16:00:00"
+                    }
+                ]
+            }"#,
+        ));
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/market/markets"))
+            .respond_with(responder)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let markets = assert_ok!(client.get_markets().await);
+
+        assert_eq!(markets.len(), 2);
+
+        let stockholm = &markets[0];
+        assert_eq!(stockholm.id, MarketId::from("XSTO"));
+        assert_eq!(stockholm.country, CountryCode::from("se"));
+        assert_eq!(stockholm.currency, Currency::SEK);
+        assert_eq!(
+            stockholm.closing_time,
+            NaiveTime::from_hms_opt(17, 30, 0).unwrap()
+        );
+
+        let nyse = &markets[1];
+        assert_eq!(nyse.id, MarketId::from("XNYS"));
+        assert_eq!(nyse.currency, Currency::USD);
+        assert_eq!(nyse.time_zone, "America/New_York");
+    }
+}
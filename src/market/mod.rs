@@ -0,0 +1,14 @@
+pub mod chart;
+pub mod exchange_rates;
+pub mod instrument_cache;
+pub mod markets;
+pub mod offers;
+pub mod order_depth;
+pub mod orderbook;
+pub mod ownership;
+pub mod price_history;
+pub mod quotes;
+pub mod suggest;
+pub mod tick_size;
+pub mod trades;
+pub mod watchlists;
@@ -0,0 +1,120 @@
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::types::OrderbookId;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OfferType {
+    Ipo,
+    RightsIssue,
+    Redemption,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OffersResponse {
+    offers: Vec<Offer>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Offer {
+    pub id: String,
+    pub company_name: String,
+    pub offer_type: OfferType,
+    pub subscription_period_start: NaiveDate,
+    pub subscription_period_end: NaiveDate,
+    /// `None` until the instrument is listed, e.g. for an IPO before its first trading day.
+    #[serde(default)]
+    pub orderbook_id: Option<OrderbookId>,
+}
+
+impl Offer {
+    pub fn is_open_on(&self, date: NaiveDate) -> bool {
+        self.subscription_period_start <= date && date <= self.subscription_period_end
+    }
+}
+
+impl Client {
+    pub async fn get_offers(&self) -> Result<Vec<Offer>, RequestError> {
+        let resp = self
+            .fetch_spec::<OffersResponse>(&endpoints::offers(), Idempotency::Idempotent)
+            .await?;
+        Ok(resp.offers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.get_offers().await);
+    }
+
+    #[tokio::test]
+    async fn can_get_offers() {
+        let mock_server = MockServer::start().await;
+
+        let responder = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{
+                "offers": [
+                    {
+                        "id": "1001",
+                        "companyName": "Nybolaget AB",
+                        "offerType": "IPO",
+                        "subscriptionPeriodStart": "2026-10-01",
+                        "subscriptionPeriodEnd": "2026-10-12",
+                        "orderbookId": null
+                    },
+                    {
+                        "id": "1002",
+                        "companyName": "Volvo AB",
+                        "offerType": "RIGHTS_ISSUE",
+                        "subscriptionPeriodStart": "2026-10-05",
+                        "subscriptionPeriodEnd": "2026-10-20",
+                        "orderbookId": "5269"
+                    }
+                ]
+            }"#,
+        ));
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/market/offers"))
+            .respond_with(responder)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let offers = assert_ok!(client.get_offers().await);
+
+        assert_eq!(offers.len(), 2);
+
+        let ipo = &offers[0];
+        assert_eq!(ipo.offer_type, OfferType::Ipo);
+        assert_eq!(ipo.orderbook_id, None);
+        assert!(ipo.is_open_on(NaiveDate::from_ymd_opt(2026, 10, 12).unwrap()));
+        assert!(!ipo.is_open_on(NaiveDate::from_ymd_opt(2026, 10, 13).unwrap()));
+
+        let held = OrderbookId::from("5269");
+        let rights_issue = &offers[1];
+        assert_eq!(rights_issue.offer_type, OfferType::RightsIssue);
+        assert_eq!(rights_issue.orderbook_id.as_ref(), Some(&held));
+    }
+}
@@ -0,0 +1,107 @@
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::types::OrderbookId;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepthEntry {
+    pub price: f64,
+    pub volume: u64,
+    #[serde(default)]
+    pub order_count: Option<u32>,
+}
+
+/// One row of the order book. A side is `None` when the book is thinner on that side.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderDepthLevel {
+    #[serde(default, rename = "buySide")]
+    pub buy: Option<DepthEntry>,
+    #[serde(default, rename = "sellSide")]
+    pub sell: Option<DepthEntry>,
+}
+
+/// Levels are ordered from the best price outwards.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderDepth {
+    pub orderbook_id: OrderbookId,
+    #[serde(default)]
+    pub levels: Vec<OrderDepthLevel>,
+}
+
+impl OrderDepth {
+    pub fn best_bid(&self) -> Option<&DepthEntry> {
+        self.levels.first()?.buy.as_ref()
+    }
+
+    pub fn best_ask(&self) -> Option<&DepthEntry> {
+        self.levels.first()?.sell.as_ref()
+    }
+}
+
+impl Client {
+    pub async fn get_order_depth(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<OrderDepth, RequestError> {
+        let resp = self
+            .fetch_spec::<OrderDepth>(
+                &endpoints::order_depth(orderbook_id),
+                Idempotency::Idempotent,
+            )
+            .await?;
+        Ok(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.get_order_depth(&OrderbookId::from("5269")).await);
+    }
+
+    #[tokio::test]
+    async fn can_get_order_depth() {
+        let mock_server = MockServer::start().await;
+
+        let responder = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{
+                "orderbookId": "5269",
+                "levels": [
+                    {"buySide": {"price": 253.8, "volume": 1200, "orderCount": 4}, "sellSide": {"price": 254.0, "volume": 800, "orderCount": 2}},
+                    {"buySide": {"price": 253.7, "volume": 3000, "orderCount": 9}, "sellSide": null}
+                ]
+            }"#,
+        ));
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/market/orderbook/5269/orderdepth"))
+            .respond_with(responder)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let depth = assert_ok!(client.get_order_depth(&OrderbookId::from("5269")).await);
+
+        assert_eq!(depth.levels.len(), 2);
+        assert_eq!(depth.best_bid().unwrap().price, 253.8);
+        assert_eq!(depth.best_ask().unwrap().order_count, Some(2));
+        assert_eq!(depth.levels[1].sell, None);
+    }
+}
@@ -0,0 +1,219 @@
+use crate::batch::fetch_many;
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::market::tick_size::{TickBand, TickSize};
+use crate::types::{Currency, InstrumentType, OrderbookId};
+use serde::{Deserialize, Serialize};
+
+/// Static details of an orderbook. For prices see `Client::get_orderbooks`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Orderbook {
+    pub id: OrderbookId,
+    pub name: String,
+    pub instrument_type: InstrumentType,
+    pub currency: Currency,
+    #[serde(default)]
+    pub isin: Option<String>,
+    #[serde(default)]
+    pub tick_size: Option<f64>,
+    /// Ticks per price band, for orderbooks whose tick depends on the price.
+    #[serde(default)]
+    pub tick_size_table: Vec<TickBand>,
+    #[serde(default)]
+    pub market_place: Option<String>,
+    /// `false` while the marketplace is closed or trading in the orderbook is halted.
+    #[serde(default)]
+    pub tradable: Option<bool>,
+    /// Today's price limits; orders outside them are rejected.
+    #[serde(default)]
+    pub lowest_allowed_price: Option<f64>,
+    #[serde(default)]
+    pub highest_allowed_price: Option<f64>,
+}
+
+impl Orderbook {
+    /// The prices the orderbook accepts: its tick size table, else its single tick size,
+    /// else the finest tick of the standard table, which rejects only prices no Nordic
+    /// share could trade at.
+    pub fn tick_sizes(&self) -> TickSize {
+        if !self.tick_size_table.is_empty() {
+            return TickSize::from_bands(self.tick_size_table.iter().copied());
+        }
+        self.tick_size
+            .map_or_else(|| TickSize::from_bands([]), TickSize::fixed)
+    }
+}
+
+impl Client {
+    /// Served from the instrument cache when one is configured and holds the orderbook.
+    pub async fn get_orderbook(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<Orderbook, RequestError> {
+        if !self.is_authenticated() {
+            return Err(RequestError::NotAuthenticatedError());
+        }
+        let cache = self.instrument_cache();
+        if let Some(orderbook) = cache.and_then(|cache| cache.get(orderbook_id)) {
+            return Ok(orderbook);
+        }
+        let resp = self.fetch_orderbook(orderbook_id).await?;
+        if let Some(cache) = cache {
+            cache.insert(resp.clone());
+        }
+        Ok(resp)
+    }
+
+    /// Bypasses the instrument cache, for when the trading status and price limits have to
+    /// be current.
+    pub(crate) async fn fetch_orderbook(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<Orderbook, RequestError> {
+        self.fetch_spec::<Orderbook>(&endpoints::orderbook(orderbook_id), Idempotency::Idempotent)
+            .await
+    }
+
+    /// [`Client::get_orderbook`] for every id, with at most `concurrency` requests in
+    /// flight. The results are in the order of `orderbook_ids`, and an id that fails, e.g.
+    /// one Avanza doesn't know, only fails its own slot.
+    pub async fn get_many_stock_info(
+        &self,
+        orderbook_ids: &[OrderbookId],
+        concurrency: usize,
+    ) -> Vec<Result<Orderbook, RequestError>> {
+        fetch_many(orderbook_ids, concurrency, |id| self.get_orderbook(id)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::test_support::{authenticated_client, authenticated_client_with, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn mock_orderbook(mock_server: &MockServer, expected_calls: u64) {
+        let responder = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{
+                "id": "5269",
+                "name": "Volvo B",
+                "instrumentType": "STOCK",
+                "currency": "SEK",
+                "isin": "SE0000115446",
+                "tickSize": 0.05,
+                "marketPlace": "Stockholmsbörsen"
+            }"#,
+        ));
+        Mock::given(method("GET"))
+            .and(path("/_mobile/market/orderbook/5269"))
+            .respond_with(responder)
+            .expect(expected_calls)
+            .mount(mock_server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.get_orderbook(&OrderbookId::from("5269")).await);
+    }
+
+    #[tokio::test]
+    async fn can_get_orderbook() {
+        let mock_server = MockServer::start().await;
+        mock_orderbook(&mock_server, 2).await;
+        let client = authenticated_client(&mock_server).await;
+
+        let orderbook = assert_ok!(client.get_orderbook(&OrderbookId::from("5269")).await);
+        assert_eq!(orderbook.name, "Volvo B");
+        assert_eq!(orderbook.instrument_type, InstrumentType::Stock);
+        assert_eq!(orderbook.tick_size, Some(0.05));
+        assert_eq!(orderbook.isin.as_deref(), Some("SE0000115446"));
+
+        // Without a cache every call goes to the network.
+        assert_ok!(client.get_orderbook(&OrderbookId::from("5269")).await);
+    }
+
+    #[tokio::test]
+    async fn can_get_many_stock_info() {
+        let mock_server = MockServer::start().await;
+        mock_orderbook(&mock_server, 2).await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/market/orderbook/404"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let client = authenticated_client(&mock_server).await;
+
+        let ids = [
+            OrderbookId::from("5269"),
+            OrderbookId::from("404"),
+            OrderbookId::from("5269"),
+        ];
+        let results = client.get_many_stock_info(&ids, 2).await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(assert_ok!(&results[0]).name, "Volvo B");
+        assert_err!(&results[1]);
+        assert_eq!(assert_ok!(&results[2]).name, "Volvo B");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn cache_hit_within_ttl() {
+        let mock_server = MockServer::start().await;
+        mock_orderbook(&mock_server, 1).await;
+        let client = authenticated_client_with(
+            &mock_server,
+            Client::builder().instrument_cache(Duration::from_secs(3600), 100),
+        )
+        .await;
+
+        assert_ok!(client.get_orderbook(&OrderbookId::from("5269")).await);
+        tokio::time::advance(Duration::from_secs(3599)).await;
+        assert_ok!(client.get_orderbook(&OrderbookId::from("5269")).await);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn refetch_after_expiry() {
+        let mock_server = MockServer::start().await;
+        mock_orderbook(&mock_server, 2).await;
+        let client = authenticated_client_with(
+            &mock_server,
+            Client::builder().instrument_cache(Duration::from_secs(3600), 100),
+        )
+        .await;
+
+        assert_ok!(client.get_orderbook(&OrderbookId::from("5269")).await);
+        tokio::time::advance(Duration::from_secs(3600)).await;
+        assert_ok!(client.get_orderbook(&OrderbookId::from("5269")).await);
+    }
+
+    #[tokio::test]
+    async fn refetch_after_invalidate() {
+        let mock_server = MockServer::start().await;
+        mock_orderbook(&mock_server, 2).await;
+        let client = authenticated_client_with(
+            &mock_server,
+            Client::builder().instrument_cache(Duration::from_secs(3600), 100),
+        )
+        .await;
+
+        assert_ok!(client.get_orderbook(&OrderbookId::from("5269")).await);
+        client
+            .instrument_cache()
+            .unwrap()
+            .invalidate(&OrderbookId::from("5269"));
+        assert_ok!(client.get_orderbook(&OrderbookId::from("5269")).await);
+    }
+}
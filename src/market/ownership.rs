@@ -0,0 +1,107 @@
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::types::OrderbookId;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnershipPoint {
+    pub date: NaiveDate,
+    pub number_of_owners: u64,
+}
+
+/// Number of owners at Avanza. Not every instrument has a history, e.g. certificates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ownership {
+    pub number_of_owners: u64,
+    #[serde(default)]
+    pub history: Vec<OwnershipPoint>,
+}
+
+impl Ownership {
+    /// Change in owners since the oldest point in the history.
+    pub fn change_over_history(&self) -> Option<i64> {
+        let oldest = self.history.iter().min_by_key(|p| p.date)?;
+        Some(self.number_of_owners as i64 - oldest.number_of_owners as i64)
+    }
+}
+
+impl Client {
+    pub async fn get_ownership(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<Ownership, RequestError> {
+        let resp = self
+            .fetch_spec::<Ownership>(&endpoints::ownership(orderbook_id), Idempotency::Idempotent)
+            .await?;
+        Ok(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.get_ownership(&OrderbookId::from("5269")).await);
+    }
+
+    #[tokio::test]
+    async fn can_get_ownership() {
+        let mock_server = MockServer::start().await;
+
+        let stock = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{
+                "numberOfOwners": 150230,
+                "history": [
+                    {"date": "2026-08-01", "numberOfOwners": 148000},
+                    {"date": "2026-09-01", "numberOfOwners": 149100},
+                    {"date": "2026-10-01", "numberOfOwners": 150010}
+                ]
+            }"#,
+        ));
+
+        let certificate =
+            ResponseTemplate::new(200).set_body_string(String::from(r#"{"numberOfOwners": 412}"#));
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/market/orderbook/5269/ownership"))
+            .respond_with(stock)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/market/orderbook/1016010/ownership"))
+            .respond_with(certificate)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let ownership = assert_ok!(client.get_ownership(&OrderbookId::from("5269")).await);
+        assert_eq!(ownership.number_of_owners, 150230);
+        assert_eq!(ownership.history.len(), 3);
+        assert_eq!(
+            ownership.history[0].date,
+            NaiveDate::from_ymd_opt(2026, 8, 1).unwrap()
+        );
+        assert_eq!(ownership.change_over_history(), Some(2230));
+
+        let ownership = assert_ok!(client.get_ownership(&OrderbookId::from("1016010")).await);
+        assert_eq!(ownership.number_of_owners, 412);
+        assert!(ownership.history.is_empty());
+        assert_eq!(ownership.change_over_history(), None);
+    }
+}
@@ -0,0 +1,253 @@
+use std::fmt;
+
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::types::OrderbookId;
+use chrono::{Days, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Resolution {
+    Day,
+    Week,
+    Month,
+}
+
+impl Resolution {
+    /// Longest range in days the chart API accepts in a single request for this resolution.
+    fn max_range_days(&self) -> u64 {
+        match self {
+            Resolution::Day => 365,
+            Resolution::Week => 5 * 365,
+            Resolution::Month => 20 * 365,
+        }
+    }
+}
+
+impl fmt::Display for Resolution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Resolution::Day => write!(f, "day"),
+            Resolution::Week => write!(f, "week"),
+            Resolution::Month => write!(f, "month"),
+        }
+    }
+}
+
+/// Funds only have a NAV close, so they are returned without open, high, low and volume.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Candle {
+    pub date: NaiveDate,
+    #[serde(default)]
+    pub open: Option<f64>,
+    #[serde(default)]
+    pub high: Option<f64>,
+    #[serde(default)]
+    pub low: Option<f64>,
+    pub close: f64,
+    #[serde(default)]
+    pub volume: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PriceHistoryResponse {
+    #[serde(default)]
+    candles: Vec<Candle>,
+}
+
+/// Splits the inclusive range `from..=to` into consecutive inclusive ranges of at most
+/// `max_days` days.
+fn chunk_range(from: NaiveDate, to: NaiveDate, max_days: u64) -> Vec<(NaiveDate, NaiveDate)> {
+    let mut chunks = Vec::new();
+    let mut start = from;
+    while start <= to {
+        let end = start
+            .checked_add_days(Days::new(max_days - 1))
+            .map_or(to, |end| end.min(to));
+        chunks.push((start, end));
+        match end.succ_opt() {
+            Some(next) => start = next,
+            None => break,
+        }
+    }
+    chunks
+}
+
+impl Client {
+    /// Candles for the inclusive range `from..=to`. Ranges longer than the API allows in
+    /// one request are fetched in several requests and stitched together in date order.
+    pub async fn get_price_history(
+        &self,
+        orderbook_id: &OrderbookId,
+        from: NaiveDate,
+        to: NaiveDate,
+        resolution: Resolution,
+    ) -> Result<Vec<Candle>, RequestError> {
+        let mut candles: Vec<Candle> = Vec::new();
+        for (chunk_from, chunk_to) in chunk_range(from, to, resolution.max_range_days()) {
+            let spec = endpoints::price_chart(orderbook_id, chunk_from, chunk_to, resolution);
+            let resp = self
+                .fetch_spec::<PriceHistoryResponse>(&spec, Idempotency::Idempotent)
+                .await?;
+            candles.extend(resp.candles);
+        }
+
+        candles.sort_by_key(|c| c.date);
+        candles.dedup_by_key(|c| c.date);
+        Ok(candles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn short_range_is_one_chunk() {
+        assert_eq!(
+            chunk_range(date(2026, 1, 1), date(2026, 1, 31), 365),
+            vec![(date(2026, 1, 1), date(2026, 1, 31))]
+        );
+    }
+
+    #[test]
+    fn long_range_is_chunked_without_gaps() {
+        assert_eq!(
+            chunk_range(date(2024, 1, 1), date(2026, 1, 10), 365),
+            vec![
+                (date(2024, 1, 1), date(2024, 12, 30)),
+                (date(2024, 12, 31), date(2025, 12, 30)),
+                (date(2025, 12, 31), date(2026, 1, 10)),
+            ]
+        );
+    }
+
+    #[test]
+    fn reversed_range_is_empty() {
+        assert!(chunk_range(date(2026, 2, 1), date(2026, 1, 1), 365).is_empty());
+    }
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(
+            client
+                .get_price_history(
+                    &OrderbookId::from("5269"),
+                    date(2026, 10, 1),
+                    date(2026, 10, 2),
+                    Resolution::Day
+                )
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn can_get_stock_price_history() {
+        let mock_server = MockServer::start().await;
+
+        let responder = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{
+                "candles": [
+                    {"date": "2026-10-02", "open": 251.0, "high": 254.4, "low": 250.2, "close": 253.9, "volume": 1830211},
+                    {"date": "2026-10-01", "open": 248.5, "high": 252.0, "low": 247.9, "close": 251.1, "volume": 2011873}
+                ]
+            }"#,
+        ));
+
+        Mock::given(method("GET"))
+            .and(path("/_api/price-chart/5269"))
+            .and(query_param("from", "2026-10-01"))
+            .and(query_param("to", "2026-10-02"))
+            .and(query_param("resolution", "day"))
+            .respond_with(responder)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let candles = assert_ok!(
+            client
+                .get_price_history(
+                    &OrderbookId::from("5269"),
+                    date(2026, 10, 1),
+                    date(2026, 10, 2),
+                    Resolution::Day
+                )
+                .await
+        );
+
+        assert_eq!(candles.len(), 2);
+        assert_eq!(candles[0].date, date(2026, 10, 1));
+        assert_eq!(candles[0].open, Some(248.5));
+        assert_eq!(candles[0].volume, Some(2011873));
+        assert_eq!(candles[1].close, 253.9);
+    }
+
+    #[tokio::test]
+    async fn can_get_fund_price_history_in_chunks() {
+        let mock_server = MockServer::start().await;
+
+        let first = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{"candles": [{"date": "2025-01-02", "close": 101.2}, {"date": "2025-12-30", "close": 110.4}]}"#,
+        ));
+        let second = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{"candles": [{"date": "2025-12-31", "close": 111.0}, {"date": "2026-01-02", "close": 111.9}]}"#,
+        ));
+
+        Mock::given(method("GET"))
+            .and(path("/_api/price-chart/377804"))
+            .and(query_param("from", "2025-01-01"))
+            .and(query_param("to", "2025-12-31"))
+            .respond_with(first)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/_api/price-chart/377804"))
+            .and(query_param("from", "2026-01-01"))
+            .and(query_param("to", "2026-01-05"))
+            .respond_with(second)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let candles = assert_ok!(
+            client
+                .get_price_history(
+                    &OrderbookId::from("377804"),
+                    date(2025, 1, 1),
+                    date(2026, 1, 5),
+                    Resolution::Day
+                )
+                .await
+        );
+
+        assert_eq!(candles.len(), 4);
+        assert!(candles.windows(2).all(|w| w[0].date < w[1].date));
+        assert!(candles
+            .iter()
+            .all(|c| c.open.is_none() && c.high.is_none() && c.low.is_none()));
+        assert!(candles.iter().all(|c| c.volume.is_none()));
+        assert_eq!(candles[3].close, 111.9);
+    }
+}
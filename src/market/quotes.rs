@@ -0,0 +1,227 @@
+use crate::batch::BatchResult;
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::types::{Currency, OrderbookId, Percent};
+use serde::{Deserialize, Serialize};
+
+/// Number of orderbook ids sent per request to the orderbook list endpoint, unless set with
+/// [`ClientBuilder::orderbook_chunk_size`](crate::client::ClientBuilder::orderbook_chunk_size).
+pub const DEFAULT_ORDERBOOK_CHUNK_SIZE: usize = 50;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Quote {
+    pub id: OrderbookId,
+    pub name: String,
+    pub currency: Currency,
+    #[serde(default)]
+    pub last_price: Option<f64>,
+    #[serde(default)]
+    pub change_percent: Option<Percent>,
+    #[serde(default)]
+    pub highest_price: Option<f64>,
+    #[serde(default)]
+    pub lowest_price: Option<f64>,
+    #[serde(default)]
+    pub total_volume_traded: Option<u64>,
+    #[serde(default)]
+    pub updated: Option<String>,
+}
+
+impl Client {
+    /// Quotes for several orderbooks, fetched in chunks of
+    /// [`ClientBuilder::orderbook_chunk_size`](crate::client::ClientBuilder::orderbook_chunk_size)
+    /// ids. The chunks are sent one after another, each waiting for the client's rate limit,
+    /// so a long list doesn't burst.
+    ///
+    /// A failed chunk doesn't stop the others: its ids come back as
+    /// [`BatchOutcome::Failed`](crate::batch::BatchOutcome::Failed) and the other chunks'
+    /// quotes are still returned. Ids Avanza doesn't know are `NotFound`.
+    pub async fn get_orderbooks(
+        &self,
+        orderbook_ids: &[OrderbookId],
+    ) -> Result<BatchResult<Quote>, RequestError> {
+        if !self.is_authenticated() {
+            return Err(RequestError::NotAuthenticatedError());
+        }
+
+        let mut result = BatchResult::default();
+        for chunk in orderbook_ids.chunks(self.orderbook_chunk_size) {
+            let spec = endpoints::orderbooks(chunk);
+            match self
+                .fetch_spec::<Vec<Quote>>(&spec, Idempotency::Idempotent)
+                .await
+            {
+                Ok(mut quotes) => {
+                    for id in chunk {
+                        if let Some(index) = quotes.iter().position(|quote| quote.id == *id) {
+                            result.push_found(id.clone(), quotes.swap_remove(index));
+                        }
+                    }
+                }
+                Err(error) => result.push_failure(chunk.to_vec(), error),
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::batch::BatchOutcome;
+    use crate::client::RateLimit;
+
+    use super::*;
+    use crate::test_support::{authenticated_client, authenticated_client_with, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path, path_regex};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.get_orderbooks(&[OrderbookId::from("5269")]).await);
+    }
+
+    #[tokio::test]
+    async fn can_get_orderbooks_in_chunks() {
+        let mock_server = MockServer::start().await;
+
+        let ids: Vec<OrderbookId> = (1..=DEFAULT_ORDERBOOK_CHUNK_SIZE + 1)
+            .map(|i| OrderbookId(i.to_string()))
+            .collect();
+        let first_chunk: Vec<String> = (1..=DEFAULT_ORDERBOOK_CHUNK_SIZE)
+            .map(|i| i.to_string())
+            .collect();
+
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/_mobile/market/orderbooklist/{}",
+                first_chunk.join(",")
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_string(String::from(
+                r#"[{"id": "1", "name": "Volvo B", "currency": "SEK", "lastPrice": 253.9, "changePercent": 1.2}]"#,
+            )))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/_mobile/market/orderbooklist/{}",
+                DEFAULT_ORDERBOOK_CHUNK_SIZE + 1
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_string(String::from(
+                r#"[{"id": "51", "name": "Apple Inc", "currency": "USD", "lastPrice": 231.4}]"#,
+            )))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let quotes = assert_ok!(client.get_orderbooks(&ids).await).into_values();
+
+        assert_eq!(quotes.len(), 2);
+        assert_eq!(quotes[0].id, OrderbookId::from("1"));
+        assert_eq!(quotes[0].change_percent, Some(Percent(1.2)));
+        assert_eq!(quotes[1].currency, Currency::USD);
+        assert_eq!(quotes[1].change_percent, None);
+    }
+
+    #[tokio::test]
+    async fn failed_chunk_only_fails_its_ids() {
+        let mock_server = MockServer::start().await;
+
+        let chunks = [
+            (
+                "1,2",
+                "200",
+                r#"[{"id": "1", "name": "Volvo B", "currency": "SEK", "lastPrice": 253.9}]"#,
+            ),
+            ("3,4", "503", ""),
+            (
+                "5",
+                "200",
+                r#"[{"id": "5", "name": "Apple Inc", "currency": "USD", "lastPrice": 231.4}]"#,
+            ),
+        ];
+        for (ids, status, body) in chunks {
+            Mock::given(method("GET"))
+                .and(path(format!("/_mobile/market/orderbooklist/{}", ids)))
+                .respond_with(
+                    ResponseTemplate::new(status.parse::<u16>().unwrap()).set_body_string(body),
+                )
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
+
+        let client =
+            authenticated_client_with(&mock_server, Client::builder().orderbook_chunk_size(2))
+                .await;
+
+        let ids: Vec<OrderbookId> = (1..=5).map(|i| OrderbookId(i.to_string())).collect();
+        let result = assert_ok!(client.get_orderbooks(&ids).await);
+
+        assert!(!result.is_complete());
+        assert_eq!(result.failures().len(), 1);
+        assert_eq!(
+            result.failures()[0].ids,
+            vec![OrderbookId::from("3"), OrderbookId::from("4")]
+        );
+        assert!(
+            matches!(result.get(&OrderbookId::from("1")), BatchOutcome::Found(quote) if quote.name == "Volvo B")
+        );
+        assert!(matches!(
+            result.get(&OrderbookId::from("2")),
+            BatchOutcome::NotFound
+        ));
+        assert!(matches!(
+            result.get(&OrderbookId::from("3")),
+            BatchOutcome::Failed(_)
+        ));
+        assert!(matches!(
+            result.get(&OrderbookId::from("4")),
+            BatchOutcome::Failed(_)
+        ));
+        assert!(
+            matches!(result.get(&OrderbookId::from("5")), BatchOutcome::Found(quote) if quote.currency == Currency::USD)
+        );
+        assert_eq!(result.values().count(), 2);
+        assert_err!(result.into_result());
+    }
+
+    #[tokio::test]
+    async fn chunks_are_paced_by_the_rate_limit() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex("^/_mobile/market/orderbooklist/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client_with(
+            &mock_server,
+            Client::builder()
+                .rate_limit(RateLimit::per_second(10))
+                .orderbook_chunk_size(1),
+        )
+        .await;
+
+        let ids: Vec<OrderbookId> = (1..=3).map(|i| OrderbookId(i.to_string())).collect();
+        let start = Instant::now();
+        let result = assert_ok!(client.get_orderbooks(&ids).await);
+
+        assert!(result.is_complete());
+        // Three requests at least 100 ms apart.
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+}
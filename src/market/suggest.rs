@@ -0,0 +1,139 @@
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::types::{InstrumentType, OrderbookId};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Suggestion {
+    pub name: String,
+    #[serde(default)]
+    pub ticker: Option<String>,
+    pub orderbook_id: OrderbookId,
+    pub instrument_type: InstrumentType,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SuggestResponse {
+    #[serde(default)]
+    hits: Vec<Suggestion>,
+}
+
+impl Client {
+    /// Compact search hits for autocompletion. A blank `prefix` returns no hits without
+    /// making a request.
+    pub async fn suggest(
+        &self,
+        prefix: &str,
+        limit: usize,
+    ) -> Result<Vec<Suggestion>, RequestError> {
+        let prefix = prefix.trim();
+        if prefix.is_empty() {
+            return Ok(Vec::new());
+        }
+        let resp = self
+            .fetch_spec::<SuggestResponse>(
+                &endpoints::suggest(prefix, limit),
+                Idempotency::Idempotent,
+            )
+            .await?;
+        Ok(resp.hits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{any, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.suggest("vol", 10).await);
+    }
+
+    #[tokio::test]
+    async fn empty_prefix_does_not_request() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let client = Client::builder()
+            .config(config())
+            .api_url(mock_server.uri())
+            .build()
+            .unwrap();
+
+        assert_eq!(assert_ok!(client.suggest("", 10).await), vec![]);
+        assert_eq!(assert_ok!(client.suggest("  ", 10).await), vec![]);
+    }
+
+    #[tokio::test]
+    async fn can_suggest_multiple_instrument_types() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/market/search/suggest"))
+            .and(query_param("query", "vol"))
+            .and(query_param("limit", "5"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(String::from(
+                r#"{
+                    "hits": [
+                        {"name": "Volvo B", "ticker": "VOLV B", "orderbookId": "5269", "instrumentType": "STOCK"},
+                        {"name": "BULL VOLVO X5 AVA", "ticker": null, "orderbookId": "1016010", "instrumentType": "CERTIFICATE"},
+                        {"name": "Volatilitetsfond", "orderbookId": "778899", "instrumentType": "FUND"}
+                    ]
+                }"#,
+            )))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let hits = assert_ok!(client.suggest("vol", 5).await);
+
+        assert_eq!(hits.len(), 3);
+        assert_eq!(hits[0].instrument_type, InstrumentType::Stock);
+        assert_eq!(hits[0].ticker.as_deref(), Some("VOLV B"));
+        assert_eq!(hits[1].instrument_type, InstrumentType::Certificate);
+        assert_eq!(hits[1].ticker, None);
+        assert_eq!(hits[2].instrument_type, InstrumentType::Fund);
+        assert_eq!(hits[2].orderbook_id, OrderbookId::from("778899"));
+    }
+
+    #[tokio::test]
+    async fn can_suggest_with_swedish_characters() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/market/search/suggest"))
+            .and(query_param("query", "Öresund & Co"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(String::from(
+                r#"{"hits": [{"name": "Öresund", "ticker": "ORES", "orderbookId": "5315", "instrumentType": "STOCK"}]}"#,
+            )))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let hits = assert_ok!(client.suggest("Öresund & Co", 10).await);
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].name, "Öresund");
+    }
+}
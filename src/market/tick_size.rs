@@ -0,0 +1,253 @@
+use serde::{Deserialize, Serialize};
+
+// Prices and ticks are binary floats, so 187.45 is not an exact multiple of 0.05 and
+// 9.9999999999 is meant to be 10. Anything this close, relative to the tick or the band
+// bound, counts as exact.
+const EPSILON: f64 = 1e-9;
+
+/// One price band of a tick size table: from `min` up to the next band, prices move in
+/// steps of `tick`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TickBand {
+    pub min: f64,
+    pub tick: f64,
+}
+
+// Lower bounds of the price bands of the ESMA tick size regime that Nordic markets follow.
+const FESE_BOUNDS: [f64; 19] = [
+    0.0, 0.1, 0.2, 0.5, 1.0, 2.0, 5.0, 10.0, 20.0, 50.0, 100.0, 200.0, 500.0, 1000.0, 2000.0,
+    5000.0, 10000.0, 20000.0, 50000.0,
+];
+
+// Ticks per price band, for liquidity bands 1 (least traded) to 6.
+const FESE_TICKS: [[f64; 6]; 19] = [
+    [0.0005, 0.0002, 0.0001, 0.0001, 0.0001, 0.0001],
+    [0.001, 0.0005, 0.0002, 0.0001, 0.0001, 0.0001],
+    [0.002, 0.001, 0.0005, 0.0002, 0.0001, 0.0001],
+    [0.005, 0.002, 0.001, 0.0005, 0.0002, 0.0001],
+    [0.01, 0.005, 0.002, 0.001, 0.0005, 0.0002],
+    [0.02, 0.01, 0.005, 0.002, 0.001, 0.0005],
+    [0.05, 0.02, 0.01, 0.005, 0.002, 0.001],
+    [0.1, 0.05, 0.02, 0.01, 0.005, 0.002],
+    [0.2, 0.1, 0.05, 0.02, 0.01, 0.005],
+    [0.5, 0.2, 0.1, 0.05, 0.02, 0.01],
+    [1.0, 0.5, 0.2, 0.1, 0.05, 0.02],
+    [2.0, 1.0, 0.5, 0.2, 0.1, 0.05],
+    [5.0, 2.0, 1.0, 0.5, 0.2, 0.1],
+    [10.0, 5.0, 2.0, 1.0, 0.5, 0.2],
+    [20.0, 10.0, 5.0, 2.0, 1.0, 0.5],
+    [50.0, 20.0, 10.0, 5.0, 2.0, 1.0],
+    [100.0, 50.0, 20.0, 10.0, 5.0, 2.0],
+    [200.0, 100.0, 50.0, 20.0, 10.0, 5.0],
+    [500.0, 200.0, 100.0, 50.0, 20.0, 10.0],
+];
+
+/// The prices an orderbook accepts, see `Orderbook::tick_sizes`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TickSize {
+    // Sorted by `min`, the first one starting at zero.
+    bands: Vec<TickBand>,
+}
+
+impl TickSize {
+    /// The same tick at every price.
+    pub fn fixed(tick: f64) -> Self {
+        Self::from_bands(vec![TickBand { min: 0.0, tick }])
+    }
+
+    /// A table of price bands, in any order. Bands without a positive tick are ignored, and
+    /// prices below the lowest band use its tick.
+    pub fn from_bands(bands: impl IntoIterator<Item = TickBand>) -> Self {
+        let mut bands: Vec<TickBand> = bands
+            .into_iter()
+            .filter(|band| band.tick.is_finite() && band.tick > 0.0)
+            .collect();
+        bands.sort_by(|a, b| a.min.total_cmp(&b.min));
+        if bands.is_empty() {
+            bands.push(FESE_FALLBACK);
+        }
+        Self { bands }
+    }
+
+    /// The standard table for `liquidity_band` 1 to 6, where 6 is the most traded shares.
+    /// Bands out of range are clamped.
+    pub fn fese(liquidity_band: u8) -> Self {
+        let column = usize::from(liquidity_band.clamp(1, 6)) - 1;
+        Self::from_bands(
+            FESE_BOUNDS
+                .iter()
+                .zip(FESE_TICKS.iter())
+                .map(|(min, ticks)| TickBand {
+                    min: *min,
+                    tick: ticks[column],
+                }),
+        )
+    }
+
+    /// The tick that applies at `price`.
+    pub fn tick_at(&self, price: f64) -> f64 {
+        self.band_at(price).tick
+    }
+
+    pub fn is_valid_price(&self, price: f64) -> bool {
+        if !price.is_finite() || price <= 0.0 {
+            return false;
+        }
+        let ticks = price / self.tick_at(price);
+        (ticks - ticks.round()).abs() <= EPSILON * ticks.abs().max(1.0)
+    }
+
+    /// The highest valid price at or below `price`.
+    pub fn round_down(&self, price: f64) -> f64 {
+        let band = self.band_at(price);
+        let steps = ((price - band.min) / band.tick + EPSILON).floor();
+        clean(band.min + steps * band.tick)
+    }
+
+    /// The lowest valid price at or above `price`. Band bounds are valid in both bands, so
+    /// rounding up to the next band's bound is the same either way.
+    pub fn round_up(&self, price: f64) -> f64 {
+        let band = self.band_at(price);
+        let steps = ((price - band.min) / band.tick - EPSILON).ceil();
+        clean(band.min + steps * band.tick)
+    }
+
+    fn band_at(&self, price: f64) -> TickBand {
+        self.bands
+            .iter()
+            .rev()
+            .find(|band| price >= band.min - EPSILON * band.min.max(1.0))
+            .copied()
+            .unwrap_or(self.bands[0])
+    }
+}
+
+// The finest tick of the standard table, which any listed share's prices are multiples of.
+const FESE_FALLBACK: TickBand = TickBand {
+    min: 0.0,
+    tick: 0.0001,
+};
+
+/// Drops the float noise of `min + steps * tick`, e.g. 0.30000000000000004.
+fn clean(price: f64) -> f64 {
+    (price * 1e8).round() / 1e8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fese_ticks_by_price_band() {
+        let most_liquid = TickSize::fese(6);
+        let least_liquid = TickSize::fese(1);
+        let cases = [
+            (0.05, 0.0001, 0.0005),
+            (0.75, 0.0001, 0.005),
+            (3.0, 0.0005, 0.02),
+            (10.0, 0.002, 0.1),
+            (187.45, 0.02, 1.0),
+            (999.99, 0.1, 5.0),
+            (1000.0, 0.2, 10.0),
+            (75000.0, 10.0, 500.0),
+        ];
+        for (price, liquid, illiquid) in cases {
+            assert_eq!(most_liquid.tick_at(price), liquid, "{}", price);
+            assert_eq!(least_liquid.tick_at(price), illiquid, "{}", price);
+        }
+    }
+
+    #[test]
+    fn validates_prices_across_bands() {
+        let ticks = TickSize::fese(4);
+        let cases = [
+            (0.0502, true),
+            (0.05025, false),
+            (1.501, true),
+            (1.5015, false),
+            (9.995, true),
+            (9.996, false),
+            (10.0, true),
+            (10.01, true),
+            (10.015, false),
+            (187.4, true),
+            (187.45, false),
+            (1234.0, true),
+            (1234.5, false),
+            (0.0, false),
+            (-10.0, false),
+            (f64::NAN, false),
+        ];
+        for (price, valid) in cases {
+            assert_eq!(ticks.is_valid_price(price), valid, "{}", price);
+        }
+    }
+
+    #[test]
+    fn rounds_within_a_band() {
+        let ticks = TickSize::fixed(0.05);
+        assert_eq!(ticks.round_down(187.47), 187.45);
+        assert_eq!(ticks.round_up(187.47), 187.5);
+        assert_eq!(ticks.round_down(187.45), 187.45);
+        assert_eq!(ticks.round_up(187.45), 187.45);
+        assert_eq!(ticks.round_up(0.26), 0.3);
+    }
+
+    #[test]
+    fn rounds_across_band_boundaries() {
+        // 5 to 10 in steps of 0.005, 10 to 20 in steps of 0.01.
+        let ticks = TickSize::fese(4);
+        assert_eq!(ticks.round_up(9.9971), 10.0);
+        assert_eq!(ticks.round_down(9.9971), 9.995);
+        assert_eq!(ticks.round_down(10.007), 10.0);
+        assert_eq!(ticks.round_up(10.007), 10.01);
+    }
+
+    #[test]
+    fn float_noise_at_a_boundary_is_the_boundary() {
+        let ticks = TickSize::fese(1);
+        let almost_ten = 9.999999999999;
+        assert!(ticks.is_valid_price(almost_ten));
+        assert_eq!(ticks.tick_at(almost_ten), 0.1);
+        assert_eq!(ticks.round_down(almost_ten), 10.0);
+        assert_eq!(ticks.round_up(almost_ten), 10.0);
+
+        // Far enough from 10 to be a price of its own, and not on the 0.05 tick below it.
+        assert!(!ticks.is_valid_price(9.999999));
+        assert_eq!(ticks.round_down(9.999999), 9.95);
+        assert_eq!(ticks.round_up(9.999999), 10.0);
+
+        let sum = 0.1 + 0.2;
+        assert!(TickSize::fixed(0.1).is_valid_price(sum));
+        assert_eq!(TickSize::fixed(0.1).round_down(sum), 0.3);
+    }
+
+    #[test]
+    fn table_from_an_orderbook() {
+        let ticks = TickSize::from_bands([
+            TickBand {
+                min: 100.0,
+                tick: 0.1,
+            },
+            TickBand {
+                min: 0.0,
+                tick: 0.01,
+            },
+            TickBand {
+                min: 50.0,
+                tick: 0.0,
+            },
+        ]);
+        assert_eq!(ticks.tick_at(99.99), 0.01);
+        assert_eq!(ticks.tick_at(100.0), 0.1);
+        assert!(ticks.is_valid_price(99.99));
+        assert!(!ticks.is_valid_price(100.05));
+    }
+
+    #[test]
+    fn empty_table_falls_back_to_finest_tick() {
+        let ticks = TickSize::from_bands([]);
+        assert_eq!(ticks.tick_at(187.45), 0.0001);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::types::OrderbookId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Trade {
+    pub deal_id: String,
+    pub price: f64,
+    pub volume: u64,
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    pub deal_time: DateTime<Utc>,
+    /// Broker code of the buyer, when disclosed.
+    #[serde(default)]
+    pub buyer: Option<String>,
+    /// Broker code of the seller, when disclosed.
+    #[serde(default)]
+    pub seller: Option<String>,
+}
+
+impl Client {
+    /// Today's trades for the orderbook, latest first.
+    pub async fn get_trades(&self, orderbook_id: &OrderbookId) -> Result<Vec<Trade>, RequestError> {
+        let resp = self
+            .fetch_spec::<Vec<Trade>>(&endpoints::trades(orderbook_id), Idempotency::Idempotent)
+            .await?;
+        Ok(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.get_trades(&OrderbookId::from("5269")).await);
+    }
+
+    #[tokio::test]
+    async fn can_get_trades() {
+        let mock_server = MockServer::start().await;
+
+        let responder = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"[
+                {"dealId": "d-2", "price": 253.9, "volume": 150, "dealTime": 1791990060000, "buyer": "AVA", "seller": "NON"},
+                {"dealId": "d-1", "price": 253.8, "volume": 40, "dealTime": 1791990000000}
+            ]"#,
+        ));
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/market/orderbook/5269/trades"))
+            .respond_with(responder)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let trades = assert_ok!(client.get_trades(&OrderbookId::from("5269")).await);
+
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].deal_id, "d-2");
+        assert_eq!(trades[0].buyer.as_deref(), Some("AVA"));
+        assert_eq!(trades[1].seller, None);
+        assert!(trades[0].deal_time > trades[1].deal_time);
+    }
+}
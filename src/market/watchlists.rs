@@ -0,0 +1,167 @@
+use std::fmt;
+
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::market::quotes::Quote;
+use crate::types::OrderbookId;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct WatchlistId(pub String);
+
+impl fmt::Display for WatchlistId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<&str> for WatchlistId {
+    fn from(value: &str) -> Self {
+        WatchlistId(String::from(value))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Watchlist {
+    pub id: WatchlistId,
+    pub name: String,
+    #[serde(default)]
+    pub orderbook_ids: Vec<OrderbookId>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchlistQuote {
+    pub orderbook_id: OrderbookId,
+    /// `None` when the quotes endpoint has nothing for the orderbook.
+    pub quote: Option<Quote>,
+}
+
+impl Client {
+    pub async fn get_watchlists(&self) -> Result<Vec<Watchlist>, RequestError> {
+        let resp = self
+            .fetch_spec::<Vec<Watchlist>>(&endpoints::watchlists(), Idempotency::Idempotent)
+            .await?;
+        Ok(resp)
+    }
+
+    pub async fn get_watchlist(
+        &self,
+        watchlist_id: &WatchlistId,
+    ) -> Result<Watchlist, RequestError> {
+        let resp = self
+            .fetch_spec::<Watchlist>(&endpoints::watchlist(watchlist_id), Idempotency::Idempotent)
+            .await?;
+        Ok(resp)
+    }
+
+    /// Quotes for every orderbook in the watchlist, in watchlist order. Fails if any of the
+    /// requests for the quotes does.
+    pub async fn get_watchlist_quotes(
+        &self,
+        watchlist_id: &WatchlistId,
+    ) -> Result<Vec<WatchlistQuote>, RequestError> {
+        let watchlist = self.get_watchlist(watchlist_id).await?;
+        let mut quotes = self
+            .get_orderbooks(&watchlist.orderbook_ids)
+            .await?
+            .into_result()?;
+
+        Ok(watchlist
+            .orderbook_ids
+            .into_iter()
+            .map(|orderbook_id| {
+                let quote = quotes
+                    .iter()
+                    .position(|q| q.id == orderbook_id)
+                    .map(|index| quotes.swap_remove(index));
+                WatchlistQuote {
+                    orderbook_id,
+                    quote,
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.get_watchlists().await);
+        assert_err!(client.get_watchlist_quotes(&WatchlistId::from("1")).await);
+    }
+
+    #[tokio::test]
+    async fn can_get_watchlists() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/usercontent/watchlist"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(String::from(
+                r#"[{"id": "111", "name": "Bevakning", "orderbookIds": ["5269", "5247"]}, {"id": "222", "name": "Tomt"}]"#,
+            )))
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let watchlists = assert_ok!(client.get_watchlists().await);
+
+        assert_eq!(watchlists.len(), 2);
+        assert_eq!(watchlists[0].orderbook_ids.len(), 2);
+        assert!(watchlists[1].orderbook_ids.is_empty());
+    }
+
+    #[tokio::test]
+    async fn can_get_watchlist_quotes() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/usercontent/watchlist/111"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(String::from(
+                r#"{"id": "111", "name": "Bevakning", "orderbookIds": ["5269", "999999", "5247"]}"#,
+            )))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/market/orderbooklist/5269,999999,5247"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(String::from(
+                r#"[
+                    {"id": "5247", "name": "Investor B", "currency": "SEK", "lastPrice": 301.2},
+                    {"id": "5269", "name": "Volvo B", "currency": "SEK", "lastPrice": 253.9}
+                ]"#,
+            )))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let quotes = assert_ok!(client.get_watchlist_quotes(&WatchlistId::from("111")).await);
+
+        assert_eq!(quotes.len(), 3);
+
+        assert_eq!(quotes[0].orderbook_id, OrderbookId::from("5269"));
+        assert_eq!(quotes[0].quote.as_ref().unwrap().name, "Volvo B");
+
+        assert_eq!(quotes[1].orderbook_id, OrderbookId::from("999999"));
+        assert_eq!(quotes[1].quote, None);
+
+        assert_eq!(quotes[2].orderbook_id, OrderbookId::from("5247"));
+        assert_eq!(quotes[2].quote.as_ref().unwrap().last_price, Some(301.2));
+    }
+}
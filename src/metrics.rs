@@ -0,0 +1,213 @@
+//! Request metrics, reported to a [`MetricsSink`] set with `ClientBuilder::metrics`.
+//!
+//! Endpoints are labeled with the method and path, ids replaced by `{id}` so the number of
+//! labels stays bounded, e.g. `GET /_mobile/account/{id}/transactions`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use reqwest::{Method, Url};
+
+use crate::error::ErrorKind;
+
+/// Receives measurements from every request a client sends. Calls happen inline on the
+/// request path, so implementations should only record and return.
+pub trait MetricsSink: Send + Sync {
+    /// A response arrived after `duration`. Retries are recorded one by one.
+    fn record_request(&self, endpoint: &str, status: u16, duration: Duration);
+
+    /// A request failed without a usable response, or its body could not be parsed.
+    fn record_error(&self, endpoint: &str, kind: ErrorKind);
+
+    /// A request was held back `delay` by the rate limit, with `waiting` requests, this one
+    /// included, held back at the moment.
+    fn record_rate_limit(&self, waiting: usize, delay: Duration) {
+        let _ = (waiting, delay);
+    }
+
+    /// The client logged in, the first time or again after the session expired.
+    fn record_authentication(&self) {}
+}
+
+#[derive(Clone)]
+pub(crate) struct Metrics(pub(crate) Arc<dyn MetricsSink>);
+
+impl fmt::Debug for Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("MetricsSink")
+    }
+}
+
+pub(crate) fn endpoint_label(method: &Method, url: &Url) -> String {
+    let path: Vec<&str> = url
+        .path()
+        .split('/')
+        .map(|segment| {
+            if segment.chars().any(|c| c.is_ascii_digit()) {
+                "{id}"
+            } else {
+                segment
+            }
+        })
+        .collect();
+    format!("{} {}", method, path.join("/"))
+}
+
+/// Upper bounds of the latency buckets, the same as Prometheus client libraries default to.
+pub const LATENCY_BUCKETS: [Duration; 11] = [
+    Duration::from_millis(5),
+    Duration::from_millis(10),
+    Duration::from_millis(25),
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_millis(2500),
+    Duration::from_secs(5),
+    Duration::from_secs(10),
+];
+
+/// Latencies counted per bucket of [`LATENCY_BUCKETS`]. `buckets[i]` counts the requests
+/// that took at most `LATENCY_BUCKETS[i]` and more than the bound before it; the last entry
+/// counts the ones slower than every bound.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Histogram {
+    pub buckets: [u64; LATENCY_BUCKETS.len() + 1],
+    pub count: u64,
+    pub sum: Duration,
+}
+
+impl Histogram {
+    fn observe(&mut self, duration: Duration) {
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|bound| duration <= *bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.sum += duration;
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EndpointMetrics {
+    /// Responses per HTTP status code.
+    pub responses: BTreeMap<u16, u64>,
+    pub errors: BTreeMap<ErrorKind, u64>,
+    pub latency: Histogram,
+}
+
+impl EndpointMetrics {
+    pub fn requests(&self) -> u64 {
+        self.responses.values().sum()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MetricsSnapshot {
+    pub endpoints: BTreeMap<String, EndpointMetrics>,
+    pub authentications: u64,
+    /// Requests held back by the rate limit at the last measurement.
+    pub rate_limit_waiting: usize,
+    /// Requests that had to wait for the rate limit at all.
+    pub rate_limited_requests: u64,
+    pub rate_limit_delay: Duration,
+}
+
+impl MetricsSnapshot {
+    /// Errors of `kind` over all endpoints.
+    pub fn errors(&self, kind: ErrorKind) -> u64 {
+        self.endpoints
+            .values()
+            .filter_map(|endpoint| endpoint.errors.get(&kind))
+            .sum()
+    }
+}
+
+/// A [`MetricsSink`] that keeps everything in memory, to export from a [`MetricsSnapshot`].
+#[derive(Debug, Default)]
+pub struct InMemoryMetrics {
+    snapshot: Mutex<MetricsSnapshot>,
+}
+
+impl InMemoryMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        self.snapshot.lock().unwrap().clone()
+    }
+}
+
+impl MetricsSink for InMemoryMetrics {
+    fn record_request(&self, endpoint: &str, status: u16, duration: Duration) {
+        let mut snapshot = self.snapshot.lock().unwrap();
+        let endpoint = snapshot
+            .endpoints
+            .entry(String::from(endpoint))
+            .or_default();
+        *endpoint.responses.entry(status).or_default() += 1;
+        endpoint.latency.observe(duration);
+    }
+
+    fn record_error(&self, endpoint: &str, kind: ErrorKind) {
+        let mut snapshot = self.snapshot.lock().unwrap();
+        let endpoint = snapshot
+            .endpoints
+            .entry(String::from(endpoint))
+            .or_default();
+        *endpoint.errors.entry(kind).or_default() += 1;
+    }
+
+    fn record_rate_limit(&self, waiting: usize, delay: Duration) {
+        let mut snapshot = self.snapshot.lock().unwrap();
+        snapshot.rate_limit_waiting = waiting;
+        if !delay.is_zero() {
+            snapshot.rate_limited_requests += 1;
+            snapshot.rate_limit_delay += delay;
+        }
+    }
+
+    fn record_authentication(&self) {
+        self.snapshot.lock().unwrap().authentications += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_replace_ids() {
+        let url =
+            Url::parse("https://www.avanza.se/_mobile/account/1234/transactions?offset=0").unwrap();
+        assert_eq!(
+            endpoint_label(&Method::GET, &url),
+            "GET /_mobile/account/{id}/transactions"
+        );
+
+        let url = Url::parse("https://www.avanza.se/_mobile/account/positions").unwrap();
+        assert_eq!(
+            endpoint_label(&Method::GET, &url),
+            "GET /_mobile/account/positions"
+        );
+    }
+
+    #[test]
+    fn histogram_buckets_by_upper_bound() {
+        let mut histogram = Histogram::default();
+        histogram.observe(Duration::from_millis(5));
+        histogram.observe(Duration::from_millis(6));
+        histogram.observe(Duration::from_secs(60));
+
+        assert_eq!(histogram.buckets[0], 1);
+        assert_eq!(histogram.buckets[1], 1);
+        assert_eq!(histogram.buckets[LATENCY_BUCKETS.len()], 1);
+        assert_eq!(histogram.count, 3);
+        assert_eq!(histogram.sum, Duration::from_millis(60011));
+    }
+}
@@ -0,0 +1,68 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::order::new_order::NewOrder;
+use crate::time::Instant;
+use crate::types::{AccountId, OrderbookId, Side};
+
+/// What makes two orders the same. The price is compared bit for bit, which is what a
+/// program placing the same order again would send.
+#[derive(Debug, Clone, PartialEq)]
+struct OrderKey {
+    account_id: AccountId,
+    orderbook_id: OrderbookId,
+    side: Side,
+    price: Option<u64>,
+    volume: u64,
+}
+
+impl From<&NewOrder> for OrderKey {
+    fn from(order: &NewOrder) -> Self {
+        OrderKey {
+            account_id: order.account_id().clone(),
+            orderbook_id: order.orderbook_id().clone(),
+            side: order.side(),
+            price: order.price().map(|price| price.value().to_bits()),
+            volume: order.volume().value(),
+        }
+    }
+}
+
+/// Orders placed within the last `window`, see
+/// [`ClientBuilder::order_dedupe_window`](crate::client::ClientBuilder::order_dedupe_window).
+pub(crate) struct OrderLedger {
+    window: Duration,
+    placed: Mutex<Vec<(OrderKey, Instant)>>,
+}
+
+impl OrderLedger {
+    pub(crate) fn new(window: Duration) -> Self {
+        Self {
+            window,
+            placed: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Records `order` as placed now, unless the same order already was within the window.
+    /// Checking and recording under one lock makes concurrent duplicates lose too.
+    pub(crate) fn try_record(&self, order: &NewOrder) -> bool {
+        let key = OrderKey::from(order);
+        let now = Instant::now();
+        let mut placed = self.placed.lock().unwrap();
+        placed.retain(|(_, at)| now.saturating_duration_since(*at) < self.window);
+        if placed.iter().any(|(placed, _)| *placed == key) {
+            return false;
+        }
+        placed.push((key, now));
+        true
+    }
+
+    /// Forgets `order` again, for orders Avanza turned down.
+    pub(crate) fn forget(&self, order: &NewOrder) {
+        let key = OrderKey::from(order);
+        let mut placed = self.placed.lock().unwrap();
+        if let Some(index) = placed.iter().rposition(|(placed, _)| *placed == key) {
+            placed.remove(index);
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::order::place::{OrderRequestStatus, OrderResponse};
+use crate::types::AccountId;
+
+impl Client {
+    /// Deletes an open order. Deleting twice leaves the order just as deleted, so the
+    /// request is retried like a read. A 204 without a body counts as deleted.
+    pub async fn delete_order(
+        &self,
+        account_id: &AccountId,
+        order_id: &str,
+    ) -> Result<OrderResponse, RequestError> {
+        let response = self
+            .fetch_spec::<Option<OrderResponse>>(
+                &endpoints::delete_order(account_id, order_id),
+                Idempotency::Idempotent,
+            )
+            .await?;
+        Ok(response.unwrap_or_else(|| OrderResponse {
+            order_request_status: OrderRequestStatus::Success,
+            message: String::new(),
+            order_id: Some(String::from(order_id)),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::client::RetryPolicy;
+
+    use super::*;
+    use crate::test_support::{authenticated_client, authenticated_client_with, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.delete_order(&AccountId::from("1234"), "o-1").await);
+    }
+
+    #[tokio::test]
+    async fn is_retried() {
+        let mock_server = MockServer::start().await;
+
+        let responder = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{"orderRequestStatus": "SUCCESS", "message": "", "orderId": "o-1"}"#,
+        ));
+
+        Mock::given(method("DELETE"))
+            .and(path("/_mobile/order/1234/o-1"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/_mobile/order/1234/o-1"))
+            .respond_with(responder)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client_with(
+            &mock_server,
+            Client::builder().retry_policy(RetryPolicy::new(3, Duration::from_millis(1))),
+        )
+        .await;
+
+        let response = assert_ok!(client.delete_order(&AccountId::from("1234"), "o-1").await);
+        assert!(response.is_success());
+    }
+
+    #[tokio::test]
+    async fn no_content_is_deleted() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("DELETE"))
+            .and(path("/_mobile/order/1234/o-1"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let response = assert_ok!(client.delete_order(&AccountId::from("1234"), "o-1").await);
+        assert!(response.is_success());
+        assert_eq!(response.order_id, Some(String::from("o-1")));
+    }
+}
@@ -0,0 +1,5 @@
+pub(crate) mod dedupe;
+pub mod delete;
+pub mod new_order;
+pub mod place;
+pub mod precheck;
@@ -0,0 +1,590 @@
+use chrono::{Days, NaiveDate};
+use serde::{Serialize, Serializer};
+
+use crate::error::{InvalidOrder, OrderValidationError};
+use crate::stockholm;
+use crate::types::{AccountId, OrderbookId, Side};
+
+/// A limit price, always positive and finite.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize)]
+#[serde(transparent)]
+pub struct Price(f64);
+
+impl Price {
+    pub fn new(value: f64) -> Result<Self, InvalidOrder> {
+        if value.is_finite() && value > 0.0 {
+            Ok(Price(value))
+        } else {
+            Err(InvalidOrder::NonPositivePrice(value))
+        }
+    }
+
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+}
+
+/// A number of shares, always at least one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(transparent)]
+pub struct Volume(u64);
+
+impl Volume {
+    pub fn new(value: u64) -> Result<Self, InvalidOrder> {
+        if value > 0 {
+            Ok(Volume(value))
+        } else {
+            Err(InvalidOrder::ZeroVolume())
+        }
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+/// How far ahead Avanza accepts `valid_until`, in calendar days from today.
+pub const MAX_VALIDITY_DAYS: u64 = 30;
+
+/// How long an order stays in the orderbook.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum OrderValidity {
+    /// Until the market closes today.
+    #[default]
+    Today,
+    /// Until the market closes on the date, at most [`MAX_VALIDITY_DAYS`] ahead.
+    UntilDate(NaiveDate),
+}
+
+impl OrderValidity {
+    fn date(&self, today: NaiveDate) -> NaiveDate {
+        match self {
+            OrderValidity::Today => today,
+            OrderValidity::UntilDate(date) => *date,
+        }
+    }
+}
+
+/// What happens to the part of an order that can't be filled right away.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrderCondition {
+    /// Stays in the orderbook until filled or `valid_until` has passed.
+    #[default]
+    Normal,
+    /// Fills what it can at once and cancels the rest.
+    FillAndKill,
+    /// Fills completely at once or not at all.
+    FillOrKill,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrderType {
+    Limit,
+    /// Trades at the prices in the orderbook, like "köp/sälj till marknadspris" in the app.
+    Market,
+}
+
+/// An order to place with `Client::place_order`. Built with [`NewOrder::buy`] or
+/// [`NewOrder::sell`], so every `NewOrder` has passed validation.
+///
+/// ```
+/// use avanza::order::new_order::NewOrder;
+/// use avanza::types::{AccountId, OrderbookId};
+///
+/// let order = NewOrder::buy(OrderbookId::from("5269"))
+///     .account(AccountId::from("1234"))
+///     .limit_price(187.45)
+///     .volume(10)
+///     .build()
+///     .unwrap();
+/// assert_eq!(order.volume().value(), 10);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewOrder {
+    account_id: AccountId,
+    orderbook_id: OrderbookId,
+    side: Side,
+    order_type: OrderType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    price: Option<Price>,
+    volume: Volume,
+    #[serde(serialize_with = "serialize_date")]
+    valid_until: NaiveDate,
+    condition: OrderCondition,
+    #[serde(skip)]
+    allow_duplicate: bool,
+}
+
+impl NewOrder {
+    pub fn buy(orderbook_id: OrderbookId) -> NewOrderBuilder {
+        NewOrderBuilder::new(Side::Buy, orderbook_id)
+    }
+
+    pub fn sell(orderbook_id: OrderbookId) -> NewOrderBuilder {
+        NewOrderBuilder::new(Side::Sell, orderbook_id)
+    }
+
+    pub fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    pub fn orderbook_id(&self) -> &OrderbookId {
+        &self.orderbook_id
+    }
+
+    pub fn side(&self) -> Side {
+        self.side
+    }
+
+    pub fn order_type(&self) -> OrderType {
+        self.order_type
+    }
+
+    /// The limit price, `None` for market orders.
+    pub fn price(&self) -> Option<Price> {
+        self.price
+    }
+
+    pub fn volume(&self) -> Volume {
+        self.volume
+    }
+
+    pub fn valid_until(&self) -> NaiveDate {
+        self.valid_until
+    }
+
+    pub fn condition(&self) -> OrderCondition {
+        self.condition
+    }
+
+    /// Exempts the order from `ClientBuilder::order_dedupe_window`.
+    pub fn allow_duplicate(mut self) -> Self {
+        self.allow_duplicate = true;
+        self
+    }
+
+    pub fn is_duplicate_allowed(&self) -> bool {
+        self.allow_duplicate
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NewOrderBuilder {
+    side: Side,
+    orderbook_id: OrderbookId,
+    account_id: Option<AccountId>,
+    price: Option<f64>,
+    market: bool,
+    volume: Option<u64>,
+    validity: OrderValidity,
+    condition: Option<OrderCondition>,
+}
+
+impl NewOrderBuilder {
+    fn new(side: Side, orderbook_id: OrderbookId) -> Self {
+        Self {
+            side,
+            orderbook_id,
+            account_id: None,
+            price: None,
+            market: false,
+            volume: None,
+            validity: OrderValidity::Today,
+            condition: None,
+        }
+    }
+
+    pub fn account(mut self, account_id: AccountId) -> Self {
+        self.account_id = Some(account_id);
+        self
+    }
+
+    pub fn limit_price(mut self, price: f64) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    /// Trades at whatever the orderbook offers instead of at a limit price. Avanza only
+    /// takes such orders for immediate execution, so the condition defaults to
+    /// [`OrderCondition::FillAndKill`] and the order is only valid today.
+    pub fn market(mut self) -> Self {
+        self.market = true;
+        self
+    }
+
+    /// Defaults to [`OrderCondition::Normal`] for limit orders. The kill conditions only
+    /// apply to today.
+    pub fn condition(mut self, condition: OrderCondition) -> Self {
+        self.condition = Some(condition);
+        self
+    }
+
+    pub fn volume(mut self, volume: u64) -> Self {
+        self.volume = Some(volume);
+        self
+    }
+
+    /// Defaults to [`OrderValidity::Today`].
+    pub fn validity(mut self, validity: OrderValidity) -> Self {
+        self.validity = validity;
+        self
+    }
+
+    /// Same as `validity(OrderValidity::UntilDate(date))`.
+    pub fn valid_until(self, date: NaiveDate) -> Self {
+        self.validity(OrderValidity::UntilDate(date))
+    }
+
+    /// The order, or every reason it is invalid.
+    pub fn build(self) -> Result<NewOrder, OrderValidationError> {
+        self.build_on(stockholm::today())
+    }
+
+    pub(crate) fn build_on(self, today: NaiveDate) -> Result<NewOrder, OrderValidationError> {
+        let mut violations = Vec::new();
+        let account_id = self.account_id;
+        if account_id.is_none() {
+            violations.push(InvalidOrder::MissingAccount());
+        }
+        let price = match (self.market, self.price.map(Price::new)) {
+            (true, Some(_)) => {
+                violations.push(InvalidOrder::PriceOnMarketOrder());
+                None
+            }
+            (true, None) => None,
+            (false, Some(Ok(price))) => Some(price),
+            (false, Some(Err(e))) => {
+                violations.push(e);
+                None
+            }
+            (false, None) => {
+                violations.push(InvalidOrder::MissingPrice());
+                None
+            }
+        };
+        let volume = match self.volume.map(Volume::new) {
+            Some(Ok(volume)) => Some(volume),
+            Some(Err(e)) => {
+                violations.push(e);
+                None
+            }
+            None => {
+                violations.push(InvalidOrder::MissingVolume());
+                None
+            }
+        };
+        let valid_until = self.validity.date(today);
+        if valid_until < today {
+            violations.push(InvalidOrder::ValidUntilInPast(valid_until));
+        }
+        if valid_until > today + Days::new(MAX_VALIDITY_DAYS) {
+            violations.push(InvalidOrder::ValidUntilTooFarAhead(valid_until));
+        }
+        let condition = self.condition.unwrap_or(if self.market {
+            OrderCondition::FillAndKill
+        } else {
+            OrderCondition::Normal
+        });
+        if self.market && condition == OrderCondition::Normal {
+            violations.push(InvalidOrder::NormalMarketOrder());
+        }
+        if condition != OrderCondition::Normal && valid_until > today {
+            violations.push(InvalidOrder::ValidBeyondToday(condition));
+        }
+
+        match (account_id, volume) {
+            (Some(account_id), Some(volume)) if violations.is_empty() => Ok(NewOrder {
+                account_id,
+                orderbook_id: self.orderbook_id,
+                side: self.side,
+                order_type: if self.market {
+                    OrderType::Market
+                } else {
+                    OrderType::Limit
+                },
+                price,
+                volume,
+                valid_until,
+                condition,
+                allow_duplicate: false,
+            }),
+            _ => Err(OrderValidationError { violations }),
+        }
+    }
+}
+
+// The API wants a plain `2026-10-16`, whatever chrono's default format may become.
+fn serialize_date<S: Serializer>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&date.format("%Y-%m-%d"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 15).unwrap()
+    }
+
+    fn complete() -> NewOrderBuilder {
+        NewOrder::buy(OrderbookId::from("5269"))
+            .account(AccountId::from("1234"))
+            .limit_price(187.45)
+            .volume(10)
+    }
+
+    fn violations(builder: NewOrderBuilder) -> Vec<InvalidOrder> {
+        builder.build_on(today()).unwrap_err().violations
+    }
+
+    #[test]
+    fn builds_valid_order() {
+        let order = complete()
+            .valid_until(NaiveDate::from_ymd_opt(2026, 10, 20).unwrap())
+            .build_on(today())
+            .unwrap();
+
+        assert_eq!(order.side(), Side::Buy);
+        assert_eq!(order.account_id(), &AccountId::from("1234"));
+        assert_eq!(order.orderbook_id(), &OrderbookId::from("5269"));
+        assert_eq!(order.order_type(), OrderType::Limit);
+        assert_eq!(order.price().map(|price| price.value()), Some(187.45));
+        assert_eq!(order.condition(), OrderCondition::Normal);
+        assert_eq!(order.volume().value(), 10);
+        assert_eq!(
+            order.valid_until(),
+            NaiveDate::from_ymd_opt(2026, 10, 20).unwrap()
+        );
+        assert!(!order.is_duplicate_allowed());
+    }
+
+    #[test]
+    fn sell_builds_a_sell_order() {
+        let order = NewOrder::sell(OrderbookId::from("5269"))
+            .account(AccountId::from("1234"))
+            .limit_price(187.45)
+            .volume(10)
+            .build_on(today())
+            .unwrap();
+        assert_eq!(order.side(), Side::Sell);
+    }
+
+    #[test]
+    fn valid_until_defaults_to_today() {
+        let order = complete().build_on(today()).unwrap();
+        assert_eq!(order.valid_until(), today());
+    }
+
+    #[test]
+    fn validity_today_is_today() {
+        let order = complete()
+            .validity(OrderValidity::Today)
+            .build_on(today())
+            .unwrap();
+        assert_eq!(order.valid_until(), today());
+    }
+
+    #[test]
+    fn accepts_a_future_weekday() {
+        // A Tuesday.
+        let date = NaiveDate::from_ymd_opt(2026, 10, 20).unwrap();
+        let order = complete()
+            .validity(OrderValidity::UntilDate(date))
+            .build_on(today())
+            .unwrap();
+        assert_eq!(order.valid_until(), date);
+        assert_eq!(
+            serde_json::to_value(&order).unwrap()["validUntil"],
+            "2026-10-20"
+        );
+    }
+
+    #[test]
+    fn rejects_valid_until_beyond_the_horizon() {
+        let last = today() + Days::new(MAX_VALIDITY_DAYS);
+        assert!(complete().valid_until(last).build_on(today()).is_ok());
+
+        let too_far = last + Days::new(1);
+        assert_eq!(
+            violations(complete().valid_until(too_far)),
+            vec![InvalidOrder::ValidUntilTooFarAhead(too_far)]
+        );
+    }
+
+    #[test]
+    fn serializes_like_the_api_expects() {
+        let order = complete().build_on(today()).unwrap().allow_duplicate();
+        assert_eq!(
+            serde_json::to_value(&order).unwrap(),
+            serde_json::json!({
+                "accountId": "1234",
+                "orderbookId": "5269",
+                "side": "BUY",
+                "orderType": "LIMIT",
+                "price": 187.45,
+                "volume": 10,
+                "validUntil": "2026-10-15",
+                "condition": "NORMAL"
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_missing_fields() {
+        assert_eq!(
+            violations(NewOrder::buy(OrderbookId::from("5269"))),
+            vec![
+                InvalidOrder::MissingAccount(),
+                InvalidOrder::MissingPrice(),
+                InvalidOrder::MissingVolume(),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_non_positive_prices() {
+        for price in [0.0, -0.05, -187.45, f64::NAN, f64::INFINITY] {
+            let found = violations(complete().limit_price(price));
+            assert_eq!(found.len(), 1, "{}", price);
+            assert!(
+                matches!(found[0], InvalidOrder::NonPositivePrice(p) if p.to_bits() == price.to_bits()),
+                "{}",
+                price
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_zero_volume() {
+        assert_eq!(
+            violations(complete().volume(0)),
+            vec![InvalidOrder::ZeroVolume()]
+        );
+    }
+
+    #[test]
+    fn rejects_valid_until_in_the_past() {
+        let yesterday = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+        assert_eq!(
+            violations(complete().valid_until(yesterday)),
+            vec![InvalidOrder::ValidUntilInPast(yesterday)]
+        );
+    }
+
+    #[test]
+    fn reports_every_violation() {
+        let yesterday = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+        let builder = NewOrder::sell(OrderbookId::from("5269"))
+            .limit_price(-1.0)
+            .volume(0)
+            .valid_until(yesterday);
+
+        assert_eq!(
+            violations(builder),
+            vec![
+                InvalidOrder::MissingAccount(),
+                InvalidOrder::NonPositivePrice(-1.0),
+                InvalidOrder::ZeroVolume(),
+                InvalidOrder::ValidUntilInPast(yesterday),
+            ]
+        );
+    }
+
+    #[test]
+    fn wrappers_validate_on_their_own() {
+        assert_eq!(Price::new(0.05).map(|p| p.value()), Ok(0.05));
+        assert_eq!(Price::new(0.0), Err(InvalidOrder::NonPositivePrice(0.0)));
+        assert_eq!(Volume::new(1).map(|v| v.value()), Ok(1));
+        assert_eq!(Volume::new(0), Err(InvalidOrder::ZeroVolume()));
+    }
+
+    fn body(builder: NewOrderBuilder) -> serde_json::Value {
+        serde_json::to_value(builder.build_on(today()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn serializes_each_condition() {
+        for (condition, serialized) in [
+            (OrderCondition::Normal, "NORMAL"),
+            (OrderCondition::FillAndKill, "FILL_AND_KILL"),
+            (OrderCondition::FillOrKill, "FILL_OR_KILL"),
+        ] {
+            let body = body(complete().condition(condition));
+            assert_eq!(body["condition"], serialized);
+            assert_eq!(body["orderType"], "LIMIT");
+            assert_eq!(body["price"], 187.45);
+        }
+    }
+
+    #[test]
+    fn market_order_has_no_price_and_kills_the_rest() {
+        let builder = NewOrder::sell(OrderbookId::from("5269"))
+            .account(AccountId::from("1234"))
+            .market()
+            .volume(10);
+        let order = builder.clone().build_on(today()).unwrap();
+        assert_eq!(order.order_type(), OrderType::Market);
+        assert_eq!(order.price(), None);
+        assert_eq!(order.condition(), OrderCondition::FillAndKill);
+
+        assert_eq!(
+            body(builder.clone()),
+            serde_json::json!({
+                "accountId": "1234",
+                "orderbookId": "5269",
+                "side": "SELL",
+                "orderType": "MARKET",
+                "volume": 10,
+                "validUntil": "2026-10-15",
+                "condition": "FILL_AND_KILL"
+            })
+        );
+        assert_eq!(
+            body(builder.condition(OrderCondition::FillOrKill))["condition"],
+            "FILL_OR_KILL"
+        );
+    }
+
+    #[test]
+    fn rejects_price_on_market_order() {
+        assert_eq!(
+            violations(complete().market()),
+            vec![InvalidOrder::PriceOnMarketOrder()]
+        );
+    }
+
+    #[test]
+    fn rejects_normal_market_order() {
+        let builder = NewOrder::buy(OrderbookId::from("5269"))
+            .account(AccountId::from("1234"))
+            .market()
+            .volume(10)
+            .condition(OrderCondition::Normal);
+        assert_eq!(violations(builder), vec![InvalidOrder::NormalMarketOrder()]);
+    }
+
+    #[test]
+    fn rejects_kill_conditions_beyond_today() {
+        let tomorrow = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        for condition in [OrderCondition::FillAndKill, OrderCondition::FillOrKill] {
+            assert_eq!(
+                violations(complete().condition(condition).valid_until(tomorrow)),
+                vec![InvalidOrder::ValidBeyondToday(condition)]
+            );
+        }
+        let market = NewOrder::buy(OrderbookId::from("5269"))
+            .account(AccountId::from("1234"))
+            .market()
+            .volume(10)
+            .valid_until(tomorrow);
+        assert_eq!(
+            violations(market),
+            vec![InvalidOrder::ValidBeyondToday(OrderCondition::FillAndKill)]
+        );
+        // Normal orders can rest for longer.
+        assert!(complete().valid_until(tomorrow).build_on(today()).is_ok());
+    }
+}
@@ -0,0 +1,301 @@
+use crate::client::{CallOptions, Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::order::new_order::NewOrder;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrderRequestStatus {
+    Success,
+    Error,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderResponse {
+    pub order_request_status: OrderRequestStatus,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub order_id: Option<String>,
+}
+
+impl OrderResponse {
+    pub fn is_success(&self) -> bool {
+        self.order_request_status == OrderRequestStatus::Success
+    }
+}
+
+impl Client {
+    /// Places a limit order. It is never retried, since a request that timed out may still
+    /// have reached Avanza; see [`CallOptions::force_retryable`] to opt in anyway. A retry
+    /// policy or timeout in `options` applies to this call only.
+    ///
+    /// With a dedupe window set, repeating an order within it fails with
+    /// `RequestError::DuplicateOrder` without sending anything. An order counts as placed
+    /// unless Avanza answers that it was not, so one that timed out blocks its duplicates
+    /// too.
+    pub async fn place_order(
+        &self,
+        order: &NewOrder,
+        options: CallOptions,
+    ) -> Result<OrderResponse, RequestError> {
+        if !self.is_authenticated() {
+            return Err(RequestError::NotAuthenticatedError());
+        }
+        let ledger = self
+            .order_ledger()
+            .filter(|_| !order.is_duplicate_allowed());
+        if let Some(ledger) = ledger {
+            if !ledger.try_record(order) {
+                return Err(RequestError::DuplicateOrder());
+            }
+        }
+        let response = self
+            .with_options(options)
+            .fetch_spec::<OrderResponse>(&endpoints::place_order(order), Idempotency::NonIdempotent)
+            .await;
+        if let (Some(ledger), Ok(response)) = (ledger, &response) {
+            if response.order_request_status == OrderRequestStatus::Error {
+                ledger.forget(order);
+            }
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::client::RetryPolicy;
+
+    use super::*;
+    use crate::test_support::{authenticated_client_with, config};
+    use crate::types::{AccountId, OrderbookId};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn order() -> NewOrder {
+        order_of(10)
+    }
+
+    fn order_of(volume: u64) -> NewOrder {
+        NewOrder::buy(OrderbookId::from("5361"))
+            .account(AccountId::from("1234"))
+            .limit_price(187.5)
+            .volume(volume)
+            .build()
+            .unwrap()
+    }
+
+    async fn retrying_client(mock_server: &MockServer) -> Client {
+        let builder = Client::builder().retry_policy(RetryPolicy::new(3, Duration::from_millis(1)));
+        authenticated_client_with(mock_server, builder).await
+    }
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.place_order(&order(), CallOptions::new()).await);
+    }
+
+    #[tokio::test]
+    async fn can_place_order() {
+        let mock_server = MockServer::start().await;
+
+        let responder = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{"orderRequestStatus": "SUCCESS", "message": "", "orderId": "o-1"}"#,
+        ));
+
+        Mock::given(method("POST"))
+            .and(path("/_mobile/order"))
+            .and(body_json(serde_json::json!({
+                "accountId": "1234",
+                "orderbookId": "5361",
+                "side": "BUY",
+                "orderType": "LIMIT",
+                "price": 187.5,
+                "volume": 10,
+                "validUntil": order().valid_until(),
+                "condition": "NORMAL"
+            })))
+            .respond_with(responder)
+            .mount(&mock_server)
+            .await;
+
+        let client = retrying_client(&mock_server).await;
+
+        let response = assert_ok!(client.place_order(&order(), CallOptions::new()).await);
+        assert!(response.is_success());
+        assert_eq!(response.order_id, Some(String::from("o-1")));
+    }
+
+    #[tokio::test]
+    async fn is_not_retried() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/_mobile/order"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = retrying_client(&mock_server).await;
+
+        assert_err!(client.place_order(&order(), CallOptions::new()).await);
+    }
+
+    #[tokio::test]
+    async fn can_force_retries() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/_mobile/order"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(4)
+            .mount(&mock_server)
+            .await;
+
+        let client = retrying_client(&mock_server).await;
+
+        assert_err!(
+            client
+                .place_order(&order(), CallOptions::new().force_retryable())
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn retry_policy_option_does_not_make_it_retryable() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/_mobile/order"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = retrying_client(&mock_server).await;
+        let options =
+            CallOptions::new().retry_policy(RetryPolicy::new(5, Duration::from_millis(1)));
+
+        assert_err!(client.place_order(&order(), options).await);
+    }
+
+    async fn mount_order(mock_server: &MockServer, status: &str, expected_calls: u64) {
+        Mock::given(method("POST"))
+            .and(path("/_mobile/order"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                r#"{{"orderRequestStatus": "{}", "orderId": "o-1"}}"#,
+                status
+            )))
+            .expect(expected_calls)
+            .mount(mock_server)
+            .await;
+    }
+
+    async fn deduping_client(mock_server: &MockServer) -> Client {
+        let builder = Client::builder()
+            .retry_policy(RetryPolicy::new(3, Duration::from_millis(1)))
+            .order_dedupe_window(Duration::from_secs(60));
+        authenticated_client_with(mock_server, builder).await
+    }
+
+    #[tokio::test]
+    async fn rejects_duplicate_within_window() {
+        let mock_server = MockServer::start().await;
+        mount_order(&mock_server, "SUCCESS", 1).await;
+        let client = deduping_client(&mock_server).await;
+
+        assert_ok!(client.place_order(&order(), CallOptions::new()).await);
+        assert!(matches!(
+            client.place_order(&order(), CallOptions::new()).await,
+            Err(RequestError::DuplicateOrder())
+        ));
+        // Clones share the ledger.
+        assert!(matches!(
+            client
+                .clone()
+                .place_order(&order(), CallOptions::new())
+                .await,
+            Err(RequestError::DuplicateOrder())
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn allows_duplicate_after_window() {
+        let mock_server = MockServer::start().await;
+        mount_order(&mock_server, "SUCCESS", 2).await;
+        let client = deduping_client(&mock_server).await;
+
+        assert_ok!(client.place_order(&order(), CallOptions::new()).await);
+        tokio::time::advance(Duration::from_secs(60)).await;
+        assert_ok!(client.place_order(&order(), CallOptions::new()).await);
+    }
+
+    #[tokio::test]
+    async fn allows_different_volume() {
+        let mock_server = MockServer::start().await;
+        mount_order(&mock_server, "SUCCESS", 2).await;
+        let client = deduping_client(&mock_server).await;
+
+        assert_ok!(client.place_order(&order(), CallOptions::new()).await);
+        assert_ok!(client.place_order(&order_of(11), CallOptions::new()).await);
+    }
+
+    #[tokio::test]
+    async fn allow_duplicate_skips_the_check() {
+        let mock_server = MockServer::start().await;
+        mount_order(&mock_server, "SUCCESS", 2).await;
+        let client = deduping_client(&mock_server).await;
+
+        assert_ok!(client.place_order(&order(), CallOptions::new()).await);
+        assert_ok!(
+            client
+                .place_order(&order().allow_duplicate(), CallOptions::new())
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn rejected_order_can_be_placed_again() {
+        let mock_server = MockServer::start().await;
+        mount_order(&mock_server, "ERROR", 2).await;
+        let client = deduping_client(&mock_server).await;
+
+        let response = assert_ok!(client.place_order(&order(), CallOptions::new()).await);
+        assert!(!response.is_success());
+        assert_ok!(client.place_order(&order(), CallOptions::new()).await);
+    }
+
+    #[tokio::test]
+    async fn concurrent_duplicates_are_rejected() {
+        let mock_server = MockServer::start().await;
+        mount_order(&mock_server, "SUCCESS", 1).await;
+        let client = deduping_client(&mock_server).await;
+
+        let order = order();
+        let (first, second) = tokio::join!(
+            client.place_order(&order, CallOptions::new()),
+            client.place_order(&order, CallOptions::new())
+        );
+        assert_eq!(
+            [first, second]
+                .iter()
+                .filter(|result| matches!(result, Err(RequestError::DuplicateOrder())))
+                .count(),
+            1
+        );
+    }
+}
@@ -0,0 +1,416 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::client::Client;
+use crate::error::RequestError;
+use crate::market::orderbook::Orderbook;
+use crate::order::new_order::NewOrder;
+use crate::types::{Currency, Side};
+
+/// A reason Avanza would most likely reject the order. Problems with the order on its own,
+/// like a negative price, are caught when building the [`NewOrder`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation {
+    /// The price is not on the orderbook's tick; `below` and `above` are the nearest
+    /// prices that are.
+    NotOnTick {
+        tick_size: f64,
+        below: f64,
+        above: f64,
+    },
+    OutsidePriceLimits {
+        lowest: f64,
+        highest: f64,
+    },
+    /// Order value plus estimated courtage, in SEK, against the account's buying power.
+    InsufficientBuyingPower {
+        required: f64,
+        available: f64,
+    },
+    /// The orderbook's currency can't be converted to SEK to check the buying power.
+    NoExchangeRate(Currency),
+    MarketClosed,
+}
+
+/// Something that doesn't stop the order but likely isn't what was meant.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Warning {
+    /// No market is open that day, so Avanza moves the order's last day or rejects it.
+    ValidUntilWeekend(NaiveDate),
+}
+
+/// What [`Client::place_order_dry_run`] would have sent, and why it might fail.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderPrecheck {
+    pub order: NewOrder,
+    pub orderbook: Orderbook,
+    /// Price times volume, in the orderbook's currency. `None` for market orders.
+    pub order_value: Option<f64>,
+    /// In SEK, at the highest allowed price for market orders. `None` when the account has no
+    /// courtage info, e.g. it can't trade, or there is no price to estimate it at.
+    pub estimated_courtage: Option<f64>,
+    pub violations: Vec<Violation>,
+    pub warnings: Vec<Warning>,
+}
+
+impl OrderPrecheck {
+    /// Whether there are no violations. Warnings don't count.
+    pub fn is_ok(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl Client {
+    /// Checks `order` the way Avanza would, without placing it: the tick size, price limits
+    /// and trading status of the orderbook and, for buys, the account's buying power. Only
+    /// reads are sent, so nothing can reach the market. Market orders have no price of their
+    /// own, so a market buy must afford the orderbook's highest allowed price, and isn't
+    /// checked against the buying power if the orderbook has none.
+    pub async fn place_order_dry_run(
+        &self,
+        order: &NewOrder,
+    ) -> Result<OrderPrecheck, RequestError> {
+        let orderbook = self.fetch_orderbook(order.orderbook_id()).await?;
+        let courtage = self.get_courtage_info(order.account_id()).await?;
+
+        let mut warnings = Vec::new();
+        if matches!(order.valid_until().weekday(), Weekday::Sat | Weekday::Sun) {
+            warnings.push(Warning::ValidUntilWeekend(order.valid_until()));
+        }
+        let mut violations = Vec::new();
+        if orderbook.tradable == Some(false) {
+            violations.push(Violation::MarketClosed);
+        }
+        let price = order.price().map(|price| price.value());
+        if let Some(price) = price {
+            let tick_sizes = orderbook.tick_sizes();
+            if !tick_sizes.is_valid_price(price) {
+                violations.push(Violation::NotOnTick {
+                    tick_size: tick_sizes.tick_at(price),
+                    below: tick_sizes.round_down(price),
+                    above: tick_sizes.round_up(price),
+                });
+            }
+            if let (Some(lowest), Some(highest)) = (
+                orderbook.lowest_allowed_price,
+                orderbook.highest_allowed_price,
+            ) {
+                if price < lowest || price > highest {
+                    violations.push(Violation::OutsidePriceLimits { lowest, highest });
+                }
+            }
+        }
+        let volume = order.volume().value() as f64;
+        let order_value = price.map(|price| price * volume);
+        // A market order can fill as high as the orderbook allows.
+        let Some(highest_value) = order_value.or(orderbook
+            .highest_allowed_price
+            .map(|highest| highest * volume))
+        else {
+            return Ok(OrderPrecheck {
+                order: order.clone(),
+                orderbook,
+                order_value: None,
+                estimated_courtage: None,
+                violations,
+                warnings,
+            });
+        };
+        let value_in_sek = self.in_sek(highest_value, &orderbook.currency).await;
+        let estimated_courtage = courtage
+            .zip(value_in_sek)
+            .map(|(courtage, value)| courtage.estimate_fee(value));
+
+        if order.side() == Side::Buy {
+            match value_in_sek {
+                Some(value) => {
+                    let required = value + estimated_courtage.unwrap_or_default();
+                    let available = self
+                        .get_cash_balance(order.account_id())
+                        .await?
+                        .buying_power;
+                    if required > available {
+                        violations.push(Violation::InsufficientBuyingPower {
+                            required,
+                            available,
+                        });
+                    }
+                }
+                None => violations.push(Violation::NoExchangeRate(orderbook.currency.clone())),
+            }
+        }
+
+        Ok(OrderPrecheck {
+            order: order.clone(),
+            orderbook,
+            order_value,
+            estimated_courtage,
+            violations,
+            warnings,
+        })
+    }
+
+    // `None` if there's no rate, or the rates couldn't be fetched: either way the buying
+    // power can't be checked, which is a violation rather than a failed precheck.
+    async fn in_sek(&self, value: f64, currency: &Currency) -> Option<f64> {
+        if *currency == Currency::SEK {
+            return Some(value);
+        }
+        self.currency_converter()
+            .await
+            .ok()?
+            .convert(value, currency, &Currency::SEK)
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config, mount_get};
+    use crate::types::{AccountId, OrderbookId};
+    use chrono::Days;
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const ORDERBOOK: &str = r#"{
+        "id": "5269",
+        "name": "Volvo B",
+        "instrumentType": "STOCK",
+        "currency": "SEK",
+        "tickSize": 0.05,
+        "tradable": true,
+        "lowestAllowedPrice": 150,
+        "highestAllowedPrice": 250
+    }"#;
+
+    const COURTAGE: &str = r#"{"courtageClass": "MINI", "minimumFee": 1, "percentageFee": 0.25}"#;
+
+    fn order(price: f64, volume: u64) -> NewOrder {
+        NewOrder::buy(OrderbookId::from("5269"))
+            .account(AccountId::from("1234"))
+            .limit_price(price)
+            .volume(volume)
+            .build()
+            .unwrap()
+    }
+
+    fn cash(buying_power: f64) -> String {
+        format!(
+            r#"{{"availableCash": {0}, "buyingPower": {0}}}"#,
+            buying_power
+        )
+    }
+
+    async fn client(mock_server: &MockServer, orderbook: &str, buying_power: f64) -> Client {
+        mount_get(mock_server, "/_mobile/market/orderbook/5269", orderbook).await;
+        mount_get(mock_server, "/_mobile/account/1234/courtage", COURTAGE).await;
+        mount_get(
+            mock_server,
+            "/_mobile/account/1234/cash",
+            &cash(buying_power),
+        )
+        .await;
+        // A dry run must never place anything.
+        Mock::given(method("POST"))
+            .and(path("/_mobile/order"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(mock_server)
+            .await;
+
+        authenticated_client(mock_server).await
+    }
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.place_order_dry_run(&order(187.45, 10)).await);
+    }
+
+    #[tokio::test]
+    async fn passing_precheck() {
+        let mock_server = MockServer::start().await;
+        let client = client(&mock_server, ORDERBOOK, 10_000.0).await;
+
+        let precheck = assert_ok!(client.place_order_dry_run(&order(187.45, 10)).await);
+        assert!(precheck.is_ok(), "{:?}", precheck.violations);
+        assert_eq!(precheck.order, order(187.45, 10));
+        assert_eq!(precheck.orderbook.name, "Volvo B");
+        assert!((precheck.order_value.unwrap() - 1874.5).abs() < 1e-9);
+        // 0.25 % of 1874.50 is more than the minimum fee.
+        let courtage = precheck.estimated_courtage.unwrap();
+        assert!((courtage - 4.68625).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn fails_on_tick_size() {
+        let mock_server = MockServer::start().await;
+        let client = client(&mock_server, ORDERBOOK, 10_000.0).await;
+
+        let precheck = assert_ok!(client.place_order_dry_run(&order(187.42, 10)).await);
+        assert_eq!(
+            precheck.violations,
+            vec![Violation::NotOnTick {
+                tick_size: 0.05,
+                below: 187.4,
+                above: 187.45
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn checks_tick_size_table() {
+        let mock_server = MockServer::start().await;
+        let banded = ORDERBOOK.replace(
+            r#""tickSize": 0.05,"#,
+            r#""tickSizeTable": [{"min": 0, "tick": 0.01}, {"min": 100, "tick": 0.1}],"#,
+        );
+        let client = client(&mock_server, &banded, 10_000.0).await;
+
+        let precheck = assert_ok!(client.place_order_dry_run(&order(187.45, 10)).await);
+        assert_eq!(
+            precheck.violations,
+            vec![Violation::NotOnTick {
+                tick_size: 0.1,
+                below: 187.4,
+                above: 187.5
+            }]
+        );
+        let precheck = assert_ok!(client.place_order_dry_run(&order(187.5, 10)).await);
+        assert!(precheck.is_ok(), "{:?}", precheck.violations);
+    }
+
+    #[tokio::test]
+    async fn fails_on_buying_power() {
+        let mock_server = MockServer::start().await;
+        let client = client(&mock_server, ORDERBOOK, 1875.0).await;
+
+        // Enough for the shares, not for the courtage on top.
+        let precheck = assert_ok!(client.place_order_dry_run(&order(187.45, 10)).await);
+        assert!(matches!(
+            precheck.violations.as_slice(),
+            [Violation::InsufficientBuyingPower { available, .. }] if *available == 1875.0
+        ));
+    }
+
+    #[tokio::test]
+    async fn fails_when_market_is_closed() {
+        let mock_server = MockServer::start().await;
+        let closed = ORDERBOOK.replace(r#""tradable": true"#, r#""tradable": false"#);
+        let client = client(&mock_server, &closed, 10_000.0).await;
+
+        let precheck = assert_ok!(client.place_order_dry_run(&order(187.45, 10)).await);
+        assert_eq!(precheck.violations, vec![Violation::MarketClosed]);
+    }
+
+    fn market_buy(volume: u64) -> NewOrder {
+        NewOrder::buy(OrderbookId::from("5269"))
+            .account(AccountId::from("1234"))
+            .market()
+            .volume(volume)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn market_buy_must_afford_the_highest_allowed_price() {
+        let mock_server = MockServer::start().await;
+        let closed = ORDERBOOK.replace(r#""tradable": true"#, r#""tradable": false"#);
+        let client = client(&mock_server, &closed, 2000.0).await;
+
+        let precheck = assert_ok!(client.place_order_dry_run(&market_buy(10)).await);
+        assert!(matches!(
+            precheck.violations.as_slice(),
+            [
+                Violation::MarketClosed,
+                Violation::InsufficientBuyingPower { required, available }
+            ] if (*required - 2506.25).abs() < 1e-9 && *available == 2000.0
+        ));
+        assert_eq!(precheck.order_value, None);
+
+        let precheck = assert_ok!(client.place_order_dry_run(&market_buy(5)).await);
+        assert_eq!(precheck.violations, vec![Violation::MarketClosed]);
+    }
+
+    #[tokio::test]
+    async fn market_buy_without_price_limits_skips_buying_power() {
+        let mock_server = MockServer::start().await;
+        let unlimited = ORDERBOOK.replace(
+            r#""lowestAllowedPrice": 150,
+        "highestAllowedPrice": 250"#,
+            r#""lowestAllowedPrice": null"#,
+        );
+        let client = client(&mock_server, &unlimited, 0.0).await;
+
+        let precheck = assert_ok!(client.place_order_dry_run(&market_buy(10)).await);
+        assert!(precheck.is_ok(), "{:?}", precheck.violations);
+        assert_eq!(precheck.estimated_courtage, None);
+    }
+
+    #[tokio::test]
+    async fn missing_exchange_rates_are_a_violation() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/market/exchange-rates"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&mock_server)
+            .await;
+        let in_usd = ORDERBOOK.replace(r#""currency": "SEK""#, r#""currency": "USD""#);
+        let client = client(&mock_server, &in_usd, 10_000.0).await;
+
+        let precheck = assert_ok!(client.place_order_dry_run(&order(187.45, 10)).await);
+        assert_eq!(
+            precheck.violations,
+            vec![Violation::NoExchangeRate(Currency::from("USD"))]
+        );
+        assert_eq!(precheck.estimated_courtage, None);
+    }
+
+    #[tokio::test]
+    async fn warns_when_valid_until_is_a_weekend() {
+        let mock_server = MockServer::start().await;
+        let client = client(&mock_server, ORDERBOOK, 10_000.0).await;
+
+        let today = crate::stockholm::today();
+        let saturday =
+            today + Days::new(u64::from(5 + 7 - today.weekday().num_days_from_monday()) % 7);
+        let sunday = saturday + Days::new(1);
+        let monday = sunday + Days::new(1);
+        for (date, warnings) in [
+            (saturday, vec![Warning::ValidUntilWeekend(saturday)]),
+            (sunday, vec![Warning::ValidUntilWeekend(sunday)]),
+            (monday, vec![]),
+        ] {
+            let order = NewOrder::buy(OrderbookId::from("5269"))
+                .account(AccountId::from("1234"))
+                .limit_price(187.45)
+                .volume(10)
+                .valid_until(date)
+                .build()
+                .unwrap();
+            let precheck = assert_ok!(client.place_order_dry_run(&order).await);
+            assert_eq!(precheck.warnings, warnings, "{}", date);
+            assert!(precheck.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn fails_outside_price_limits() {
+        let mock_server = MockServer::start().await;
+        let client = client(&mock_server, ORDERBOOK, 10_000.0).await;
+
+        let precheck = assert_ok!(client.place_order_dry_run(&order(260.0, 1)).await);
+        assert_eq!(
+            precheck.violations,
+            vec![Violation::OutsidePriceLimits {
+                lowest: 150.0,
+                highest: 250.0
+            }]
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use std::collections::VecDeque;
+use std::future::Future;
+
+use futures_util::{stream, Stream};
+
+use crate::error::RequestError;
+
+/// One page of a paginated endpoint.
+pub(crate) struct Page<T> {
+    pub items: Vec<T>,
+    pub has_more: bool,
+}
+
+struct State<F, T> {
+    fetch: F,
+    offset: usize,
+    buffer: VecDeque<T>,
+    done: bool,
+}
+
+/// Yields the items of every page in order, calling `fetch` with the offset of the next
+/// page only once the previous one is used up. The stream ends after a page without more
+/// results or after the first error.
+pub(crate) fn paginate<T, F, Fut>(fetch: F) -> impl Stream<Item = Result<T, RequestError>>
+where
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = Result<Page<T>, RequestError>>,
+{
+    let state = State {
+        fetch,
+        offset: 0,
+        buffer: VecDeque::new(),
+        done: false,
+    };
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(item) = state.buffer.pop_front() {
+                return Some((Ok(item), state));
+            }
+            if state.done {
+                return None;
+            }
+            match (state.fetch)(state.offset).await {
+                Ok(page) => {
+                    // An empty page would otherwise request the same offset forever.
+                    state.done = !page.has_more || page.items.is_empty();
+                    state.offset += page.items.len();
+                    state.buffer.extend(page.items);
+                }
+                Err(e) => {
+                    state.done = true;
+                    return Some((Err(e), state));
+                }
+            }
+        }
+    })
+}
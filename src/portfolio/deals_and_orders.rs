@@ -0,0 +1,122 @@
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::types::{AccountId, OrderbookId, Side};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrderState {
+    Accepted,
+    PartiallyFilled,
+    Filled,
+    Cancelled,
+    Rejected,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub order_id: String,
+    pub account_id: AccountId,
+    pub orderbook_id: OrderbookId,
+    pub side: Side,
+    pub price: f64,
+    pub volume: u64,
+    #[serde(default)]
+    pub filled_volume: u64,
+    pub state: OrderState,
+    #[serde(default)]
+    pub valid_until: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Deal {
+    pub deal_id: String,
+    #[serde(default)]
+    pub order_id: Option<String>,
+    pub account_id: AccountId,
+    pub orderbook_id: OrderbookId,
+    pub side: Side,
+    pub price: f64,
+    pub volume: u64,
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    pub deal_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DealsAndOrdersResponse {
+    #[serde(default)]
+    pub orders: Vec<Order>,
+    #[serde(default)]
+    pub deals: Vec<Deal>,
+}
+
+impl Client {
+    pub async fn get_deals_and_orders(&self) -> Result<DealsAndOrdersResponse, RequestError> {
+        let resp = self
+            .fetch_spec::<DealsAndOrdersResponse>(
+                &endpoints::deals_and_orders(),
+                Idempotency::Idempotent,
+            )
+            .await?;
+        Ok(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.get_deals_and_orders().await);
+    }
+
+    #[tokio::test]
+    async fn can_get_deals_and_orders() {
+        let mock_server = MockServer::start().await;
+
+        let responder = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{
+                "orders": [
+                    {"orderId": "o-1", "accountId": "1234", "orderbookId": "5269", "side": "BUY", "price": 250.0, "volume": 100, "filledVolume": 40, "state": "PARTIALLY_FILLED", "validUntil": "2026-10-14"}
+                ],
+                "deals": [
+                    {"dealId": "d-1", "orderId": "o-1", "accountId": "1234", "orderbookId": "5269", "side": "BUY", "price": 250.0, "volume": 40, "dealTime": 1791990000000}
+                ]
+            }"#,
+        ));
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/dealsandorders"))
+            .respond_with(responder)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let response = assert_ok!(client.get_deals_and_orders().await);
+
+        assert_eq!(response.orders.len(), 1);
+        assert_eq!(response.orders[0].state, OrderState::PartiallyFilled);
+        assert_eq!(response.orders[0].side, Side::Buy);
+        assert_eq!(response.orders[0].filled_volume, 40);
+        assert_eq!(response.deals.len(), 1);
+        assert_eq!(response.deals[0].order_id.as_deref(), Some("o-1"));
+    }
+}
@@ -0,0 +1,242 @@
+//! What changed between two [`PositionsResponse`]s, e.g. since yesterday's.
+
+use std::collections::BTreeMap;
+
+use crate::portfolio::positions::{Positions, PositionsResponse};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PositionChange {
+    /// Held now but not before.
+    Added(Positions),
+    /// Held before but not now, with its last known figures.
+    Removed(Positions),
+    /// Held both times with a different volume, value or profit.
+    Changed {
+        position: Positions,
+        volume_delta: i64,
+        value_delta: f64,
+        profit_delta: f64,
+        /// Whether the volume changed, i.e. there was a trade. Without one only the market
+        /// price moved.
+        traded: bool,
+    },
+}
+
+/// See [`PositionsResponse::diff`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PositionsDiff {
+    /// Sorted by account id, then orderbook id.
+    pub changes: Vec<PositionChange>,
+}
+
+impl PositionsDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl PositionsResponse {
+    /// The changes from `earlier` to `self`. Positions are matched by account and orderbook
+    /// id, so moving a holding to another account shows up as removed from one and added to
+    /// the other. Positions that didn't change at all are left out.
+    pub fn diff(&self, earlier: &PositionsResponse) -> PositionsDiff {
+        let before = by_key(earlier);
+        let now = by_key(self);
+
+        let mut changes = Vec::new();
+        for (key, position) in &now {
+            match before.get(key) {
+                None => changes.push((*key, PositionChange::Added((*position).clone()))),
+                Some(previous) => {
+                    let volume_delta = position.volume - previous.volume;
+                    let value_delta = position.value - previous.value;
+                    let profit_delta = position.profit - previous.profit;
+                    if volume_delta != 0 || value_delta != 0.0 || profit_delta != 0.0 {
+                        let change = PositionChange::Changed {
+                            position: (*position).clone(),
+                            volume_delta,
+                            value_delta,
+                            profit_delta,
+                            traded: volume_delta != 0,
+                        };
+                        changes.push((*key, change));
+                    }
+                }
+            }
+        }
+        for (key, position) in &before {
+            if !now.contains_key(key) {
+                changes.push((*key, PositionChange::Removed((*position).clone())));
+            }
+        }
+        changes.sort_by_key(|(key, _)| *key);
+
+        PositionsDiff {
+            changes: changes.into_iter().map(|(_, change)| change).collect(),
+        }
+    }
+}
+
+fn by_key(positions: &PositionsResponse) -> BTreeMap<(&str, &str), &Positions> {
+    positions
+        .instrument_positions
+        .iter()
+        .flat_map(|group| &group.positions)
+        .map(|position| {
+            let key = (position.account_id.as_str(), position.orderbook_id.as_str());
+            (key, position)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::portfolio::positions::InstrumentPositions;
+
+    fn position(account_id: &str, orderbook_id: &str, volume: i64, price: f64) -> Positions {
+        let acquired_value = 100.0 * volume as f64;
+        let value = price * volume as f64;
+        Positions {
+            account_id: String::from(account_id),
+            account_name: String::from("Konto"),
+            account_type: String::from("Investeringssparkonto"),
+            acquired_value,
+            average_acquired_price: 100.0,
+            change: 0.0,
+            change_percent: 0.0,
+            currency: String::from("SEK"),
+            depositable: true,
+            flag_code: String::from("SE"),
+            last_price: price,
+            last_price_updated: String::from("2026-10-15T09:00:00"),
+            name: format!("Instrument {}", orderbook_id),
+            orderbook_id: String::from(orderbook_id),
+            profit: value - acquired_value,
+            profit_percent: 0.0,
+            tradable: true,
+            value,
+            volume,
+        }
+    }
+
+    fn response(positions: Vec<Positions>) -> PositionsResponse {
+        PositionsResponse {
+            instrument_positions: vec![InstrumentPositions {
+                instrument_type: String::from("STOCK"),
+                total_value: positions.iter().map(|p| p.value).sum(),
+                positions,
+                todays_profit_percent: 0.0,
+                total_profit_percent: 0.0,
+                total_profit_value: 0.0,
+            }],
+            total_profit: 0.0,
+            total_profit_percent: 0.0,
+            total_balance: 0.0,
+            total_own_capital: 0.0,
+            total_buying_power: 0.0,
+        }
+    }
+
+    #[test]
+    fn unchanged_positions_are_left_out() {
+        let positions = || response(vec![position("1", "5269", 10, 110.0)]);
+        assert!(positions().diff(&positions()).is_empty());
+    }
+
+    #[test]
+    fn new_position_is_added() {
+        let yesterday = response(vec![position("1", "5269", 10, 110.0)]);
+        let today = response(vec![
+            position("1", "5269", 10, 110.0),
+            position("1", "5247", 5, 250.0),
+        ]);
+
+        assert_eq!(
+            today.diff(&yesterday).changes,
+            vec![PositionChange::Added(position("1", "5247", 5, 250.0))]
+        );
+    }
+
+    #[test]
+    fn closed_position_is_removed() {
+        let yesterday = response(vec![
+            position("1", "5269", 10, 110.0),
+            position("1", "5247", 5, 250.0),
+        ]);
+        let today = response(vec![position("1", "5269", 10, 110.0)]);
+
+        assert_eq!(
+            today.diff(&yesterday).changes,
+            vec![PositionChange::Removed(position("1", "5247", 5, 250.0))]
+        );
+    }
+
+    #[test]
+    fn price_move_is_not_a_trade() {
+        let yesterday = response(vec![position("1", "5269", 10, 110.0)]);
+        let today = response(vec![position("1", "5269", 10, 115.0)]);
+
+        assert_eq!(
+            today.diff(&yesterday).changes,
+            vec![PositionChange::Changed {
+                position: position("1", "5269", 10, 115.0),
+                volume_delta: 0,
+                value_delta: 50.0,
+                profit_delta: 50.0,
+                traded: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn volume_change_is_a_trade() {
+        let yesterday = response(vec![position("1", "5269", 10, 110.0)]);
+        let today = response(vec![position("1", "5269", 4, 110.0)]);
+
+        assert_eq!(
+            today.diff(&yesterday).changes,
+            vec![PositionChange::Changed {
+                position: position("1", "5269", 4, 110.0),
+                volume_delta: -6,
+                value_delta: -660.0,
+                profit_delta: -60.0,
+                traded: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn position_moved_between_accounts_is_removed_and_added() {
+        let yesterday = response(vec![position("1", "5269", 10, 110.0)]);
+        let today = response(vec![position("2", "5269", 10, 110.0)]);
+
+        assert_eq!(
+            today.diff(&yesterday).changes,
+            vec![
+                PositionChange::Removed(position("1", "5269", 10, 110.0)),
+                PositionChange::Added(position("2", "5269", 10, 110.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn changes_are_sorted_by_account_and_orderbook() {
+        let yesterday = response(vec![
+            position("2", "5247", 5, 250.0),
+            position("1", "5269", 10, 110.0),
+        ]);
+        let today = response(vec![
+            position("1", "5361", 1, 190.0),
+            position("2", "5247", 5, 240.0),
+        ]);
+
+        let changes = today.diff(&yesterday).changes;
+        assert_eq!(changes.len(), 3);
+        assert!(matches!(&changes[0], PositionChange::Removed(p) if p.orderbook_id == "5269"));
+        assert!(matches!(&changes[1], PositionChange::Added(p) if p.orderbook_id == "5361"));
+        assert!(
+            matches!(&changes[2], PositionChange::Changed { position, traded: false, .. } if position.account_id == "2")
+        );
+    }
+}
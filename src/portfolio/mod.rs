@@ -1 +1,6 @@
+pub mod deals_and_orders;
+pub mod diff;
+pub mod overview;
 pub mod positions;
+pub mod snapshot;
+pub mod wealth;
@@ -0,0 +1,93 @@
+use crate::account::accounts::AccountType;
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::types::{AccountId, Percent};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountOverview {
+    pub account_id: AccountId,
+    pub name: String,
+    pub account_type: AccountType,
+    pub own_capital: f64,
+    pub buying_power: f64,
+    pub total_balance: f64,
+    #[serde(default)]
+    pub performance_percent: Option<Percent>,
+}
+
+/// Totals over all accounts the user can see, including ones held by attorney.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Overview {
+    pub accounts: Vec<AccountOverview>,
+    pub total_own_capital: f64,
+    pub total_buying_power: f64,
+    pub total_balance: f64,
+    #[serde(default)]
+    pub total_performance_percent: Option<Percent>,
+}
+
+impl Client {
+    /// Balances and buying power per account together with the totals across them.
+    pub async fn get_overview(&self) -> Result<Overview, RequestError> {
+        let resp = self
+            .fetch_spec::<Overview>(&endpoints::overview(), Idempotency::Idempotent)
+            .await?;
+        Ok(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.get_overview().await);
+    }
+
+    #[tokio::test]
+    async fn can_get_overview() {
+        let mock_server = MockServer::start().await;
+
+        let responder = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{
+                "accounts": [
+                    {"accountId": "1234", "name": "ISK", "accountType": "Investeringssparkonto", "ownCapital": 152340.5, "buyingPower": 2300.0, "totalBalance": 152340.5, "performancePercent": 8.4},
+                    {"accountId": "5678", "name": "Buffert", "accountType": "Sparkonto", "ownCapital": 50000.0, "buyingPower": 50000.0, "totalBalance": 50000.0, "performancePercent": null}
+                ],
+                "totalOwnCapital": 202340.5,
+                "totalBuyingPower": 52300.0,
+                "totalBalance": 202340.5,
+                "totalPerformancePercent": 6.1
+            }"#,
+        ));
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/overview"))
+            .respond_with(responder)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let overview = assert_ok!(client.get_overview().await);
+        assert_eq!(overview.accounts.len(), 2);
+        assert_eq!(overview.accounts[0].account_type, AccountType::Isk);
+        assert_eq!(overview.accounts[1].performance_percent, None);
+        assert_eq!(overview.total_buying_power, 52300.0);
+        assert_eq!(overview.total_performance_percent, Some(Percent(6.1)));
+    }
+}
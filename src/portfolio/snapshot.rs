@@ -0,0 +1,125 @@
+use crate::client::Client;
+use crate::error::RequestError;
+use crate::portfolio::deals_and_orders::DealsAndOrdersResponse;
+use crate::portfolio::overview::Overview;
+use crate::portfolio::positions::PositionsResponse;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snapshot {
+    pub overview: Overview,
+    pub positions: PositionsResponse,
+    pub deals_and_orders: DealsAndOrdersResponse,
+}
+
+impl Client {
+    /// Overview, positions and deals and orders, requested concurrently. Fails with the
+    /// first error, the other results are dropped. The requests still go through the
+    /// client's rate limit.
+    pub async fn get_snapshot(&self) -> Result<Snapshot, RequestError> {
+        let (overview, positions, deals_and_orders) = tokio::try_join!(
+            self.get_overview(),
+            self.get_positions(),
+            self.get_deals_and_orders()
+        )?;
+        Ok(Snapshot {
+            overview,
+            positions,
+            deals_and_orders,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::client::RateLimit;
+
+    use super::*;
+    use crate::test_support::{
+        authenticated_client, authenticated_client_with, config, DEALS_AND_ORDERS, OVERVIEW,
+        POSITIONS,
+    };
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn mock_endpoints(mock_server: &MockServer, delays_ms: [u64; 3]) {
+        let bodies = [
+            ("/_mobile/account/overview", OVERVIEW),
+            ("/_mobile/account/positions", POSITIONS),
+            ("/_mobile/account/dealsandorders", DEALS_AND_ORDERS),
+        ];
+        for ((endpoint, body), delay) in bodies.into_iter().zip(delays_ms) {
+            Mock::given(method("GET"))
+                .and(path(endpoint))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string(body)
+                        .set_delay(Duration::from_millis(delay)),
+                )
+                .expect(1)
+                .mount(mock_server)
+                .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.get_snapshot().await);
+    }
+
+    #[tokio::test]
+    async fn fetches_sections_concurrently() {
+        let mock_server = MockServer::start().await;
+        mock_endpoints(&mock_server, [300, 200, 100]).await;
+        let client = authenticated_client(&mock_server).await;
+
+        let start = Instant::now();
+        let snapshot = assert_ok!(client.get_snapshot().await);
+        let elapsed = start.elapsed();
+
+        assert_eq!(snapshot.overview.total_buying_power, 250.0);
+        assert_eq!(snapshot.positions.total_balance, 4000.0);
+        assert!(snapshot.deals_and_orders.orders.is_empty());
+        assert!(elapsed >= Duration::from_millis(300));
+        assert!(elapsed < Duration::from_millis(550), "took {:?}", elapsed);
+    }
+
+    #[tokio::test]
+    async fn fails_with_first_error() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/positions"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("not json"))
+            .mount(&mock_server)
+            .await;
+        let client = authenticated_client(&mock_server).await;
+
+        assert_err!(client.get_snapshot().await);
+    }
+
+    #[tokio::test]
+    async fn respects_rate_limit() {
+        let mock_server = MockServer::start().await;
+        mock_endpoints(&mock_server, [0, 0, 0]).await;
+        let client = authenticated_client_with(
+            &mock_server,
+            Client::builder().rate_limit(RateLimit::per_second(10)),
+        )
+        .await;
+
+        let start = Instant::now();
+        assert_ok!(client.get_snapshot().await);
+
+        // Three requests at least 100 ms apart.
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,316 @@
+use std::collections::HashMap;
+
+use futures_util::future::try_join_all;
+
+use crate::account::accounts::{Account, AccountType};
+use crate::account::transfers::TransferDirection;
+use crate::client::Client;
+use crate::error::RequestError;
+use crate::types::Currency;
+use serde::{Deserialize, Serialize};
+
+/// Amounts in SEK, see [`Client::get_wealth_summary`].
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WealthTotals {
+    /// The market value of the positions.
+    pub invested: f64,
+    pub cash: f64,
+    pub credit_used: f64,
+    /// Deposits that haven't settled yet.
+    pub pending_inflows: f64,
+}
+
+impl WealthTotals {
+    fn add(&mut self, other: &WealthTotals) {
+        self.invested += other.invested;
+        self.cash += other.cash;
+        self.credit_used += other.credit_used;
+        self.pending_inflows += other.pending_inflows;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WealthSummary {
+    pub total: WealthTotals,
+    /// Only account types the user has accounts of.
+    pub by_account_type: HashMap<AccountType, WealthTotals>,
+}
+
+impl Client {
+    /// Invested value, cash, credit used and pending deposits over all accounts, in total and
+    /// per account type. Positions valued in another currency are converted to SEK with the
+    /// current exchange rates, and a currency without a rate fails the summary with
+    /// `RequestError::ConversionError`.
+    ///
+    /// Accounts, positions, exchange rates and the cash and transfers of every account are
+    /// requested concurrently, still within the client's rate limit. Like
+    /// [`get_snapshot`](Client::get_snapshot) it fails with the first error and drops the
+    /// other results, since totals that silently miss an account would look right but not be.
+    pub async fn get_wealth_summary(&self) -> Result<WealthSummary, RequestError> {
+        let (positions, converter, accounts) =
+            tokio::try_join!(self.get_positions(), self.currency_converter(), async {
+                let accounts = self.get_accounts().await?;
+                try_join_all(
+                    accounts
+                        .into_iter()
+                        .map(|account| self.account_wealth(account)),
+                )
+                .await
+            })?;
+
+        let mut account_types: HashMap<String, AccountType> = HashMap::new();
+        let mut by_account_type: HashMap<AccountType, WealthTotals> = HashMap::new();
+        for (account, totals) in &accounts {
+            account_types.insert(account.id.0.clone(), account.account_type);
+            by_account_type
+                .entry(account.account_type)
+                .or_default()
+                .add(totals);
+        }
+        for position in positions
+            .instrument_positions
+            .iter()
+            .flat_map(|group| &group.positions)
+        {
+            let account_type = account_types
+                .get(&position.account_id)
+                .copied()
+                .unwrap_or(AccountType::Other);
+            let value = converter.convert(
+                position.value,
+                &Currency::from(position.currency.as_str()),
+                &Currency::SEK,
+            )?;
+            by_account_type.entry(account_type).or_default().invested += value;
+        }
+
+        let mut total = WealthTotals::default();
+        for totals in by_account_type.values() {
+            total.add(totals);
+        }
+        Ok(WealthSummary {
+            total,
+            by_account_type,
+        })
+    }
+
+    async fn account_wealth(
+        &self,
+        account: Account,
+    ) -> Result<(Account, WealthTotals), RequestError> {
+        let (cash, transfers) = tokio::try_join!(
+            self.get_cash_balance(&account.id),
+            self.get_pending_transfers(&account.id)
+        )?;
+        let pending_inflows = transfers
+            .iter()
+            .filter(|transfer| transfer.is_pending())
+            .filter(|transfer| transfer.direction == TransferDirection::Deposit)
+            .map(|transfer| transfer.amount)
+            .sum();
+        let totals = WealthTotals {
+            invested: 0.0,
+            cash: cash.available_cash,
+            credit_used: cash.credit_used,
+            pending_inflows,
+        };
+        Ok((account, totals))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config, mount_get, mount_positions};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const ACCOUNTS: &str = r#"{
+        "accounts": [
+            {"id": "1111", "name": "ISK", "accountType": "Investeringssparkonto", "tradable": true, "depositable": true, "ownership": "OWNED"},
+            {"id": "2222", "name": "Barnens ISK", "accountType": "Investeringssparkonto", "tradable": true, "depositable": true, "ownership": "ATTORNEY"},
+            {"id": "3333", "name": "KF", "accountType": "Kapitalforsakring", "tradable": true, "depositable": true, "ownership": "OWNED"},
+            {"id": "4444", "name": "Buffert", "accountType": "Sparkonto", "tradable": false, "depositable": true, "ownership": "OWNED"}
+        ]
+    }"#;
+
+    // Apple is valued in USD, 1000 USD being 10870 SEK at the rate below.
+    const POSITIONS: &str = r#"{
+        "instrumentPositions": [
+            {
+                "instrumentType": "STOCK",
+                "positions": [
+                    {"accountId": "1111", "accountName": "ISK", "accountType": "Investeringssparkonto", "acquiredValue": 9000, "averageAcquiredPrice": 180, "change": 0, "changePercent": 0, "currency": "SEK", "depositable": false, "flagCode": "SE", "lastPrice": 200, "lastPriceUpdated": "2026-10-15T15:30:00", "name": "Volvo B", "orderbookId": "5269", "profit": 1000, "profitPercent": 11.1, "tradable": true, "value": 10000, "volume": 50},
+                    {"accountId": "2222", "accountName": "Barnens ISK", "accountType": "Investeringssparkonto", "acquiredValue": 2000, "averageAcquiredPrice": 200, "change": 0, "changePercent": 0, "currency": "SEK", "depositable": false, "flagCode": "SE", "lastPrice": 250, "lastPriceUpdated": "2026-10-15T15:30:00", "name": "Investor B", "orderbookId": "5247", "profit": 500, "profitPercent": 25, "tradable": true, "value": 2500, "volume": 10},
+                    {"accountId": "3333", "accountName": "KF", "accountType": "Kapitalforsakring", "acquiredValue": 9000, "averageAcquiredPrice": 90, "change": 0, "changePercent": 0, "currency": "USD", "depositable": false, "flagCode": "US", "lastPrice": 100, "lastPriceUpdated": "2026-10-15T15:30:00", "name": "Apple", "orderbookId": "238449", "profit": 1870, "profitPercent": 20.8, "tradable": true, "value": 1000, "volume": 10}
+                ],
+                "todaysProfitPercent": 0,
+                "totalProfitPercent": 14.9,
+                "totalProfitValue": 3370,
+                "totalValue": 23370
+            }
+        ],
+        "totalProfit": 3370,
+        "totalProfitPercent": 14.9,
+        "totalBalance": 0,
+        "totalOwnCapital": 23370,
+        "totalBuyingPower": 0
+    }"#;
+
+    fn cash(available: f64, credit_used: Option<f64>) -> String {
+        format!(
+            r#"{{"availableCash": {}, "buyingPower": {}, "creditLimit": {}, "creditUsed": {}}}"#,
+            available,
+            available,
+            credit_used.map_or(String::from("null"), |_| String::from("50000")),
+            credit_used.map_or(String::from("null"), |used| used.to_string()),
+        )
+    }
+
+    fn transfers(transfers: &[(f64, &str, &str)]) -> String {
+        let transfers: Vec<String> = transfers
+            .iter()
+            .enumerate()
+            .map(|(i, (amount, direction, status))| {
+                format!(
+                    r#"{{"id": "t-{}", "amount": {}, "direction": "{}", "status": "{}", "initiatedDate": "2026-10-14"}}"#,
+                    i, amount, direction, status
+                )
+            })
+            .collect();
+        format!(r#"{{"transfers": [{}]}}"#, transfers.join(","))
+    }
+
+    async fn mount_accounts(mock_server: &MockServer) {
+        mount_get(mock_server, "/_mobile/account/accounts", ACCOUNTS).await;
+        mount_positions(mock_server, POSITIONS).await;
+        mount_get(
+            mock_server,
+            "/_mobile/market/exchange-rates",
+            r#"{"exchangeRates": [{"fromCurrency": "USD", "toCurrency": "SEK", "rate": 10.87}]}"#,
+        )
+        .await;
+        let accounts = [
+            (
+                "1111",
+                cash(1500.0, Some(20000.0)),
+                transfers(&[
+                    (5000.0, "DEPOSIT", "PENDING"),
+                    (300.0, "WITHDRAWAL", "PENDING"),
+                ]),
+            ),
+            ("2222", cash(200.0, None), transfers(&[])),
+            (
+                "3333",
+                cash(300.0, None),
+                transfers(&[(1000.0, "DEPOSIT", "COMPLETED")]),
+            ),
+            (
+                "4444",
+                cash(40000.0, None),
+                transfers(&[(2500.0, "DEPOSIT", "PENDING")]),
+            ),
+        ];
+        for (account_id, cash, transfers) in accounts {
+            mount_get(
+                mock_server,
+                &format!("/_mobile/account/{}/cash", account_id),
+                &cash,
+            )
+            .await;
+            mount_get(
+                mock_server,
+                &format!("/_mobile/account/{}/transfers/status", account_id),
+                &transfers,
+            )
+            .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.get_wealth_summary().await);
+    }
+
+    #[tokio::test]
+    async fn sums_up_all_accounts() {
+        let mock_server = MockServer::start().await;
+        mount_accounts(&mock_server).await;
+        let client = authenticated_client(&mock_server).await;
+
+        let summary = assert_ok!(client.get_wealth_summary().await);
+
+        assert_eq!(
+            summary.total,
+            WealthTotals {
+                invested: 23370.0,
+                cash: 42000.0,
+                credit_used: 20000.0,
+                pending_inflows: 7500.0,
+            }
+        );
+        assert_eq!(summary.by_account_type.len(), 3);
+        assert_eq!(
+            summary.by_account_type[&AccountType::Isk],
+            WealthTotals {
+                invested: 12500.0,
+                cash: 1700.0,
+                credit_used: 20000.0,
+                pending_inflows: 5000.0,
+            }
+        );
+        assert_eq!(
+            summary.by_account_type[&AccountType::Kf],
+            WealthTotals {
+                invested: 10870.0,
+                cash: 300.0,
+                credit_used: 0.0,
+                pending_inflows: 0.0,
+            }
+        );
+        assert_eq!(
+            summary.by_account_type[&AccountType::Savings],
+            WealthTotals {
+                invested: 0.0,
+                cash: 40000.0,
+                credit_used: 0.0,
+                pending_inflows: 2500.0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_when_one_account_fails() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/account/3333/cash"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+        mount_accounts(&mock_server).await;
+        let client = authenticated_client(&mock_server).await;
+
+        assert_err!(client.get_wealth_summary().await);
+    }
+
+    #[tokio::test]
+    async fn fails_without_exchange_rate() {
+        let mock_server = MockServer::start().await;
+        mount_positions(&mock_server, &POSITIONS.replace(r#""USD""#, r#""GBP""#)).await;
+        mount_accounts(&mock_server).await;
+        let client = authenticated_client(&mock_server).await;
+
+        assert!(matches!(
+            client.get_wealth_summary().await,
+            Err(RequestError::ConversionError(_))
+        ));
+    }
+}
@@ -0,0 +1,48 @@
+//! The types most programs need, for a glob import.
+//!
+//! ```no_run
+//! use avanza::prelude::*;
+//!
+//! #[tokio::main(flavor = "current_thread")]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client: Client = Client::builder()
+//!         .config(Config {
+//!             avanza_username: String::from("user"),
+//!             avanza_password: String::from("pass"),
+//!             avanza_totp_secret: String::from("secret"),
+//!         })
+//!         .build()?;
+//!     client.authenticate().await?;
+//!
+//!     let overview: Overview = client.get_overview().await?;
+//!     let positions: PositionsResponse = client.get_positions().await?;
+//!     for account in &overview.accounts {
+//!         let id: &AccountId = &account.account_id;
+//!         println!("{} {}", id, account.own_capital);
+//!     }
+//!     for group in &positions.instrument_positions {
+//!         for position in &group.positions {
+//!             let orderbook_id = OrderbookId::from(position.orderbook_id.as_str());
+//!             println!("{} {}", orderbook_id, position.value);
+//!         }
+//!     }
+//!
+//!     let stocks: Result<Vec<_>, RequestError> = client
+//!         .suggest("volvo", 5)
+//!         .await
+//!         .map(|hits| {
+//!             hits.into_iter()
+//!                 .filter(|hit| hit.instrument_type == InstrumentType::Stock)
+//!                 .collect()
+//!         });
+//!     println!("{:?}", stocks?);
+//!     Ok(())
+//! }
+//! ```
+
+pub use crate::api::AvanzaApi;
+pub use crate::client::{CallOptions, Client, ClientBuilder, Config, RateLimit, RetryPolicy};
+pub use crate::error::{ConfigError, ConversionError, RequestError};
+pub use crate::portfolio::overview::Overview;
+pub use crate::portfolio::positions::PositionsResponse;
+pub use crate::types::{AccountId, Currency, InstrumentType, OrderbookId};
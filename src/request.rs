@@ -1,3 +1,7 @@
+//! Standalone JSON POSTs, each on a new `reqwest::Client`. `Client` doesn't use these: it
+//! logs in over its own HTTP client, the one passed to
+//! [`ClientBuilder::with_http_client`](crate::ClientBuilder::with_http_client) if any.
+
 use std::collections::HashMap;
 
 use reqwest::Response;
@@ -0,0 +1,191 @@
+//! Deserializers for fields Avanza doesn't encode consistently.
+
+/// Numbers that arrive as JSON numbers, as strings like `"12.5"`, or, mostly in older
+/// transaction exports and some fund fields, with a decimal comma like `"12,50"`. Spaces,
+/// e.g. thousands separators, are ignored. Anything else is an error naming the field.
+///
+/// Use `deserialize` with `#[serde(deserialize_with = "..")]`, or wrap `deserialize_named`
+/// to name the field in errors.
+pub mod lenient_f64 {
+    use std::fmt;
+
+    use serde::de::{self, Deserializer, Unexpected, Visitor};
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<f64, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_named(deserializer, "value")
+    }
+
+    pub fn deserialize_named<'de, D>(deserializer: D, field: &'static str) -> Result<f64, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(LenientF64 { field })
+    }
+
+    /// Like [`deserialize_named`], with `null` as `None`.
+    pub fn deserialize_option_named<'de, D>(
+        deserializer: D,
+        field: &'static str,
+    ) -> Result<Option<f64>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_option(LenientOptionF64 { field })
+    }
+
+    /// Parses `"12.5"`, `"12,50"` or `"1 234,50"`. A string with both a comma and a dot is
+    /// ambiguous and rejected, and so is anything that isn't a finite number, like `"NaN"`
+    /// or `"1e999"`.
+    pub fn parse(value: &str) -> Option<f64> {
+        let value: String = value
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '\u{a0}')
+            .collect();
+        if value.contains(',') && value.contains('.') {
+            return None;
+        }
+        value
+            .replace(',', ".")
+            .parse::<f64>()
+            .ok()
+            .filter(|parsed| parsed.is_finite())
+    }
+
+    struct LenientF64 {
+        field: &'static str,
+    }
+
+    impl<'de> Visitor<'de> for LenientF64 {
+        type Value = f64;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a number or a numeric string for `{}`", self.field)
+        }
+
+        fn visit_f64<E: de::Error>(self, value: f64) -> Result<f64, E> {
+            Ok(value)
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<f64, E> {
+            Ok(value as f64)
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<f64, E> {
+            Ok(value as f64)
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<f64, E> {
+            parse(value).ok_or_else(|| E::invalid_value(Unexpected::Str(value), &self))
+        }
+    }
+
+    struct LenientOptionF64 {
+        field: &'static str,
+    }
+
+    impl<'de> Visitor<'de> for LenientOptionF64 {
+        type Value = Option<f64>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a number, a numeric string or null for `{}`", self.field)
+        }
+
+        fn visit_none<E: de::Error>(self) -> Result<Option<f64>, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Option<f64>, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D>(self, deserializer: D) -> Result<Option<f64>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserialize_named(deserializer, self.field).map(Some)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::lenient_f64;
+
+    #[derive(Debug, Deserialize)]
+    struct Amounts {
+        #[serde(deserialize_with = "amount")]
+        amount: f64,
+        #[serde(default, deserialize_with = "price")]
+        price: Option<f64>,
+    }
+
+    fn amount<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        lenient_f64::deserialize_named(deserializer, "amount")
+    }
+
+    fn price<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+        lenient_f64::deserialize_option_named(deserializer, "price")
+    }
+
+    fn amount_of(json: &str) -> Result<f64, serde_json::Error> {
+        serde_json::from_str::<Amounts>(&format!(r#"{{"amount": {}}}"#, json))
+            .map(|amounts| amounts.amount)
+    }
+
+    #[test]
+    fn accepts_numbers() {
+        assert_eq!(amount_of("12.5").unwrap(), 12.5);
+        assert_eq!(amount_of("12").unwrap(), 12.0);
+        assert_eq!(amount_of("-3").unwrap(), -3.0);
+    }
+
+    #[test]
+    fn accepts_dot_decimal_strings() {
+        assert_eq!(amount_of(r#""12.5""#).unwrap(), 12.5);
+        assert_eq!(amount_of(r#""-0.25""#).unwrap(), -0.25);
+    }
+
+    #[test]
+    fn accepts_comma_decimal_strings() {
+        assert_eq!(amount_of(r#""12,50""#).unwrap(), 12.5);
+        assert_eq!(amount_of(r#""1 234,50""#).unwrap(), 1234.5);
+        assert_eq!(amount_of("\"1\u{a0}234,50\"").unwrap(), 1234.5);
+    }
+
+    #[test]
+    fn option_accepts_null_and_missing() {
+        let amounts: Amounts = serde_json::from_str(r#"{"amount": 1, "price": null}"#).unwrap();
+        assert_eq!(amounts.price, None);
+        let amounts: Amounts = serde_json::from_str(r#"{"amount": 1}"#).unwrap();
+        assert_eq!(amounts.price, None);
+        let amounts: Amounts = serde_json::from_str(r#"{"amount": 1, "price": "7,5"}"#).unwrap();
+        assert_eq!(amounts.price, Some(7.5));
+    }
+
+    #[test]
+    fn rejects_malformed_values_naming_the_field() {
+        for malformed in [
+            r#""12,5,0""#,
+            r#""1,234.50""#,
+            r#""n/a""#,
+            r#""""#,
+            "true",
+            r#""NaN""#,
+            r#""inf""#,
+            r#""-infinity""#,
+            r#""1e999""#,
+        ] {
+            let error = amount_of(malformed).unwrap_err().to_string();
+            assert!(error.contains("`amount`"), "{}: {}", malformed, error);
+        }
+        let error = serde_json::from_str::<Amounts>(r#"{"amount": 1, "price": "abc"}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("`price`"), "{}", error);
+    }
+}
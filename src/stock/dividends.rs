@@ -0,0 +1,120 @@
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::types::{Currency, OrderbookId};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DividendType {
+    Ordinary,
+    Extra,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DividendHistoryResponse {
+    #[serde(default)]
+    dividends: Vec<Dividend>,
+}
+
+/// Amounts are per share as paid, i.e. not adjusted for later splits.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Dividend {
+    pub ex_date: NaiveDate,
+    #[serde(default)]
+    pub payment_date: Option<NaiveDate>,
+    pub amount: f64,
+    pub currency: Currency,
+    pub dividend_type: DividendType,
+}
+
+impl Client {
+    /// Past dividends ordered by ex-date, oldest first.
+    pub async fn get_dividend_history(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<Vec<Dividend>, RequestError> {
+        let mut dividends = self
+            .fetch_spec::<DividendHistoryResponse>(
+                &endpoints::dividends(orderbook_id),
+                Idempotency::Idempotent,
+            )
+            .await?
+            .dividends;
+        dividends.sort_by_key(|d| d.ex_date);
+        Ok(dividends)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(
+            client
+                .get_dividend_history(&OrderbookId::from("5269"))
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn can_get_dividend_history() {
+        let mock_server = MockServer::start().await;
+
+        let responder = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{
+                "dividends": [
+                    {"exDate": "2025-04-04", "paymentDate": "2025-04-10", "amount": 10.5, "currency": "SEK", "dividendType": "ORDINARY"},
+                    {"exDate": "2025-04-04", "paymentDate": "2025-04-10", "amount": 8.0, "currency": "SEK", "dividendType": "EXTRA"},
+                    {"exDate": "2024-04-05", "paymentDate": "2024-04-11", "amount": 7.0, "currency": "SEK", "dividendType": "ORDINARY", "splitFactor": 1},
+                    {"exDate": "2023-04-05", "paymentDate": null, "amount": 6.0, "currency": "SEK", "dividendType": "ORDINARY"}
+                ]
+            }"#,
+        ));
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/market/stock/5269/dividends"))
+            .respond_with(responder)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let dividends = assert_ok!(
+            client
+                .get_dividend_history(&OrderbookId::from("5269"))
+                .await
+        );
+
+        assert_eq!(dividends.len(), 4);
+        assert_eq!(
+            dividends[0].ex_date,
+            NaiveDate::from_ymd_opt(2023, 4, 5).unwrap()
+        );
+        assert_eq!(dividends[0].payment_date, None);
+        assert_eq!(dividends[0].currency, Currency::SEK);
+
+        let extra: Vec<&Dividend> = dividends
+            .iter()
+            .filter(|d| d.dividend_type == DividendType::Extra)
+            .collect();
+        assert_eq!(extra.len(), 1);
+        assert_eq!(extra[0].amount, 8.0);
+    }
+}
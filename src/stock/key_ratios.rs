@@ -0,0 +1,111 @@
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::types::{OrderbookId, Percent};
+use serde::{Deserialize, Serialize};
+
+/// Ratios Avanza can't compute, e.g. P/E for a loss-making company, are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyRatios {
+    pub price_earnings_ratio: Option<f64>,
+    pub price_sales_ratio: Option<f64>,
+    pub price_book_ratio: Option<f64>,
+    pub dividend_yield: Option<Percent>,
+    pub direct_yield: Option<Percent>,
+    pub volatility: Option<Percent>,
+    pub beta: Option<f64>,
+    pub earnings_per_share: Option<f64>,
+    pub equity_per_share: Option<f64>,
+}
+
+impl Client {
+    pub async fn get_key_ratios(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<KeyRatios, RequestError> {
+        let resp = self
+            .fetch_spec::<KeyRatios>(
+                &endpoints::key_ratios(orderbook_id),
+                Idempotency::Idempotent,
+            )
+            .await?;
+        Ok(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.get_key_ratios(&OrderbookId::from("5269")).await);
+    }
+
+    #[tokio::test]
+    async fn can_get_key_ratios() {
+        let mock_server = MockServer::start().await;
+
+        let profitable = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{
+                "priceEarningsRatio": 11.4,
+                "priceSalesRatio": 1.05,
+                "priceBookRatio": 2.9,
+                "dividendYield": 5.2,
+                "directYield": 5.2,
+                "volatility": 24.1,
+                "beta": 1.12,
+                "earningsPerShare": 22.3,
+                "equityPerShare": 87.6
+            }"#,
+        ));
+
+        let loss_making = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{
+                "priceEarningsRatio": null,
+                "priceSalesRatio": 14.2,
+                "priceBookRatio": 3.1,
+                "dividendYield": null,
+                "volatility": 61.5,
+                "beta": null,
+                "earningsPerShare": -1.84,
+                "equityPerShare": 4.2
+            }"#,
+        ));
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/market/stock/5269/keyratios"))
+            .respond_with(profitable)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/market/stock/1153498/keyratios"))
+            .respond_with(loss_making)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let ratios = assert_ok!(client.get_key_ratios(&OrderbookId::from("5269")).await);
+        assert_eq!(ratios.price_earnings_ratio, Some(11.4));
+        assert_eq!(ratios.dividend_yield, Some(Percent(5.2)));
+        assert_eq!(ratios.beta, Some(1.12));
+
+        let ratios = assert_ok!(client.get_key_ratios(&OrderbookId::from("1153498")).await);
+        assert_eq!(ratios.price_earnings_ratio, None);
+        assert_eq!(ratios.dividend_yield, None);
+        assert_eq!(ratios.direct_yield, None);
+        assert_eq!(ratios.earnings_per_share, Some(-1.84));
+        assert_eq!(ratios.volatility, Some(Percent(61.5)));
+    }
+}
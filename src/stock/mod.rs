@@ -0,0 +1,3 @@
+pub mod dividends;
+pub mod key_ratios;
+pub mod short_positions;
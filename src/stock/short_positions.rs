@@ -0,0 +1,113 @@
+use crate::client::{Client, Idempotency};
+use crate::endpoints;
+use crate::error::RequestError;
+use crate::types::{OrderbookId, Percent};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortHolder {
+    pub holder_name: String,
+    pub percent: Percent,
+    pub date: NaiveDate,
+}
+
+/// Disclosed short interest as reported to Finansinspektionen. Stocks without disclosed
+/// shorts have a zero total and no holders.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortPositions {
+    #[serde(default)]
+    pub total_short_percent: Percent,
+    #[serde(default)]
+    pub holders: Vec<ShortHolder>,
+}
+
+impl Client {
+    pub async fn get_short_positions(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<ShortPositions, RequestError> {
+        let resp = self
+            .fetch_spec::<ShortPositions>(
+                &endpoints::short_positions(orderbook_id),
+                Idempotency::Idempotent,
+            )
+            .await?;
+        Ok(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{authenticated_client, config};
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .api_url(String::from("http://fake-url.com"))
+            .build()
+            .unwrap();
+        assert_err!(client.get_short_positions(&OrderbookId::from("5269")).await);
+    }
+
+    #[tokio::test]
+    async fn can_get_short_positions() {
+        let mock_server = MockServer::start().await;
+
+        let heavily_shorted = ResponseTemplate::new(200).set_body_string(String::from(
+            r#"{
+                "totalShortPercent": 12.4,
+                "holders": [
+                    {"holderName": "Citadel Advisors LLC", "percent": 4.1, "date": "2026-10-09"},
+                    {"holderName": "Marshall Wace LLP", "percent": 3.3, "date": "2026-10-12"}
+                ]
+            }"#,
+        ));
+
+        let none = ResponseTemplate::new(200)
+            .set_body_string(String::from(r#"{"totalShortPercent": 0, "holders": []}"#));
+
+        Mock::given(method("GET"))
+            .and(path("/_mobile/market/stock/549768/shortpositions"))
+            .respond_with(heavily_shorted)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/_mobile/market/stock/5269/shortpositions"))
+            .respond_with(none)
+            .mount(&mock_server)
+            .await;
+
+        let client = authenticated_client(&mock_server).await;
+
+        let shorts = assert_ok!(
+            client
+                .get_short_positions(&OrderbookId::from("549768"))
+                .await
+        );
+        assert_eq!(shorts.total_short_percent, Percent(12.4));
+        assert_eq!(shorts.holders.len(), 2);
+        assert_eq!(shorts.holders[0].holder_name, "Citadel Advisors LLC");
+        assert_eq!(
+            shorts.holders[1].date,
+            NaiveDate::from_ymd_opt(2026, 10, 12).unwrap()
+        );
+
+        let shorts = assert_ok!(client.get_short_positions(&OrderbookId::from("5269")).await);
+        assert_eq!(shorts, ShortPositions::default());
+    }
+
+    #[test]
+    fn missing_fields_mean_no_shorts() {
+        let shorts: ShortPositions = serde_json::from_str("{}").expect("failed to parse");
+        assert_eq!(shorts.total_short_percent, Percent(0.0));
+        assert!(shorts.holders.is_empty());
+    }
+}
@@ -0,0 +1,57 @@
+//! Stockholm time, which Avanza's charts, trading days and order validity are in.
+
+use chrono::{
+    DateTime, Datelike, Days, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday,
+};
+
+const CET: i32 = 3600;
+const CEST: i32 = 2 * 3600;
+
+/// Central European Time, with summer time from 01:00 UTC on the last Sunday of March to
+/// 01:00 UTC on the last Sunday of October.
+pub(crate) fn in_stockholm(time: DateTime<Utc>) -> DateTime<FixedOffset> {
+    let utc = time.naive_utc();
+    let year = utc.year();
+    let summer_time = summer_time_switch(year, 3) <= utc && utc < summer_time_switch(year, 10);
+    let offset = if summer_time { CEST } else { CET };
+    time.with_timezone(&FixedOffset::east_opt(offset).unwrap())
+}
+
+/// The date in Stockholm now, whatever the host's timezone.
+pub(crate) fn today() -> NaiveDate {
+    in_stockholm(Utc::now()).date_naive()
+}
+
+fn summer_time_switch(year: i32, month: u32) -> NaiveDateTime {
+    let mut day = NaiveDate::from_ymd_opt(year, month, 31).unwrap();
+    while day.weekday() != Weekday::Sun {
+        day = day - Days::new(1);
+    }
+    day.and_time(NaiveTime::from_hms_opt(1, 0, 0).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date_in_stockholm(rfc3339: &str) -> NaiveDate {
+        let time = DateTime::parse_from_rfc3339(rfc3339).unwrap();
+        in_stockholm(time.with_timezone(&Utc)).date_naive()
+    }
+
+    #[test]
+    fn late_utc_evening_is_the_next_day() {
+        assert_eq!(
+            date_in_stockholm("2026-10-14T22:30:00Z"),
+            NaiveDate::from_ymd_opt(2026, 10, 15).unwrap()
+        );
+        assert_eq!(
+            date_in_stockholm("2026-01-14T23:30:00Z"),
+            NaiveDate::from_ymd_opt(2026, 1, 15).unwrap()
+        );
+        assert_eq!(
+            date_in_stockholm("2026-01-14T22:30:00Z"),
+            NaiveDate::from_ymd_opt(2026, 1, 14).unwrap()
+        );
+    }
+}
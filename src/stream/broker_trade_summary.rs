@@ -0,0 +1,69 @@
+use crate::error::RequestError;
+use crate::stream::connection::{PushConnection, Subscription};
+use crate::types::OrderbookId;
+use serde::{Deserialize, Serialize};
+
+/// Volumes one broker has bought and sold in an orderbook so far today.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokerTradeSummary {
+    pub orderbook_id: OrderbookId,
+    pub broker_code: String,
+    #[serde(default)]
+    pub buy_volume: u64,
+    #[serde(default)]
+    pub sell_volume: u64,
+}
+
+impl BrokerTradeSummary {
+    pub fn net_volume(&self) -> i64 {
+        self.buy_volume as i64 - self.sell_volume as i64
+    }
+}
+
+impl PushConnection {
+    pub fn subscribe_broker_trade_summary(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<Subscription<BrokerTradeSummary>, RequestError> {
+        self.subscribe(format!("/brokertradesummary/{}", orderbook_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::Client;
+    use crate::stream::mock_server::MockPushServer;
+
+    use super::*;
+    use crate::test_support::authenticated_client_with;
+    use serde_json::json;
+    use tokio_test::assert_ok;
+    use wiremock::MockServer;
+
+    #[tokio::test]
+    async fn can_subscribe_to_broker_trade_summary() {
+        let mock_server = MockServer::start().await;
+        let push_server = MockPushServer::start().await;
+
+        let client =
+            authenticated_client_with(&mock_server, Client::builder().push_url(push_server.url()))
+                .await;
+
+        let connection = assert_ok!(client.connect_push().await);
+        let mut summaries =
+            assert_ok!(connection.subscribe_broker_trade_summary(&OrderbookId::from("5269")));
+        push_server
+            .wait_for_subscription("/brokertradesummary/5269")
+            .await;
+
+        push_server.publish(
+            "/brokertradesummary/5269",
+            json!({"orderbookId": "5269", "brokerCode": "NON", "buyVolume": 200, "sellVolume": 1450}),
+        );
+
+        let summary = assert_ok!(summaries.next().await.expect("stream ended"));
+        assert_eq!(summary.broker_code, "NON");
+        assert_eq!(summary.net_volume(), -1250);
+    }
+}
@@ -0,0 +1,139 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_util::{Future, Stream};
+use tokio::time::Sleep;
+
+/// Coalesces the updates of `inner` that arrive within `window` of the first one and
+/// yields only the latest at the end of the window. Errors pass straight through. With
+/// no window every update is yielded as it arrives.
+pub(crate) struct Conflate<S, T> {
+    inner: S,
+    window: Option<Duration>,
+    pending: Option<T>,
+    timer: Option<Pin<Box<Sleep>>>,
+    done: bool,
+}
+
+impl<S, T> Conflate<S, T> {
+    pub(crate) fn new(inner: S, window: Option<Duration>) -> Self {
+        Conflate {
+            inner,
+            window,
+            pending: None,
+            timer: None,
+            done: false,
+        }
+    }
+
+    pub(crate) fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    pub(crate) fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S, T, E> Stream for Conflate<S, T>
+where
+    S: Stream<Item = Result<T, E>> + Unpin,
+    T: Unpin,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let window = match this.window {
+            Some(window) => window,
+            None => return Pin::new(&mut this.inner).poll_next(cx),
+        };
+
+        while !this.done {
+            match Pin::new(&mut this.inner).poll_next(cx) {
+                Poll::Ready(Some(Ok(item))) => {
+                    if this.timer.is_none() {
+                        this.timer = Some(Box::pin(tokio::time::sleep(window)));
+                    }
+                    this.pending = Some(item);
+                }
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => this.done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        if this.done {
+            this.timer = None;
+            return Poll::Ready(this.pending.take().map(Ok));
+        }
+        match this.timer.as_mut().map(|timer| timer.as_mut().poll(cx)) {
+            Some(Poll::Ready(())) => {
+                this.timer = None;
+                Poll::Ready(this.pending.take().map(Ok))
+            }
+            _ => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+    use tokio::sync::mpsc;
+
+    type Pumped = Pin<Box<dyn Stream<Item = Result<u32, ()>> + Send>>;
+
+    fn pumped() -> (mpsc::UnboundedSender<Result<u32, ()>>, Pumped) {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let stream = futures_util::stream::poll_fn(move |cx| receiver.poll_recv(cx));
+        (sender, Box::pin(stream))
+    }
+
+    #[tokio::test]
+    async fn collapses_rapid_updates_into_the_latest() {
+        let (sender, stream) = pumped();
+        let mut conflated = Conflate::new(stream, Some(Duration::from_millis(100)));
+
+        for update in 1..=5 {
+            sender.send(Ok(update)).unwrap();
+        }
+        assert_eq!(conflated.next().await, Some(Ok(5)));
+
+        sender.send(Ok(6)).unwrap();
+        assert_eq!(conflated.next().await, Some(Ok(6)));
+
+        drop(sender);
+        assert_eq!(conflated.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn emits_pending_update_before_ending() {
+        let (sender, stream) = pumped();
+        let mut conflated = Conflate::new(stream, Some(Duration::from_secs(60)));
+
+        sender.send(Ok(1)).unwrap();
+        sender.send(Ok(2)).unwrap();
+        drop(sender);
+
+        assert_eq!(conflated.next().await, Some(Ok(2)));
+        assert_eq!(conflated.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn without_window_every_update_passes() {
+        let (sender, stream) = pumped();
+        let conflated = Conflate::new(stream, None);
+
+        for update in 1..=3 {
+            sender.send(Ok(update)).unwrap();
+        }
+        sender.send(Err(())).unwrap();
+        drop(sender);
+
+        let updates: Vec<Result<u32, ()>> = conflated.collect().await;
+        assert_eq!(updates, vec![Ok(1), Ok(2), Ok(3), Err(())]);
+    }
+}
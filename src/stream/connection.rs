@@ -0,0 +1,1163 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use futures_util::{SinkExt, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::net::TcpStream;
+use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio::time::Instant;
+use tokio_tungstenite::tungstenite::{self, Message};
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+use crate::client::Client;
+use crate::error::RequestError;
+use crate::stream::event::{PushEvent, PushPayload};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+const HANDSHAKE_CHANNEL: &str = "/meta/handshake";
+const CONNECT_CHANNEL: &str = "/meta/connect";
+const SUBSCRIBE_CHANNEL: &str = "/meta/subscribe";
+const UNSUBSCRIBE_CHANNEL: &str = "/meta/unsubscribe";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CometdMessage {
+    pub channel: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub successful: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subscription: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supported_connection_types: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub advice: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The server's advice on how often to send connects and how long it holds them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Advice {
+    interval: Duration,
+    timeout: Duration,
+}
+
+impl Default for Advice {
+    fn default() -> Self {
+        Advice {
+            interval: Duration::ZERO,
+            timeout: Duration::from_millis(60000),
+        }
+    }
+}
+
+impl Advice {
+    /// Advice messages may only carry the fields that changed.
+    fn update(&mut self, advice: &Value) {
+        if let Some(interval) = advice["interval"].as_u64() {
+            self.interval = Duration::from_millis(interval);
+        }
+        if let Some(timeout) = advice["timeout"].as_u64() {
+            self.timeout = Duration::from_millis(timeout);
+        }
+    }
+}
+
+struct Session {
+    client_id: String,
+    next_id: u64,
+    advice: Advice,
+}
+
+impl Session {
+    fn message(&mut self, channel: &str) -> CometdMessage {
+        self.next_id += 1;
+        CometdMessage {
+            channel: String::from(channel),
+            id: Some(self.next_id.to_string()),
+            client_id: Some(self.client_id.clone()),
+            ..Default::default()
+        }
+    }
+
+    fn connect(&mut self) -> CometdMessage {
+        CometdMessage {
+            connection_type: Some(String::from("websocket")),
+            ..self.message(CONNECT_CHANNEL)
+        }
+    }
+
+    fn subscribe(&mut self, channel: &str) -> CometdMessage {
+        CometdMessage {
+            subscription: Some(String::from(channel)),
+            ..self.message(SUBSCRIBE_CHANNEL)
+        }
+    }
+
+    fn unsubscribe(&mut self, channel: &str) -> CometdMessage {
+        CometdMessage {
+            subscription: Some(String::from(channel)),
+            ..self.message(UNSUBSCRIBE_CHANNEL)
+        }
+    }
+}
+
+enum Delivery {
+    Data(Value),
+    Denied(String),
+    Unauthenticated,
+    Closed,
+}
+
+enum Command {
+    Subscribe {
+        channel: String,
+        subscriber: Subscriber,
+    },
+    Unsubscribe {
+        channel: String,
+        id: u64,
+    },
+    UnsubscribeChannel {
+        channel: String,
+    },
+    ActiveSubscriptions {
+        reply: oneshot::Sender<Vec<String>>,
+    },
+    Close {
+        done: oneshot::Sender<()>,
+    },
+}
+
+/// Default number of updates a subscription buffers before newer ones are dropped.
+pub const DEFAULT_BUFFER_SIZE: usize = 1024;
+
+/// Shared between a `Subscription` and the connection task to bound the updates that
+/// are waiting to be read.
+#[derive(Debug)]
+struct Buffer {
+    capacity: usize,
+    queued: AtomicUsize,
+    lagged: AtomicU64,
+}
+
+struct Subscriber {
+    id: u64,
+    sender: mpsc::UnboundedSender<Delivery>,
+    buffer: Arc<Buffer>,
+}
+
+impl Subscriber {
+    fn deliver(&self, data: Value) {
+        // Counted before sending, so the subscription can't take the update off the count
+        // before it was put on.
+        if self.buffer.queued.fetch_add(1, Ordering::AcqRel) >= self.buffer.capacity {
+            self.buffer.queued.fetch_sub(1, Ordering::AcqRel);
+            self.buffer.lagged.fetch_add(1, Ordering::AcqRel);
+            return;
+        }
+        if self.sender.send(Delivery::Data(data)).is_err() {
+            self.buffer.queued.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+}
+
+/// How a `PushConnection` recovers from a dropped socket. Between attempts it waits
+/// `initial_backoff`, doubling after every failed attempt up to `max_backoff`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconnectPolicy {
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        ReconnectPolicy {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PushOptions {
+    reconnect: Option<ReconnectPolicy>,
+    buffer_size: usize,
+    max_network_delay: Duration,
+}
+
+impl Default for PushOptions {
+    fn default() -> Self {
+        PushOptions {
+            reconnect: None,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+            max_network_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl PushOptions {
+    /// Re-handshake and re-subscribe every active channel when the socket drops.
+    pub fn reconnect(self, policy: ReconnectPolicy) -> Self {
+        Self {
+            reconnect: Some(policy),
+            ..self
+        }
+    }
+
+    /// Updates each subscription holds for a slow consumer, see `Subscription`.
+    pub fn buffer_size(self, buffer_size: usize) -> Self {
+        Self {
+            buffer_size: buffer_size.max(1),
+            ..self
+        }
+    }
+
+    /// How long past the advised timeout to wait for a connect reply before the
+    /// connection is considered dead.
+    pub fn max_network_delay(self, max_network_delay: Duration) -> Self {
+        Self {
+            max_network_delay,
+            ..self
+        }
+    }
+}
+
+/// Reported on `PushConnection::events`. Updates published between `Disconnected` and
+/// `Reconnected` are lost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    Disconnected,
+    Reconnected,
+    /// The session is no longer valid. Every subscription gets a `NotAuthenticatedError`
+    /// and ends, authenticate again and open a new connection.
+    AuthenticationExpired,
+    /// Reconnecting failed `max_retries` times in a row. Every subscription ends.
+    GaveUp,
+}
+
+/// State the connection task publishes to its `PushConnection`.
+#[derive(Debug)]
+struct Shared {
+    client_id: Mutex<String>,
+    next_subscriber_id: AtomicU64,
+    last_heartbeat: Mutex<Option<DateTime<Utc>>>,
+    events: broadcast::Sender<ConnectionEvent>,
+}
+
+/// A CometD session on Avanza's push channel. The socket is owned by a background task
+/// which stops when the connection is dropped or the server closes it, unless
+/// reconnection is enabled in `PushOptions`.
+///
+/// All subscriptions share the one socket. Subscribing to a channel that is already
+/// subscribed shares the server subscription, and each stream gets every update.
+#[derive(Debug)]
+pub struct PushConnection {
+    commands: mpsc::UnboundedSender<Command>,
+    shared: Arc<Shared>,
+    buffer_size: usize,
+}
+
+/// Updates for one subscribed channel as a `Stream`, which ends once the connection is
+/// gone. Dropping it unsubscribes.
+///
+/// At most `PushOptions::buffer_size` updates are held for a consumer that falls behind.
+/// Further updates are dropped, and the next item is a `PushLagged` error with the number
+/// of updates lost before the buffered ones are yielded.
+pub struct Subscription<T> {
+    channel: String,
+    id: u64,
+    commands: mpsc::UnboundedSender<Command>,
+    receiver: mpsc::UnboundedReceiver<Delivery>,
+    buffer: Arc<Buffer>,
+    subscribed: bool,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T: PushPayload> Subscription<T> {
+    pub fn channel(&self) -> &str {
+        &self.channel
+    }
+
+    /// A subscription the server refuses, e.g. for an account the session doesn't own,
+    /// yields a single `PushSubscriptionDenied` error and then ends.
+    pub async fn next(&mut self) -> Option<Result<T, RequestError>> {
+        std::future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+
+    fn decode(&self, data: Value) -> Result<T, RequestError> {
+        let event = PushEvent::parse(&self.channel, data)?;
+        T::from_event(event).ok_or_else(|| {
+            RequestError::PushProtocolError(format!("unexpected payload on {}", self.channel))
+        })
+    }
+
+    /// Tells the server to stop pushing updates for this channel, unless another
+    /// subscription on the same connection still wants them.
+    pub fn unsubscribe(mut self) -> Result<(), RequestError> {
+        self.subscribed = false;
+        self.commands
+            .send(Command::Unsubscribe {
+                channel: self.channel.clone(),
+                id: self.id,
+            })
+            .map_err(|_| RequestError::PushProtocolError(String::from("connection closed")))
+    }
+}
+
+impl<T: PushPayload> Stream for Subscription<T> {
+    type Item = Result<T, RequestError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let lagged = this.buffer.lagged.swap(0, Ordering::AcqRel);
+        if lagged > 0 {
+            return Poll::Ready(Some(Err(RequestError::PushLagged(lagged))));
+        }
+        let delivery = match this.receiver.poll_recv(cx) {
+            Poll::Ready(Some(delivery)) => delivery,
+            Poll::Ready(None) => return Poll::Ready(None),
+            Poll::Pending => return Poll::Pending,
+        };
+        Poll::Ready(Some(match delivery {
+            Delivery::Data(data) => {
+                this.buffer.queued.fetch_sub(1, Ordering::AcqRel);
+                this.decode(data)
+            }
+            Delivery::Denied(error) => Err(RequestError::PushSubscriptionDenied(error)),
+            Delivery::Unauthenticated => Err(RequestError::NotAuthenticatedError()),
+            Delivery::Closed => Err(RequestError::PushConnectionClosed()),
+        }))
+    }
+}
+
+impl<T> Drop for Subscription<T> {
+    fn drop(&mut self) {
+        if self.subscribed {
+            let _ = self.commands.send(Command::Unsubscribe {
+                channel: self.channel.clone(),
+                id: self.id,
+            });
+        }
+    }
+}
+
+async fn send(socket: &mut Socket, messages: &[CometdMessage]) -> Result<(), RequestError> {
+    let text = serde_json::to_string(messages)?;
+    socket.send(Message::Text(text)).await?;
+    Ok(())
+}
+
+fn parse_frame(message: Message) -> Result<Vec<CometdMessage>, RequestError> {
+    match message {
+        Message::Text(text) => Ok(serde_json::from_str::<Vec<CometdMessage>>(&text)?),
+        _ => Ok(Vec::new()),
+    }
+}
+
+fn is_auth_error(error: &str) -> bool {
+    error.starts_with("401") || error.starts_with("403")
+}
+
+async fn handshake(socket: &mut Socket, subscription_id: &str) -> Result<Session, RequestError> {
+    let request = CometdMessage {
+        channel: String::from(HANDSHAKE_CHANNEL),
+        id: Some(String::from("0")),
+        version: Some(String::from("1.0")),
+        minimum_version: Some(String::from("1.0")),
+        supported_connection_types: Some(vec![String::from("websocket")]),
+        advice: Some(json!({"timeout": 60000, "interval": 0})),
+        ext: Some(json!({ "subscriptionId": subscription_id })),
+        ..Default::default()
+    };
+    send(socket, &[request]).await?;
+
+    while let Some(frame) = socket.next().await {
+        for message in parse_frame(frame?)? {
+            if message.channel != HANDSHAKE_CHANNEL {
+                continue;
+            }
+            return match (message.successful, message.client_id, message.error) {
+                (Some(true), Some(client_id), _) => {
+                    let mut advice = Advice::default();
+                    if let Some(value) = &message.advice {
+                        advice.update(value);
+                    }
+                    Ok(Session {
+                        client_id,
+                        next_id: 0,
+                        advice,
+                    })
+                }
+                (_, _, Some(error)) if is_auth_error(&error) => {
+                    Err(RequestError::NotAuthenticatedError())
+                }
+                (_, _, error) => Err(RequestError::PushProtocolError(
+                    error.unwrap_or_else(|| String::from("handshake failed")),
+                )),
+            };
+        }
+    }
+    Err(RequestError::PushProtocolError(String::from(
+        "connection closed during handshake",
+    )))
+}
+
+struct Connector {
+    url: String,
+    subscription_id: String,
+}
+
+impl Connector {
+    async fn open(&self) -> Result<(Socket, Session), RequestError> {
+        let mut socket = match connect_async(self.url.as_str()).await {
+            Ok((socket, _)) => socket,
+            Err(tungstenite::Error::Http(response))
+                if response.status() == 401 || response.status() == 403 =>
+            {
+                return Err(RequestError::NotAuthenticatedError());
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let mut session = handshake(&mut socket, &self.subscription_id).await?;
+        let connect = session.connect();
+        send(&mut socket, &[connect]).await?;
+        Ok((socket, session))
+    }
+
+    async fn reconnect(&self, policy: &ReconnectPolicy) -> Result<(Socket, Session), RequestError> {
+        let mut last_error = RequestError::PushProtocolError(String::from("no reconnect attempts"));
+        for attempt in 0..policy.max_retries {
+            tokio::time::sleep(policy.backoff(attempt)).await;
+            match self.open().await {
+                Ok(connection) => return Ok(connection),
+                Err(RequestError::NotAuthenticatedError()) => {
+                    return Err(RequestError::NotAuthenticatedError())
+                }
+                Err(e) => last_error = e,
+            }
+        }
+        Err(last_error)
+    }
+}
+
+/// Subscribers by channel. The server is subscribed once per channel and every
+/// subscriber of it gets a copy of each update.
+type Subscriptions = HashMap<String, Vec<Subscriber>>;
+
+enum Exit {
+    Closed,
+    Disconnected,
+}
+
+/// Tracks the outstanding connect. The server replies to it after at most the advised
+/// timeout, and the next connect is sent the advised interval after that.
+struct Heartbeat {
+    sent: Option<Instant>,
+    next: Instant,
+}
+
+impl Heartbeat {
+    fn sent_now() -> Self {
+        let now = Instant::now();
+        Heartbeat {
+            sent: Some(now),
+            next: now,
+        }
+    }
+
+    fn deadline(&self, advice: &Advice, max_network_delay: Duration) -> Instant {
+        match self.sent {
+            Some(sent) => sent + advice.timeout + max_network_delay,
+            None => self.next,
+        }
+    }
+}
+
+async fn pump(
+    socket: &mut Socket,
+    session: &mut Session,
+    commands: &mut mpsc::UnboundedReceiver<Command>,
+    subscriptions: &mut Subscriptions,
+    shared: &Shared,
+    max_network_delay: Duration,
+) -> Exit {
+    let mut heartbeat = Heartbeat::sent_now();
+    loop {
+        let deadline = heartbeat.deadline(&session.advice, max_network_delay);
+        tokio::select! {
+            command = commands.recv() => match command {
+                Some(Command::Subscribe { channel, subscriber }) => {
+                    let subscribers = subscriptions.entry(channel.clone()).or_default();
+                    subscribers.push(subscriber);
+                    if subscribers.len() == 1 {
+                        let message = session.subscribe(&channel);
+                        if send(socket, &[message]).await.is_err() {
+                            return Exit::Disconnected;
+                        }
+                    }
+                }
+                Some(Command::Unsubscribe { channel, id }) => {
+                    let subscribers = match subscriptions.get_mut(&channel) {
+                        Some(subscribers) => subscribers,
+                        None => continue,
+                    };
+                    subscribers.retain(|subscriber| subscriber.id != id);
+                    if subscribers.is_empty() {
+                        subscriptions.remove(&channel);
+                        let message = session.unsubscribe(&channel);
+                        if send(socket, &[message]).await.is_err() {
+                            return Exit::Disconnected;
+                        }
+                    }
+                }
+                Some(Command::UnsubscribeChannel { channel }) => {
+                    if subscriptions.remove(&channel).is_some() {
+                        let message = session.unsubscribe(&channel);
+                        if send(socket, &[message]).await.is_err() {
+                            return Exit::Disconnected;
+                        }
+                    }
+                }
+                Some(Command::ActiveSubscriptions { reply }) => {
+                    let mut channels: Vec<String> = subscriptions.keys().cloned().collect();
+                    channels.sort();
+                    let _ = reply.send(channels);
+                }
+                Some(Command::Close { done }) => {
+                    for (_, subscribers) in subscriptions.drain() {
+                        for subscriber in subscribers {
+                            let _ = subscriber.sender.send(Delivery::Closed);
+                        }
+                    }
+                    let _ = socket.close(None).await;
+                    let _ = done.send(());
+                    return Exit::Closed;
+                }
+                None => {
+                    let _ = socket.close(None).await;
+                    return Exit::Closed;
+                }
+            },
+            _ = tokio::time::sleep_until(deadline) => {
+                if heartbeat.sent.is_some() {
+                    // The server stopped answering, it may well have stopped delivering too.
+                    return Exit::Disconnected;
+                }
+                let connect = session.connect();
+                if send(socket, &[connect]).await.is_err() {
+                    return Exit::Disconnected;
+                }
+                heartbeat = Heartbeat::sent_now();
+            },
+            frame = socket.next() => {
+                let messages = match frame {
+                    Some(Ok(frame)) => match parse_frame(frame) {
+                        Ok(messages) => messages,
+                        Err(_) => continue,
+                    },
+                    _ => return Exit::Disconnected,
+                };
+                for message in messages {
+                    if message.channel == CONNECT_CHANNEL {
+                        if let Some(advice) = &message.advice {
+                            session.advice.update(advice);
+                        }
+                        if message.successful == Some(false) {
+                            return Exit::Disconnected;
+                        }
+                        *shared.last_heartbeat.lock().unwrap() = Some(Utc::now());
+                        heartbeat = Heartbeat {
+                            sent: None,
+                            next: Instant::now() + session.advice.interval,
+                        };
+                    } else if message.channel == SUBSCRIBE_CHANNEL {
+                        if message.successful == Some(false) {
+                            if let Some(subscribers) = message
+                                .subscription
+                                .and_then(|channel| subscriptions.remove(&channel))
+                            {
+                                let error = message
+                                    .error
+                                    .unwrap_or_else(|| String::from("subscription denied"));
+                                for subscriber in subscribers {
+                                    let _ = subscriber.sender.send(Delivery::Denied(error.clone()));
+                                }
+                            }
+                        }
+                    } else if let (Some(subscribers), Some(data)) =
+                        (subscriptions.get(&message.channel), message.data)
+                    {
+                        for subscriber in subscribers {
+                            subscriber.deliver(data.clone());
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+async fn run(
+    connector: Connector,
+    options: PushOptions,
+    mut socket: Socket,
+    mut session: Session,
+    mut commands: mpsc::UnboundedReceiver<Command>,
+    shared: Arc<Shared>,
+) {
+    let mut subscriptions = Subscriptions::new();
+
+    loop {
+        let exit = pump(
+            &mut socket,
+            &mut session,
+            &mut commands,
+            &mut subscriptions,
+            &shared,
+            options.max_network_delay,
+        )
+        .await;
+        if let Exit::Closed = exit {
+            return;
+        }
+        let _ = shared.events.send(ConnectionEvent::Disconnected);
+
+        let policy = match &options.reconnect {
+            Some(policy) => policy,
+            None => return,
+        };
+        match connector.reconnect(policy).await {
+            Ok((new_socket, new_session)) => {
+                socket = new_socket;
+                session = new_session;
+                *shared.client_id.lock().unwrap() = session.client_id.clone();
+
+                let messages: Vec<CometdMessage> = subscriptions
+                    .keys()
+                    .map(|channel| session.subscribe(channel))
+                    .collect();
+                if !messages.is_empty() && send(&mut socket, &messages).await.is_err() {
+                    continue;
+                }
+                let _ = shared.events.send(ConnectionEvent::Reconnected);
+            }
+            Err(RequestError::NotAuthenticatedError()) => {
+                for subscriber in subscriptions.values().flatten() {
+                    let _ = subscriber.sender.send(Delivery::Unauthenticated);
+                }
+                let _ = shared.events.send(ConnectionEvent::AuthenticationExpired);
+                return;
+            }
+            Err(_) => {
+                let _ = shared.events.send(ConnectionEvent::GaveUp);
+                return;
+            }
+        }
+    }
+}
+
+impl PushConnection {
+    /// The CometD client id, which changes when the connection is re-established.
+    pub fn client_id(&self) -> String {
+        self.shared.client_id.lock().unwrap().clone()
+    }
+
+    /// Connection state changes, mainly useful with reconnection enabled.
+    pub fn events(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.shared.events.subscribe()
+    }
+
+    /// When the server last answered a connect, `None` until it first has. A healthy
+    /// connection hears back at least once per advised timeout.
+    pub fn last_heartbeat(&self) -> Option<DateTime<Utc>> {
+        *self.shared.last_heartbeat.lock().unwrap()
+    }
+
+    /// Channels the server is currently subscribed to, sorted.
+    pub async fn active_subscriptions(&self) -> Vec<String> {
+        let (reply, channels) = oneshot::channel();
+        if self
+            .commands
+            .send(Command::ActiveSubscriptions { reply })
+            .is_err()
+        {
+            return Vec::new();
+        }
+        channels.await.unwrap_or_default()
+    }
+
+    /// Unsubscribes from `channel` and ends every stream on it. The channel is not
+    /// resubscribed after a reconnect. Unknown channels are ignored.
+    pub fn unsubscribe(&self, channel: &str) -> Result<(), RequestError> {
+        let _ = self.commands.send(Command::UnsubscribeChannel {
+            channel: String::from(channel),
+        });
+        Ok(())
+    }
+
+    /// Closes the socket. Every stream yields a `PushConnectionClosed` error and ends.
+    pub async fn close(self) {
+        let (done, closed) = oneshot::channel();
+        if self.commands.send(Command::Close { done }).is_ok() {
+            let _ = closed.await;
+        }
+    }
+
+    /// Subscribes to any channel, including ones without a typed `subscribe_*` method,
+    /// which then arrive as `PushEvent::Unknown`.
+    pub fn subscribe_channel(
+        &self,
+        channel: &str,
+    ) -> Result<Subscription<PushEvent>, RequestError> {
+        self.subscribe(String::from(channel))
+    }
+
+    pub(crate) fn subscribe<T: PushPayload>(
+        &self,
+        channel: String,
+    ) -> Result<Subscription<T>, RequestError> {
+        let id = self
+            .shared
+            .next_subscriber_id
+            .fetch_add(1, Ordering::Relaxed);
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let buffer = Arc::new(Buffer {
+            capacity: self.buffer_size,
+            queued: AtomicUsize::new(0),
+            lagged: AtomicU64::new(0),
+        });
+        self.commands
+            .send(Command::Subscribe {
+                channel: channel.clone(),
+                subscriber: Subscriber {
+                    id,
+                    sender,
+                    buffer: buffer.clone(),
+                },
+            })
+            .map_err(|_| RequestError::PushProtocolError(String::from("connection closed")))?;
+        Ok(Subscription {
+            channel,
+            id,
+            commands: self.commands.clone(),
+            receiver,
+            buffer,
+            subscribed: true,
+            marker: PhantomData,
+        })
+    }
+}
+
+impl Client {
+    /// Opens a push connection and performs the CometD handshake using the push
+    /// subscription id from authentication.
+    pub async fn connect_push(&self) -> Result<PushConnection, RequestError> {
+        self.connect_push_with(PushOptions::default()).await
+    }
+
+    pub async fn connect_push_with(
+        &self,
+        options: PushOptions,
+    ) -> Result<PushConnection, RequestError> {
+        if !self.is_authenticated() {
+            return Err(RequestError::NotAuthenticatedError());
+        }
+
+        let connector = Connector {
+            url: self.push_url.clone(),
+            subscription_id: self.push_subscription_id(),
+        };
+        let (socket, session) = connector.open().await?;
+
+        let (events, _) = broadcast::channel(16);
+        let shared = Arc::new(Shared {
+            client_id: Mutex::new(session.client_id.clone()),
+            next_subscriber_id: AtomicU64::new(0),
+            last_heartbeat: Mutex::new(None),
+            events,
+        });
+        let (commands, receiver) = mpsc::unbounded_channel();
+        let buffer_size = options.buffer_size;
+        tokio::spawn(run(
+            connector,
+            options,
+            socket,
+            session,
+            receiver,
+            shared.clone(),
+        ));
+
+        Ok(PushConnection {
+            commands,
+            shared,
+            buffer_size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stream::mock_server::MockPushServer;
+    use crate::types::OrderbookId;
+
+    use super::*;
+    use crate::test_support::authenticated_client_with;
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::MockServer;
+
+    async fn connect(
+        mock_server: &MockServer,
+        push_server: &MockPushServer,
+        options: PushOptions,
+    ) -> PushConnection {
+        let client =
+            authenticated_client_with(mock_server, Client::builder().push_url(push_server.url()))
+                .await;
+        assert_ok!(client.connect_push_with(options).await)
+    }
+
+    fn reconnecting() -> PushOptions {
+        PushOptions::default().reconnect(ReconnectPolicy {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(50),
+        })
+    }
+
+    fn quote(last_price: f64) -> Value {
+        quote_for("5269", last_price)
+    }
+
+    fn quote_for(orderbook_id: &str, last_price: f64) -> Value {
+        json!({"orderbookId": orderbook_id, "lastPrice": last_price, "updated": 1760433600000i64})
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let policy = ReconnectPolicy {
+            max_retries: 10,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(1),
+        };
+
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(800));
+        assert_eq!(policy.backoff(4), Duration::from_secs(1));
+        assert_eq!(policy.backoff(40), Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn resubscribes_after_reconnect() {
+        let mock_server = MockServer::start().await;
+        let push_server = MockPushServer::start().await;
+
+        let connection = connect(&mock_server, &push_server, reconnecting()).await;
+        let mut events = connection.events();
+        let mut quotes = assert_ok!(connection.subscribe_quotes(&OrderbookId::from("5269")));
+        push_server.wait_for_subscription("/quotes/5269").await;
+
+        push_server.drop_connections();
+
+        assert_eq!(
+            assert_ok!(events.recv().await),
+            ConnectionEvent::Disconnected
+        );
+        assert_eq!(
+            assert_ok!(events.recv().await),
+            ConnectionEvent::Reconnected
+        );
+        push_server.wait_for_subscribes("/quotes/5269", 2).await;
+        assert_eq!(push_server.received(HANDSHAKE_CHANNEL).len(), 2);
+
+        push_server.publish("/quotes/5269", quote(254.1));
+        let quote = assert_ok!(quotes.next().await.expect("stream ended"));
+        assert_eq!(quote.last_price, Some(254.1));
+    }
+
+    #[tokio::test]
+    async fn surfaces_expired_session_instead_of_retrying() {
+        let mock_server = MockServer::start().await;
+        let push_server = MockPushServer::start().await;
+
+        let connection = connect(&mock_server, &push_server, reconnecting()).await;
+        let mut events = connection.events();
+        let mut quotes = assert_ok!(connection.subscribe_quotes(&OrderbookId::from("5269")));
+        push_server.wait_for_subscription("/quotes/5269").await;
+
+        push_server.reject_handshakes();
+        push_server.drop_connections();
+
+        assert_eq!(
+            assert_ok!(events.recv().await),
+            ConnectionEvent::Disconnected
+        );
+        assert_eq!(
+            assert_ok!(events.recv().await),
+            ConnectionEvent::AuthenticationExpired
+        );
+        assert!(matches!(
+            assert_err!(quotes.next().await.expect("stream ended")),
+            RequestError::NotAuthenticatedError()
+        ));
+        assert!(quotes.next().await.is_none());
+        assert_eq!(push_server.received(HANDSHAKE_CHANNEL).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn works_with_stream_combinators_and_unsubscribes_on_drop() {
+        let mock_server = MockServer::start().await;
+        let push_server = MockPushServer::start().await;
+
+        let connection = connect(&mock_server, &push_server, PushOptions::default()).await;
+        let quotes = assert_ok!(connection.subscribe_quotes(&OrderbookId::from("5269")));
+        push_server.wait_for_subscription("/quotes/5269").await;
+
+        for price in [254.0, 254.1, 254.2] {
+            push_server.publish("/quotes/5269", quote(price));
+        }
+
+        let prices: Vec<Option<f64>> = quotes
+            .take(2)
+            .map(|update| update.expect("bad update").last_price)
+            .collect()
+            .await;
+        assert_eq!(prices, vec![Some(254.0), Some(254.1)]);
+
+        push_server.wait_for_unsubscription("/quotes/5269").await;
+    }
+
+    #[tokio::test]
+    async fn reports_dropped_updates_when_consumer_lags() {
+        let mock_server = MockServer::start().await;
+        let push_server = MockPushServer::start().await;
+
+        let options = PushOptions::default().buffer_size(2);
+        let connection = connect(&mock_server, &push_server, options).await;
+        let mut quotes = assert_ok!(connection.subscribe_quotes(&OrderbookId::from("5269")));
+        push_server.wait_for_subscription("/quotes/5269").await;
+
+        for price in [254.0, 254.1, 254.2, 254.3, 254.4] {
+            push_server.publish("/quotes/5269", quote(price));
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        assert!(matches!(
+            quotes.next().await,
+            Some(Err(RequestError::PushLagged(3)))
+        ));
+        let first = assert_ok!(quotes.next().await.expect("stream ended"));
+        let second = assert_ok!(quotes.next().await.expect("stream ended"));
+        assert_eq!(first.last_price, Some(254.0));
+        assert_eq!(second.last_price, Some(254.1));
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), quotes.next())
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn sends_connects_at_the_advised_interval() {
+        let mock_server = MockServer::start().await;
+        let push_server = MockPushServer::start().await;
+        push_server.answer_connects(json!({"interval": 100, "timeout": 30000}));
+
+        let connection = connect(&mock_server, &push_server, PushOptions::default()).await;
+        tokio::time::sleep(Duration::from_millis(550)).await;
+
+        let connects = push_server.received(CONNECT_CHANNEL).len();
+        assert!((4..=7).contains(&connects), "sent {} connects", connects);
+        assert!(connection.last_heartbeat().is_some());
+    }
+
+    #[tokio::test]
+    async fn missed_heartbeat_reconnects() {
+        let mock_server = MockServer::start().await;
+        let push_server = MockPushServer::start().await;
+        push_server.advise(json!({"interval": 0, "timeout": 100}));
+
+        let options = reconnecting().max_network_delay(Duration::from_millis(50));
+        let connection = connect(&mock_server, &push_server, options).await;
+        let mut events = connection.events();
+        assert_eq!(connection.last_heartbeat(), None);
+
+        assert_eq!(
+            assert_ok!(events.recv().await),
+            ConnectionEvent::Disconnected
+        );
+        assert_eq!(
+            assert_ok!(events.recv().await),
+            ConnectionEvent::Reconnected
+        );
+        assert_eq!(push_server.received(HANDSHAKE_CHANNEL).len(), 2);
+    }
+
+    #[test]
+    fn advice_keeps_fields_it_does_not_mention() {
+        let mut advice = Advice::default();
+        advice.update(&json!({"interval": 2000}));
+        assert_eq!(advice.interval, Duration::from_secs(2));
+        assert_eq!(advice.timeout, Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn routes_each_channel_to_its_own_stream() {
+        let mock_server = MockServer::start().await;
+        let push_server = MockPushServer::start().await;
+
+        let connection = connect(&mock_server, &push_server, PushOptions::default()).await;
+        let ids = ["5269", "5247", "5361"];
+        let mut streams = Vec::new();
+        for id in ids {
+            streams.push(assert_ok!(
+                connection.subscribe_quotes(&OrderbookId::from(id))
+            ));
+            push_server
+                .wait_for_subscription(&format!("/quotes/{}", id))
+                .await;
+        }
+
+        for round in 0..3 {
+            for (i, id) in ids.iter().enumerate() {
+                let price = (i * 100 + round) as f64;
+                push_server.publish(&format!("/quotes/{}", id), quote_for(id, price));
+            }
+        }
+
+        for (i, stream) in streams.iter_mut().enumerate() {
+            for round in 0..3 {
+                let update = assert_ok!(stream.next().await.expect("stream ended"));
+                assert_eq!(update.orderbook_id, OrderbookId::from(ids[i]));
+                assert_eq!(update.last_price, Some((i * 100 + round) as f64));
+            }
+        }
+        assert_eq!(push_server.received(HANDSHAKE_CHANNEL).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn same_channel_twice_shares_the_subscription() {
+        let mock_server = MockServer::start().await;
+        let push_server = MockPushServer::start().await;
+
+        let connection = connect(&mock_server, &push_server, PushOptions::default()).await;
+        let first = assert_ok!(connection.subscribe_quotes(&OrderbookId::from("5269")));
+        let mut second = assert_ok!(connection.subscribe_quotes(&OrderbookId::from("5269")));
+        push_server.wait_for_subscription("/quotes/5269").await;
+
+        drop(first);
+        push_server.publish("/quotes/5269", quote(254.0));
+
+        let update = assert_ok!(second.next().await.expect("stream ended"));
+        assert_eq!(update.last_price, Some(254.0));
+        assert_eq!(push_server.received(SUBSCRIBE_CHANNEL).len(), 1);
+        assert!(push_server.received(UNSUBSCRIBE_CHANNEL).is_empty());
+
+        drop(second);
+        push_server.wait_for_unsubscription("/quotes/5269").await;
+    }
+
+    #[tokio::test]
+    async fn lists_and_unsubscribes_channels() {
+        let mock_server = MockServer::start().await;
+        let push_server = MockPushServer::start().await;
+
+        let connection = connect(&mock_server, &push_server, reconnecting()).await;
+        let mut events = connection.events();
+        let mut volvo = assert_ok!(connection.subscribe_quotes(&OrderbookId::from("5269")));
+        let _ericsson = assert_ok!(connection.subscribe_quotes(&OrderbookId::from("5247")));
+        push_server.wait_for_subscription("/quotes/5269").await;
+        push_server.wait_for_subscription("/quotes/5247").await;
+
+        assert_eq!(
+            connection.active_subscriptions().await,
+            vec![String::from("/quotes/5247"), String::from("/quotes/5269")]
+        );
+
+        assert_ok!(connection.unsubscribe("/quotes/5269"));
+        assert_ok!(connection.unsubscribe("/quotes/5269"));
+        assert_ok!(connection.unsubscribe("/quotes/unknown"));
+        push_server.wait_for_unsubscription("/quotes/5269").await;
+        assert!(volvo.next().await.is_none());
+        assert_eq!(
+            connection.active_subscriptions().await,
+            vec![String::from("/quotes/5247")]
+        );
+        assert_eq!(push_server.received(UNSUBSCRIBE_CHANNEL).len(), 1);
+
+        push_server.drop_connections();
+        assert_eq!(
+            assert_ok!(events.recv().await),
+            ConnectionEvent::Disconnected
+        );
+        assert_eq!(
+            assert_ok!(events.recv().await),
+            ConnectionEvent::Reconnected
+        );
+        push_server.wait_for_subscribes("/quotes/5247", 2).await;
+        let volvo_subscribes = push_server
+            .received(SUBSCRIBE_CHANNEL)
+            .iter()
+            .filter(|m| m["subscription"] == "/quotes/5269")
+            .count();
+        assert_eq!(volvo_subscribes, 1);
+    }
+
+    #[tokio::test]
+    async fn close_ends_every_stream() {
+        let mock_server = MockServer::start().await;
+        let push_server = MockPushServer::start().await;
+
+        let connection = connect(&mock_server, &push_server, PushOptions::default()).await;
+        let mut quotes = assert_ok!(connection.subscribe_quotes(&OrderbookId::from("5269")));
+        let mut deals = assert_ok!(connection.subscribe_channel("/deals/1234"));
+        push_server.wait_for_subscription("/deals/1234").await;
+
+        connection.close().await;
+
+        assert!(matches!(
+            quotes.next().await,
+            Some(Err(RequestError::PushConnectionClosed()))
+        ));
+        assert!(quotes.next().await.is_none());
+        assert!(matches!(
+            deals.next().await,
+            Some(Err(RequestError::PushConnectionClosed()))
+        ));
+        assert!(deals.next().await.is_none());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::error::RequestError;
+use crate::portfolio::deals_and_orders::{Deal, Order};
+use crate::stream::connection::{PushConnection, Subscription};
+use crate::types::AccountId;
+
+impl PushConnection {
+    /// Fills on the account as they happen.
+    pub fn subscribe_deals(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Subscription<Deal>, RequestError> {
+        self.subscribe(format!("/deals/{}", account_id))
+    }
+
+    /// The full order every time its state changes, e.g. accepted, partially filled, filled.
+    pub fn subscribe_orders(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Subscription<Order>, RequestError> {
+        self.subscribe(format!("/orders/{}", account_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::Client;
+    use crate::portfolio::deals_and_orders::OrderState;
+    use crate::stream::mock_server::MockPushServer;
+
+    use super::*;
+    use crate::test_support::authenticated_client_with;
+    use serde_json::json;
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::MockServer;
+
+    fn order(state: &str, filled_volume: u64) -> serde_json::Value {
+        json!({
+            "orderId": "o-1",
+            "accountId": "1234",
+            "orderbookId": "5269",
+            "side": "BUY",
+            "price": 250.0,
+            "volume": 100,
+            "filledVolume": filled_volume,
+            "state": state
+        })
+    }
+
+    #[tokio::test]
+    async fn can_follow_order_until_filled() {
+        let mock_server = MockServer::start().await;
+        let push_server = MockPushServer::start().await;
+        let client =
+            authenticated_client_with(&mock_server, Client::builder().push_url(push_server.url()))
+                .await;
+
+        let connection = assert_ok!(client.connect_push().await);
+        let account_id = AccountId::from("1234");
+        let mut orders = assert_ok!(connection.subscribe_orders(&account_id));
+        let mut deals = assert_ok!(connection.subscribe_deals(&account_id));
+        push_server.wait_for_subscription("/orders/1234").await;
+        push_server.wait_for_subscription("/deals/1234").await;
+
+        push_server.publish("/orders/1234", order("ACCEPTED", 0));
+        push_server.publish(
+            "/deals/1234",
+            json!({"dealId": "d-1", "orderId": "o-1", "accountId": "1234", "orderbookId": "5269", "side": "BUY", "price": 250.0, "volume": 40, "dealTime": 1791990000000i64}),
+        );
+        push_server.publish("/orders/1234", order("PARTIALLY_FILLED", 40));
+        push_server.publish("/orders/1234", order("FILLED", 100));
+
+        let states: Vec<(OrderState, u64)> = vec![
+            assert_ok!(orders.next().await.expect("stream ended")),
+            assert_ok!(orders.next().await.expect("stream ended")),
+            assert_ok!(orders.next().await.expect("stream ended")),
+        ]
+        .into_iter()
+        .map(|o| (o.state, o.filled_volume))
+        .collect();
+
+        assert_eq!(
+            states,
+            vec![
+                (OrderState::Accepted, 0),
+                (OrderState::PartiallyFilled, 40),
+                (OrderState::Filled, 100)
+            ]
+        );
+
+        let deal = assert_ok!(deals.next().await.expect("stream ended"));
+        assert_eq!(deal.order_id.as_deref(), Some("o-1"));
+        assert_eq!(deal.volume, 40);
+    }
+
+    #[tokio::test]
+    async fn foreign_account_is_an_error() {
+        let mock_server = MockServer::start().await;
+        let push_server = MockPushServer::start().await;
+        let client =
+            authenticated_client_with(&mock_server, Client::builder().push_url(push_server.url()))
+                .await;
+
+        push_server.deny_subscription("/orders/9999");
+
+        let connection = assert_ok!(client.connect_push().await);
+        let mut orders = assert_ok!(connection.subscribe_orders(&AccountId::from("9999")));
+
+        let denied = orders.next().await.expect("expected an error");
+        assert!(matches!(
+            assert_err!(denied),
+            RequestError::PushSubscriptionDenied(_)
+        ));
+        assert!(orders.next().await.is_none());
+    }
+}
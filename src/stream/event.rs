@@ -0,0 +1,201 @@
+use crate::market::order_depth::OrderDepth;
+use crate::market::trades::Trade;
+use crate::portfolio::deals_and_orders::{Deal, Order};
+use crate::stream::broker_trade_summary::BrokerTradeSummary;
+use crate::stream::positions::PositionsUpdate;
+use crate::stream::quotes::QuoteUpdate;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// A data message from the push channel, decoded by the prefix of its channel.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PushEvent {
+    Quote(QuoteUpdate),
+    OrderDepth(OrderDepth),
+    Trade(Trade),
+    Deal(Deal),
+    Order(Order),
+    Positions(PositionsUpdate),
+    BrokerTradeSummary(BrokerTradeSummary),
+    /// A channel this crate does not model yet, with the payload as sent.
+    Unknown {
+        channel: String,
+        raw: Value,
+    },
+}
+
+fn decode<T: DeserializeOwned>(
+    data: Value,
+    event: fn(T) -> PushEvent,
+) -> Result<PushEvent, serde_json::Error> {
+    serde_json::from_value(data).map(event)
+}
+
+impl PushEvent {
+    pub fn parse(channel: &str, data: Value) -> Result<PushEvent, serde_json::Error> {
+        let prefix = channel.trim_start_matches('/').split('/').next();
+        match prefix {
+            Some("quotes") => decode(data, PushEvent::Quote),
+            Some("orderdepths") => decode(data, PushEvent::OrderDepth),
+            Some("trades") => decode(data, PushEvent::Trade),
+            Some("deals") => decode(data, PushEvent::Deal),
+            Some("orders") => decode(data, PushEvent::Order),
+            Some("positions") => decode(data, PushEvent::Positions),
+            Some("brokertradesummary") => decode(data, PushEvent::BrokerTradeSummary),
+            _ => Ok(PushEvent::Unknown {
+                channel: String::from(channel),
+                raw: data,
+            }),
+        }
+    }
+}
+
+/// Payloads a `Subscription` can yield, picked out of the decoded `PushEvent`.
+pub trait PushPayload: Sized {
+    fn from_event(event: PushEvent) -> Option<Self>;
+}
+
+impl PushPayload for PushEvent {
+    fn from_event(event: PushEvent) -> Option<Self> {
+        Some(event)
+    }
+}
+
+macro_rules! push_payload {
+    ($variant:ident, $payload:ty) => {
+        impl PushPayload for $payload {
+            fn from_event(event: PushEvent) -> Option<Self> {
+                match event {
+                    PushEvent::$variant(payload) => Some(payload),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+push_payload!(Quote, QuoteUpdate);
+push_payload!(OrderDepth, OrderDepth);
+push_payload!(Trade, Trade);
+push_payload!(Deal, Deal);
+push_payload!(Order, Order);
+push_payload!(Positions, PositionsUpdate);
+push_payload!(BrokerTradeSummary, BrokerTradeSummary);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AccountId, OrderbookId, Side};
+    use serde_json::json;
+
+    fn parse_frame(frame: &str) -> PushEvent {
+        let messages: Vec<Value> = serde_json::from_str(frame).unwrap();
+        let message = messages[0].clone();
+        PushEvent::parse(
+            message["channel"].as_str().unwrap(),
+            message["data"].clone(),
+        )
+        .expect("failed to parse frame")
+    }
+
+    #[test]
+    fn parses_quote() {
+        let event = parse_frame(
+            r#"[{"channel": "/quotes/5269", "data": {"orderbookId": "5269", "lastPrice": 253.9, "buyPrice": 253.8, "sellPrice": 254.0, "updated": 1791990000000}}]"#,
+        );
+        match event {
+            PushEvent::Quote(quote) => {
+                assert_eq!(quote.orderbook_id, OrderbookId::from("5269"));
+                assert_eq!(quote.ask, Some(254.0));
+            }
+            other => panic!("expected a quote, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_order_depth() {
+        let event = parse_frame(
+            r#"[{"channel": "/orderdepths/5269", "data": {"orderbookId": "5269", "levels": [{"buySide": {"price": 253.8, "volume": 1200}, "sellSide": {"price": 254.0, "volume": 300}}]}}]"#,
+        );
+        match event {
+            PushEvent::OrderDepth(depth) => {
+                assert_eq!(depth.levels.len(), 1);
+                assert_eq!(depth.best_bid().map(|e| e.price), Some(253.8));
+            }
+            other => panic!("expected order depth, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_trade() {
+        let event = parse_frame(
+            r#"[{"channel": "/trades/5269", "data": {"dealId": "t-1", "price": 253.9, "volume": 100, "dealTime": 1791990000000, "buyer": "AVA", "seller": "NON"}}]"#,
+        );
+        match event {
+            PushEvent::Trade(trade) => assert_eq!(trade.volume, 100),
+            other => panic!("expected a trade, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_deal_and_order() {
+        let deal = parse_frame(
+            r#"[{"channel": "/deals/1234", "data": {"dealId": "d-1", "orderId": "o-1", "accountId": "1234", "orderbookId": "5269", "side": "BUY", "price": 253.9, "volume": 10, "dealTime": 1791990000000}}]"#,
+        );
+        match deal {
+            PushEvent::Deal(deal) => assert_eq!(deal.side, Side::Buy),
+            other => panic!("expected a deal, got {:?}", other),
+        }
+
+        let order = parse_frame(
+            r#"[{"channel": "/orders/1234", "data": {"orderId": "o-1", "accountId": "1234", "orderbookId": "5269", "side": "SELL", "price": 260.0, "volume": 10, "state": "ACCEPTED"}}]"#,
+        );
+        match order {
+            PushEvent::Order(order) => assert_eq!(order.account_id, AccountId::from("1234")),
+            other => panic!("expected an order, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_positions() {
+        let event = parse_frame(
+            r#"[{"channel": "/positions/1234", "data": {"accountId": "1234", "orderbookId": "5269", "volume": 140}}]"#,
+        );
+        match event {
+            PushEvent::Positions(update) => assert_eq!(update.volume, Some(140)),
+            other => panic!("expected positions, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_broker_trade_summary() {
+        let event = parse_frame(
+            r#"[{"channel": "/brokertradesummary/5269", "data": {"orderbookId": "5269", "brokerCode": "AVA", "buyVolume": 1500, "sellVolume": 900}}]"#,
+        );
+        match event {
+            PushEvent::BrokerTradeSummary(summary) => {
+                assert_eq!(summary.broker_code, "AVA");
+                assert_eq!(summary.net_volume(), 600);
+            }
+            other => panic!("expected a broker trade summary, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn keeps_unknown_channels_as_raw_json() {
+        let event =
+            parse_frame(r#"[{"channel": "/news/5269", "data": {"headline": "Quarterly report"}}]"#);
+        assert_eq!(
+            event,
+            PushEvent::Unknown {
+                channel: String::from("/news/5269"),
+                raw: json!({"headline": "Quarterly report"}),
+            }
+        );
+    }
+
+    #[test]
+    fn known_channel_with_bad_payload_is_an_error() {
+        assert!(PushEvent::parse("/quotes/5269", json!({"lastPrice": "n/a"})).is_err());
+    }
+}
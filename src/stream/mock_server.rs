@@ -0,0 +1,229 @@
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio_tungstenite::accept_async;
+use tokio_tungstenite::tungstenite::Message;
+
+pub(crate) const MOCK_CLIENT_ID: &str = "mock-client-id";
+
+#[derive(Default)]
+struct State {
+    received: Vec<Value>,
+    denied: Vec<String>,
+    reject_handshakes: bool,
+    advice: Option<Value>,
+    connect_advice: Option<Value>,
+    connections: Vec<mpsc::UnboundedSender<Message>>,
+}
+
+/// A CometD server good enough to test `PushConnection` against. It acknowledges
+/// handshakes and (un)subscriptions, holds connects unless told to answer them and
+/// records every message received.
+pub(crate) struct MockPushServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+}
+
+fn reply(message: &Value, state: &State) -> Option<Value> {
+    let channel = message["channel"].as_str()?;
+    let denied = &state.denied;
+    match channel {
+        "/meta/handshake" if state.reject_handshakes => Some(json!({
+            "channel": channel,
+            "id": message["id"],
+            "successful": false,
+            "error": "401::Authentication failed"
+        })),
+        "/meta/subscribe" if denied.iter().any(|d| message["subscription"] == d.as_str()) => {
+            Some(json!({
+                "channel": channel,
+                "id": message["id"],
+                "successful": false,
+                "error": "403::Forbidden",
+                "subscription": message["subscription"]
+            }))
+        }
+        "/meta/handshake" => Some(json!({
+            "channel": channel,
+            "id": message["id"],
+            "successful": true,
+            "clientId": MOCK_CLIENT_ID,
+            "version": "1.0",
+            "supportedConnectionTypes": ["websocket"],
+            "advice": state.advice.clone().unwrap_or_else(
+                || json!({"reconnect": "retry", "interval": 0, "timeout": 30000})
+            )
+        })),
+        "/meta/connect" => state.connect_advice.as_ref().map(|advice| {
+            json!({
+                "channel": channel,
+                "id": message["id"],
+                "successful": true,
+                "advice": advice
+            })
+        }),
+        "/meta/subscribe" | "/meta/unsubscribe" => Some(json!({
+            "channel": channel,
+            "id": message["id"],
+            "successful": true,
+            "subscription": message["subscription"]
+        })),
+        _ => None,
+    }
+}
+
+impl MockPushServer {
+    pub(crate) async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("failed to bind mock push server");
+        let addr = listener.local_addr().unwrap();
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let accept_state = state.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let state = accept_state.clone();
+                tokio::spawn(async move {
+                    let socket = match accept_async(stream).await {
+                        Ok(socket) => socket,
+                        Err(_) => return,
+                    };
+                    let (mut sink, mut source) = socket.split();
+                    let (sender, mut outgoing) = mpsc::unbounded_channel::<Message>();
+                    state.lock().unwrap().connections.push(sender.clone());
+
+                    tokio::spawn(async move {
+                        while let Some(message) = outgoing.recv().await {
+                            if sink.send(message).await.is_err() {
+                                break;
+                            }
+                        }
+                    });
+
+                    while let Some(Ok(Message::Text(text))) = source.next().await {
+                        let messages: Vec<Value> = serde_json::from_str(&text).unwrap_or_default();
+                        let mut state = state.lock().unwrap();
+                        let replies: Vec<Value> =
+                            messages.iter().filter_map(|m| reply(m, &state)).collect();
+                        state.received.extend(messages);
+                        drop(state);
+                        if !replies.is_empty() {
+                            let _ = sender.send(Message::Text(Value::from(replies).to_string()));
+                        }
+                    }
+                });
+            }
+        });
+
+        MockPushServer { addr, state }
+    }
+
+    pub(crate) fn url(&self) -> String {
+        format!("ws://{}/_push/cometd", self.addr)
+    }
+
+    pub(crate) fn received(&self, channel: &str) -> Vec<Value> {
+        self.state
+            .lock()
+            .unwrap()
+            .received
+            .iter()
+            .filter(|m| m["channel"] == channel)
+            .cloned()
+            .collect()
+    }
+
+    /// Makes the server refuse subscriptions to `subscription`.
+    pub(crate) fn deny_subscription(&self, subscription: &str) {
+        self.state
+            .lock()
+            .unwrap()
+            .denied
+            .push(String::from(subscription));
+    }
+
+    /// Makes the server fail every handshake as if the session had expired.
+    pub(crate) fn reject_handshakes(&self) {
+        self.state.lock().unwrap().reject_handshakes = true;
+    }
+
+    /// Replaces the advice sent with handshake replies.
+    pub(crate) fn advise(&self, advice: Value) {
+        self.state.lock().unwrap().advice = Some(advice);
+    }
+
+    /// Makes the server answer connects right away with `advice`.
+    pub(crate) fn answer_connects(&self, advice: Value) {
+        self.state.lock().unwrap().connect_advice = Some(advice);
+    }
+
+    /// Closes every open connection, as when Avanza drops an idle socket.
+    pub(crate) fn drop_connections(&self) {
+        for connection in self.state.lock().unwrap().connections.drain(..) {
+            let _ = connection.send(Message::Close(None));
+        }
+    }
+
+    fn is_subscribed(&self, subscription: &str) -> bool {
+        let mut subscribed = false;
+        for message in self.state.lock().unwrap().received.iter() {
+            if message["subscription"] == subscription {
+                subscribed = message["channel"] == "/meta/subscribe";
+            }
+        }
+        subscribed
+    }
+
+    /// Waits until the client is subscribed to `subscription`.
+    pub(crate) async fn wait_for_subscription(&self, subscription: &str) {
+        for _ in 0..500 {
+            if self.is_subscribed(subscription) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("client never subscribed to {}", subscription);
+    }
+
+    /// Waits until the client has subscribed to `subscription` `count` times, e.g. once more
+    /// after a reconnect.
+    pub(crate) async fn wait_for_subscribes(&self, subscription: &str, count: usize) {
+        for _ in 0..500 {
+            let subscribes = self
+                .received("/meta/subscribe")
+                .iter()
+                .filter(|m| m["subscription"] == subscription)
+                .count();
+            if subscribes >= count {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("client never resubscribed to {}", subscription);
+    }
+
+    /// Waits until the client has unsubscribed from `subscription`.
+    pub(crate) async fn wait_for_unsubscription(&self, subscription: &str) {
+        for _ in 0..500 {
+            if !self.is_subscribed(subscription) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("client never unsubscribed from {}", subscription);
+    }
+
+    /// Sends a data message on `channel` to every connected client.
+    pub(crate) fn publish(&self, channel: &str, data: Value) {
+        let frame = json!([{ "channel": channel, "data": data }]).to_string();
+        for connection in self.state.lock().unwrap().connections.iter() {
+            let _ = connection.send(Message::Text(frame.clone()));
+        }
+    }
+}
@@ -0,0 +1,11 @@
+pub mod broker_trade_summary;
+mod conflate;
+pub mod connection;
+pub mod deals_and_orders;
+pub mod event;
+#[cfg(test)]
+mod mock_server;
+pub mod order_depth;
+pub mod positions;
+pub mod quotes;
+pub mod trades;
@@ -0,0 +1,79 @@
+use crate::error::RequestError;
+use crate::market::order_depth::OrderDepth;
+use crate::stream::connection::{PushConnection, Subscription};
+use crate::types::OrderbookId;
+
+impl PushConnection {
+    /// Every update is a full snapshot of the order book, so consumers should replace
+    /// their state with it rather than merge it.
+    pub fn subscribe_order_depth(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<Subscription<OrderDepth>, RequestError> {
+        self.subscribe(format!("/orderdepths/{}", orderbook_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::Client;
+    use crate::stream::mock_server::MockPushServer;
+
+    use super::*;
+    use crate::test_support::authenticated_client_with;
+    use serde_json::json;
+    use tokio_test::assert_ok;
+    use wiremock::MockServer;
+
+    #[tokio::test]
+    async fn can_subscribe_to_order_depth() {
+        let mock_server = MockServer::start().await;
+        let push_server = MockPushServer::start().await;
+
+        let client =
+            authenticated_client_with(&mock_server, Client::builder().push_url(push_server.url()))
+                .await;
+
+        let connection = assert_ok!(client.connect_push().await);
+        let mut depth = assert_ok!(connection.subscribe_order_depth(&OrderbookId::from("5269")));
+        push_server.wait_for_subscription("/orderdepths/5269").await;
+
+        push_server.publish(
+            "/orderdepths/5269",
+            json!({
+                "orderbookId": "5269",
+                "levels": [
+                    {"buySide": {"price": 253.8, "volume": 1200, "orderCount": 4}, "sellSide": {"price": 254.0, "volume": 800, "orderCount": 2}}
+                ]
+            }),
+        );
+        push_server.publish(
+            "/orderdepths/5269",
+            json!({
+                "orderbookId": "5269",
+                "levels": [
+                    {"buySide": {"price": 253.9, "volume": 100, "orderCount": 1}, "sellSide": {"price": 254.0, "volume": 600, "orderCount": 1}},
+                    {"buySide": {"price": 253.8, "volume": 1200, "orderCount": 4}, "sellSide": null}
+                ]
+            }),
+        );
+
+        let first = assert_ok!(depth.next().await.expect("stream ended"));
+        assert_eq!(first.levels.len(), 1);
+        assert_eq!(first.best_bid().unwrap().price, 253.8);
+
+        let second = assert_ok!(depth.next().await.expect("stream ended"));
+        assert_eq!(second.levels.len(), 2);
+        assert_eq!(second.best_bid().unwrap().price, 253.9);
+        assert_eq!(second.best_ask().unwrap().volume, 600);
+
+        assert_ok!(depth.unsubscribe());
+        push_server
+            .wait_for_unsubscription("/orderdepths/5269")
+            .await;
+
+        let unsubscribe = push_server.received("/meta/unsubscribe");
+        assert_eq!(unsubscribe.len(), 1);
+        assert_eq!(unsubscribe[0]["subscription"], "/orderdepths/5269");
+    }
+}
@@ -0,0 +1,75 @@
+use crate::error::RequestError;
+use crate::stream::connection::{PushConnection, Subscription};
+use crate::types::{AccountId, OrderbookId};
+use serde::{Deserialize, Serialize};
+
+/// Sent when holdings or buying power change on an account, e.g. after a fill, a deposit
+/// or a corporate action. The payload is thin, so treat it as a signal to re-fetch
+/// `get_positions` rather than as a complete position.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionsUpdate {
+    pub account_id: AccountId,
+    #[serde(default)]
+    pub orderbook_id: Option<OrderbookId>,
+    #[serde(default)]
+    pub volume: Option<i64>,
+    #[serde(default)]
+    pub value: Option<f64>,
+    #[serde(default)]
+    pub buying_power: Option<f64>,
+}
+
+impl PushConnection {
+    pub fn subscribe_positions(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Subscription<PositionsUpdate>, RequestError> {
+        self.subscribe(format!("/positions/{}", account_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::client::Client;
+    use crate::stream::mock_server::MockPushServer;
+
+    use super::*;
+    use crate::test_support::authenticated_client_with;
+    use serde_json::json;
+    use tokio_test::assert_ok;
+    use wiremock::MockServer;
+
+    #[tokio::test]
+    async fn can_subscribe_to_positions() {
+        let mock_server = MockServer::start().await;
+        let push_server = MockPushServer::start().await;
+
+        let client =
+            authenticated_client_with(&mock_server, Client::builder().push_url(push_server.url()))
+                .await;
+
+        let connection = assert_ok!(client.connect_push().await);
+        let mut isk = assert_ok!(connection.subscribe_positions(&AccountId::from("1234")));
+        let mut kf = assert_ok!(connection.subscribe_positions(&AccountId::from("5678")));
+        push_server.wait_for_subscription("/positions/1234").await;
+        push_server.wait_for_subscription("/positions/5678").await;
+
+        push_server.publish(
+            "/positions/5678",
+            json!({"accountId": "5678", "orderbookId": "5269", "volume": 140, "value": 35546.0, "buyingPower": 1200.5}),
+        );
+
+        let update = assert_ok!(kf.next().await.expect("stream ended"));
+        assert_eq!(update.account_id, AccountId::from("5678"));
+        assert_eq!(update.orderbook_id, Some(OrderbookId::from("5269")));
+        assert_eq!(update.volume, Some(140));
+        assert_eq!(update.buying_power, Some(1200.5));
+
+        assert!(tokio::time::timeout(Duration::from_millis(100), isk.next())
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,191 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use crate::error::RequestError;
+use crate::stream::conflate::Conflate;
+use crate::stream::connection::{PushConnection, Subscription};
+use crate::types::OrderbookId;
+use chrono::{DateTime, Utc};
+use futures_util::Stream;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteUpdate {
+    pub orderbook_id: OrderbookId,
+    #[serde(default)]
+    pub last_price: Option<f64>,
+    #[serde(default, rename = "buyPrice")]
+    pub bid: Option<f64>,
+    #[serde(default, rename = "sellPrice")]
+    pub ask: Option<f64>,
+    #[serde(default)]
+    pub total_volume_traded: Option<u64>,
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    pub updated: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QuoteSubscriptionOptions {
+    conflate: Option<Duration>,
+}
+
+impl QuoteSubscriptionOptions {
+    /// Yield at most one update per `window`, the latest one received during it.
+    pub fn conflate(self, window: Duration) -> Self {
+        Self {
+            conflate: Some(window),
+        }
+    }
+}
+
+/// Quotes for one orderbook, conflated if requested in `QuoteSubscriptionOptions`.
+pub struct QuoteSubscription {
+    inner: Conflate<Subscription<QuoteUpdate>, QuoteUpdate>,
+}
+
+impl QuoteSubscription {
+    pub fn channel(&self) -> &str {
+        self.inner.get_ref().channel()
+    }
+
+    pub async fn next(&mut self) -> Option<Result<QuoteUpdate, RequestError>> {
+        std::future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+
+    pub fn unsubscribe(self) -> Result<(), RequestError> {
+        self.inner.into_inner().unsubscribe()
+    }
+}
+
+impl Stream for QuoteSubscription {
+    type Item = Result<QuoteUpdate, RequestError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.get_mut().inner).poll_next(cx)
+    }
+}
+
+impl PushConnection {
+    pub fn subscribe_quotes(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<Subscription<QuoteUpdate>, RequestError> {
+        self.subscribe(format!("/quotes/{}", orderbook_id))
+    }
+
+    pub fn subscribe_quotes_with(
+        &self,
+        orderbook_id: &OrderbookId,
+        options: QuoteSubscriptionOptions,
+    ) -> Result<QuoteSubscription, RequestError> {
+        let subscription = self.subscribe_quotes(orderbook_id)?;
+        Ok(QuoteSubscription {
+            inner: Conflate::new(subscription, options.conflate),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::Client;
+    use crate::stream::mock_server::{MockPushServer, MOCK_CLIENT_ID};
+
+    use super::*;
+    use crate::test_support::{authenticated_client_with, config};
+    use chrono::TimeZone;
+    use serde_json::json;
+    use tokio_test::{assert_err, assert_ok};
+    use wiremock::MockServer;
+
+    #[tokio::test]
+    async fn require_auth() {
+        let client = Client::builder()
+            .config(config())
+            .push_url(String::from("ws://fake-url.com/_push/cometd"))
+            .build()
+            .unwrap();
+        assert_err!(client.connect_push().await);
+    }
+
+    #[tokio::test]
+    async fn can_subscribe_to_quotes() {
+        let mock_server = MockServer::start().await;
+        let push_server = MockPushServer::start().await;
+
+        let client =
+            authenticated_client_with(&mock_server, Client::builder().push_url(push_server.url()))
+                .await;
+
+        let connection = assert_ok!(client.connect_push().await);
+        assert_eq!(connection.client_id(), MOCK_CLIENT_ID);
+
+        let handshake = push_server.received("/meta/handshake");
+        assert_eq!(handshake.len(), 1);
+        assert_eq!(
+            handshake[0]["ext"]["subscriptionId"],
+            "54320ff65-a4d3-4af0-9e9b-22729a6157c9"
+        );
+
+        let mut quotes = assert_ok!(connection.subscribe_quotes(&OrderbookId::from("5269")));
+        push_server.wait_for_subscription("/quotes/5269").await;
+
+        let subscribe = push_server.received("/meta/subscribe");
+        assert_eq!(subscribe[0]["clientId"], MOCK_CLIENT_ID);
+
+        push_server.publish(
+            "/quotes/5269",
+            json!({
+                "orderbookId": "5269",
+                "lastPrice": 253.9,
+                "buyPrice": 253.8,
+                "sellPrice": 254.0,
+                "totalVolumeTraded": 1830211,
+                "updated": 1791990000000i64
+            }),
+        );
+
+        let update = assert_ok!(quotes.next().await.expect("stream ended"));
+        assert_eq!(update.orderbook_id, OrderbookId::from("5269"));
+        assert_eq!(update.last_price, Some(253.9));
+        assert_eq!(update.bid, Some(253.8));
+        assert_eq!(update.ask, Some(254.0));
+        assert_eq!(update.total_volume_traded, Some(1830211));
+        assert_eq!(
+            update.updated,
+            Utc.timestamp_millis_opt(1791990000000).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn can_conflate_quotes() {
+        let mock_server = MockServer::start().await;
+        let push_server = MockPushServer::start().await;
+
+        let client =
+            authenticated_client_with(&mock_server, Client::builder().push_url(push_server.url()))
+                .await;
+
+        let connection = assert_ok!(client.connect_push().await);
+        let options = QuoteSubscriptionOptions::default().conflate(Duration::from_millis(200));
+        let mut quotes =
+            assert_ok!(connection.subscribe_quotes_with(&OrderbookId::from("5269"), options));
+        push_server.wait_for_subscription("/quotes/5269").await;
+
+        for (i, price) in [253.9, 254.0, 254.1, 253.8, 254.2].iter().enumerate() {
+            push_server.publish(
+                "/quotes/5269",
+                json!({"orderbookId": "5269", "lastPrice": price, "updated": 1791990000000i64 + i as i64}),
+            );
+        }
+
+        let update = assert_ok!(quotes.next().await.expect("stream ended"));
+        assert_eq!(update.last_price, Some(254.2));
+        assert!(
+            tokio::time::timeout(Duration::from_millis(300), quotes.next())
+                .await
+                .is_err()
+        );
+    }
+}
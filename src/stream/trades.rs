@@ -0,0 +1,147 @@
+use std::collections::{HashSet, VecDeque};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_util::Stream;
+
+use crate::error::RequestError;
+use crate::market::trades::Trade;
+use crate::stream::connection::{PushConnection, Subscription};
+use crate::types::OrderbookId;
+
+/// Number of recent deal ids remembered for deduplication.
+const SEEN_DEALS_CAPACITY: usize = 1024;
+
+/// Trades for one orderbook. The server may replay the latest trade after a reconnect,
+/// so trades with a deal id that has already been delivered are dropped.
+pub struct TradeSubscription {
+    inner: Subscription<Trade>,
+    seen: HashSet<String>,
+    order: VecDeque<String>,
+}
+
+impl TradeSubscription {
+    pub fn channel(&self) -> &str {
+        self.inner.channel()
+    }
+
+    pub async fn next(&mut self) -> Option<Result<Trade, RequestError>> {
+        std::future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+
+    pub fn unsubscribe(self) -> Result<(), RequestError> {
+        self.inner.unsubscribe()
+    }
+
+    /// Returns false if the deal id has been seen before.
+    fn remember(&mut self, deal_id: &str) -> bool {
+        if self.seen.contains(deal_id) {
+            return false;
+        }
+        if self.order.len() == SEEN_DEALS_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        self.seen.insert(String::from(deal_id));
+        self.order.push_back(String::from(deal_id));
+        true
+    }
+}
+
+impl Stream for TradeSubscription {
+    type Item = Result<Trade, RequestError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            let trade = match Pin::new(&mut this.inner).poll_next(cx) {
+                Poll::Ready(Some(Ok(trade))) => trade,
+                other => return other,
+            };
+            if this.remember(&trade.deal_id) {
+                return Poll::Ready(Some(Ok(trade)));
+            }
+        }
+    }
+}
+
+impl PushConnection {
+    pub fn subscribe_trades(
+        &self,
+        orderbook_id: &OrderbookId,
+    ) -> Result<TradeSubscription, RequestError> {
+        Ok(TradeSubscription {
+            inner: self.subscribe(format!("/trades/{}", orderbook_id))?,
+            seen: HashSet::new(),
+            order: VecDeque::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::client::Client;
+    use crate::stream::connection::{ConnectionEvent, PushOptions, ReconnectPolicy};
+    use crate::stream::mock_server::MockPushServer;
+
+    use super::*;
+    use crate::test_support::authenticated_client_with;
+    use serde_json::json;
+    use tokio_test::assert_ok;
+    use wiremock::MockServer;
+
+    #[tokio::test]
+    async fn can_subscribe_to_trades() {
+        let mock_server = MockServer::start().await;
+        let push_server = MockPushServer::start().await;
+
+        let client =
+            authenticated_client_with(&mock_server, Client::builder().push_url(push_server.url()))
+                .await;
+
+        let connection = assert_ok!(
+            client
+                .connect_push_with(PushOptions::default().reconnect(ReconnectPolicy {
+                    max_retries: 3,
+                    initial_backoff: Duration::from_millis(10),
+                    max_backoff: Duration::from_millis(50),
+                }))
+                .await
+        );
+        let mut events = connection.events();
+        let mut trades = assert_ok!(connection.subscribe_trades(&OrderbookId::from("5269")));
+        push_server.wait_for_subscription("/trades/5269").await;
+
+        let first = json!({"dealId": "d-1", "price": 253.8, "volume": 40, "dealTime": 1791990000000i64, "buyer": "AVA", "seller": "NON"});
+        let second =
+            json!({"dealId": "d-2", "price": 253.9, "volume": 150, "dealTime": 1791990060000i64});
+
+        push_server.publish("/trades/5269", first.clone());
+
+        let trade = assert_ok!(trades.next().await.expect("stream ended"));
+        assert_eq!(trade.deal_id, "d-1");
+        assert_eq!(trade.buyer.as_deref(), Some("AVA"));
+
+        push_server.drop_connections();
+        assert_eq!(
+            assert_ok!(events.recv().await),
+            ConnectionEvent::Disconnected
+        );
+        assert_eq!(
+            assert_ok!(events.recv().await),
+            ConnectionEvent::Reconnected
+        );
+        push_server.wait_for_subscribes("/trades/5269", 2).await;
+
+        // The server replays the last trade after a reconnect.
+        push_server.publish("/trades/5269", first);
+        push_server.publish("/trades/5269", second);
+
+        let trade = assert_ok!(trades.next().await.expect("stream ended"));
+        assert_eq!(trade.deal_id, "d-2");
+        assert_eq!(trade.volume, 150);
+    }
+}
@@ -0,0 +1,242 @@
+//! Parsing that reports the fields a model doesn't have, for
+//! [`ClientBuilder::strict_parsing`](crate::ClientBuilder::strict_parsing).
+//!
+//! The body is parsed into a [`Value`] first and the model deserialized from that, through a
+//! deserializer that notes the path of every value serde skips. Serde skips a value exactly
+//! when no field of the model takes its key. Values buffered for `flatten` or untagged enums
+//! aren't looked into.
+
+use std::cell::RefCell;
+
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+use serde_json::{Map, Value};
+
+use crate::error::RequestError;
+
+pub(crate) fn from_slice<T: DeserializeOwned>(body: &[u8]) -> Result<T, RequestError> {
+    let value: Value = serde_json::from_slice(body)?;
+    let ignored = RefCell::new(Vec::new());
+    let parsed = T::deserialize(Tracked {
+        value,
+        path: String::new(),
+        ignored: &ignored,
+    })?;
+    let ignored = ignored.into_inner();
+    if ignored.is_empty() {
+        Ok(parsed)
+    } else {
+        Err(RequestError::UnknownFields(ignored))
+    }
+}
+
+struct Tracked<'a> {
+    value: Value,
+    path: String,
+    ignored: &'a RefCell<Vec<String>>,
+}
+
+impl<'de> Deserializer<'de> for Tracked<'_> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Array(values) => visitor.visit_seq(TrackedSeq {
+                values: values.into_iter().enumerate(),
+                path: self.path,
+                ignored: self.ignored,
+            }),
+            Value::Object(entries) => visitor.visit_map(TrackedMap {
+                entries: entries.into_iter(),
+                next: None,
+                path: self.path,
+                ignored: self.ignored,
+            }),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.value.deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.ignored.borrow_mut().push(self.path);
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+struct TrackedSeq<'a> {
+    values: std::iter::Enumerate<std::vec::IntoIter<Value>>,
+    path: String,
+    ignored: &'a RefCell<Vec<String>>,
+}
+
+impl<'de> SeqAccess<'de> for TrackedSeq<'_> {
+    type Error = serde_json::Error;
+
+    fn next_element_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, Self::Error> {
+        let Some((index, value)) = self.values.next() else {
+            return Ok(None);
+        };
+        seed.deserialize(Tracked {
+            value,
+            path: format!("{}[{}]", self.path, index),
+            ignored: self.ignored,
+        })
+        .map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len())
+    }
+}
+
+struct TrackedMap<'a> {
+    entries: <Map<String, Value> as IntoIterator>::IntoIter,
+    next: Option<(String, Value)>,
+    path: String,
+    ignored: &'a RefCell<Vec<String>>,
+}
+
+impl<'de> MapAccess<'de> for TrackedMap<'_> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+        let parsed = seed.deserialize(Value::String(key.clone()))?;
+        self.next = Some((key, value));
+        Ok(Some(parsed))
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<S::Value, Self::Error> {
+        let (key, value) = self
+            .next
+            .take()
+            .ok_or_else(|| de::Error::custom("value requested before its key"))?;
+        let path = if self.path.is_empty() {
+            key
+        } else {
+            format!("{}.{}", self.path, key)
+        };
+        seed.deserialize(Tracked {
+            value,
+            path,
+            ignored: self.ignored,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::portfolio::positions::PositionsResponse;
+
+    const POSITIONS: &str = r#"{
+        "instrumentPositions": [
+            {
+                "instrumentType": "STOCK",
+                "positions": [
+                    {"accountId": "1111", "accountName": "ISK", "accountType": "Investeringssparkonto", "acquiredValue": 9000, "averageAcquiredPrice": 180, "change": 0, "changePercent": 0, "currency": "SEK", "depositable": false, "flagCode": "SE", "lastPrice": 200, "lastPriceUpdated": "2026-10-15T15:30:00", "name": "Volvo B", "orderbookId": "5269", "profit": 1000, "profitPercent": 11.1, "tradable": true, "value": 10000, "volume": 50}
+                ],
+                "todaysProfitPercent": 0,
+                "totalProfitPercent": 11.1,
+                "totalProfitValue": 1000,
+                "totalValue": 10000
+            }
+        ],
+        "totalProfit": 1000,
+        "totalProfitPercent": 11.1,
+        "totalBalance": 0,
+        "totalOwnCapital": 10000,
+        "totalBuyingPower": 0
+    }"#;
+
+    #[test]
+    fn parses_known_fields() {
+        let strict: PositionsResponse = from_slice(POSITIONS.as_bytes()).unwrap();
+        let lenient: PositionsResponse = serde_json::from_str(POSITIONS).unwrap();
+        assert_eq!(strict.total_own_capital, lenient.total_own_capital);
+        assert_eq!(
+            strict.instrument_positions[0].positions,
+            lenient.instrument_positions[0].positions
+        );
+    }
+
+    #[test]
+    fn names_every_unknown_field() {
+        let fixture = POSITIONS
+            .replace(
+                r#""volume": 50}"#,
+                r#""volume": 50, "marketPlace": "XSTO"}"#,
+            )
+            .replace(
+                r#""totalBuyingPower": 0"#,
+                r#""totalBuyingPower": 0, "currencyAccounts": [{"currency": "USD"}]"#,
+            );
+
+        let error = from_slice::<PositionsResponse>(fixture.as_bytes()).unwrap_err();
+
+        let RequestError::UnknownFields(paths) = error else {
+            panic!("expected UnknownFields, got {:?}", error);
+        };
+        assert_eq!(
+            paths,
+            [
+                "currencyAccounts",
+                "instrumentPositions[0].positions[0].marketPlace"
+            ]
+        );
+    }
+
+    #[test]
+    fn type_errors_are_parse_errors() {
+        let fixture = POSITIONS.replace(r#""totalBalance": 0"#, r#""totalBalance": "none""#);
+        assert!(matches!(
+            from_slice::<PositionsResponse>(fixture.as_bytes()),
+            Err(RequestError::ParseError(_))
+        ));
+    }
+}
@@ -0,0 +1,107 @@
+//! Canned Avanza responses and helpers to serve them from a [`wiremock`] server, for tests
+//! that run the real [`Client`](crate::client::Client) against a local mock of the API.
+//!
+//! ```no_run
+//! use avanza::test_support::{authenticated_client, mount_positions, POSITIONS};
+//! use wiremock::MockServer;
+//!
+//! # async fn example() {
+//! let server = MockServer::start().await;
+//! mount_positions(&server, POSITIONS).await;
+//!
+//! let client = authenticated_client(&server).await;
+//! assert_eq!(client.get_positions().await.unwrap().total_balance, 4000.0);
+//! # }
+//! ```
+
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::client::{Client, ClientBuilder, Config};
+
+/// Response to the username and password step of the login.
+pub const CREDENTIALS_RESPONSE: &str = r#"{"twoFactorLogin":{"transactionId":"4530ff65-a4d3-4af0-9e9b-22729a6157c9","method":"TOTP"}}"#;
+
+/// Response to the TOTP step of the login, sent with [`SECURITY_TOKEN`] in the
+/// `x-securitytoken` header.
+pub const TOTP_RESPONSE: &str = r#"{"authenticationSession":"4530ff65-a4d3-4af0-9e9b-22729a6157c9","pushSubscriptionId":"54320ff65-a4d3-4af0-9e9b-22729a6157c9","customerId":"123232", "registrationComplete": true}"#;
+
+pub const SECURITY_TOKEN: &str = "mysecrettoken";
+
+/// No positions, 4000 SEK in balance and buying power.
+pub const POSITIONS: &str = r#"{"instrumentPositions":[],"totalOwnCapital":100000,"totalProfit":40000,"totalBuyingPower":4000,"totalBalance":4000,"totalProfitPercent":10}"#;
+
+/// No accounts, 1000 SEK in own capital of which 250 is buying power.
+pub const OVERVIEW: &str =
+    r#"{"accounts":[],"totalOwnCapital":1000,"totalBuyingPower":250,"totalBalance":1000}"#;
+
+/// No orders and no deals.
+pub const DEALS_AND_ORDERS: &str = r#"{"orders":[],"deals":[]}"#;
+
+/// Makes both steps of [`Client::authenticate`](crate::client::Client::authenticate) succeed.
+pub async fn mount_successful_auth(server: &MockServer) {
+    Mock::given(method("POST"))
+        .and(path("/_api/authentication/sessions/usercredentials"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(CREDENTIALS_RESPONSE))
+        .mount(server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/_api/authentication/sessions/totp"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(TOTP_RESPONSE)
+                .append_header("x-securitytoken", SECURITY_TOKEN),
+        )
+        .mount(server)
+        .await;
+}
+
+/// Credentials the mocked login accepts.
+pub fn config() -> Config {
+    Config {
+        avanza_username: String::from("user"),
+        avanza_password: String::from("pass"),
+        avanza_totp_secret: String::from("secret"),
+    }
+}
+
+/// A client for `server`, logged in through [`mount_successful_auth`].
+pub async fn authenticated_client(server: &MockServer) -> Client {
+    authenticated_client_with(server, Client::builder()).await
+}
+
+/// Like [`authenticated_client`], with the other settings of `builder`, e.g. a retry policy.
+pub async fn authenticated_client_with(server: &MockServer, builder: ClientBuilder) -> Client {
+    mount_successful_auth(server).await;
+    let client = builder
+        .config(config())
+        .api_url(server.uri())
+        .build()
+        .unwrap();
+    client.authenticate().await.expect("failed to authenticate");
+    client
+}
+
+/// Answers `get_positions` with `fixture`, e.g. [`POSITIONS`].
+pub async fn mount_positions(server: &MockServer, fixture: &str) {
+    mount_get(server, "/_mobile/account/positions", fixture).await;
+}
+
+/// Answers `get_overview` with `fixture`, e.g. [`OVERVIEW`].
+pub async fn mount_overview(server: &MockServer, fixture: &str) {
+    mount_get(server, "/_mobile/account/overview", fixture).await;
+}
+
+/// Answers `get_deals_and_orders` with `fixture`, e.g. [`DEALS_AND_ORDERS`].
+pub async fn mount_deals_and_orders(server: &MockServer, fixture: &str) {
+    mount_get(server, "/_mobile/account/dealsandorders", fixture).await;
+}
+
+/// Answers GET requests to `endpoint` with `body`.
+pub async fn mount_get(server: &MockServer, endpoint: &str, body: &str) {
+    Mock::given(method("GET"))
+        .and(path(endpoint))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(server)
+        .await;
+}
@@ -0,0 +1,290 @@
+//! An in-memory [`AvanzaApi`] for testing code written against the trait.
+//!
+//! Every endpoint first answers with the responses queued with the matching `queue_*` method,
+//! in order, then from the closure set with `on_*`. It panics if it has neither, except
+//! `authenticate` which succeeds unless told otherwise.
+//!
+//! ```
+//! use avanza::api::AvanzaApi;
+//! use avanza::test_util::MockClient;
+//! use avanza::RequestError;
+//!
+//! async fn account_count(api: &impl AvanzaApi) -> Result<usize, RequestError> {
+//!     Ok(api.get_accounts().await?.len())
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! let mock = MockClient::new().queue_get_accounts(Err(RequestError::NotAuthenticatedError()));
+//!
+//! assert!(account_count(&mock).await.is_err());
+//! assert_eq!(mock.methods(), ["get_accounts"]);
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde_json::json;
+
+use crate::account::accounts::Account;
+use crate::account::cash_balance::CashBalance;
+use crate::account::courtage::CourtageInfo;
+use crate::account::transactions::{Transaction, TransactionOptions};
+use crate::account::transfers::Transfer;
+use crate::api::AvanzaApi;
+use crate::batch::BatchResult;
+use crate::client::{AuthenticateResponse, CallOptions};
+use crate::error::RequestError;
+use crate::fund::holdings::{FundHoldings, LookThrough};
+use crate::market::chart::ChartData;
+use crate::market::exchange_rates::ExchangeRate;
+use crate::market::markets::Market;
+use crate::market::offers::Offer;
+use crate::market::order_depth::OrderDepth;
+use crate::market::orderbook::Orderbook;
+use crate::market::ownership::Ownership;
+use crate::market::price_history::{Candle, Resolution};
+use crate::market::quotes::Quote;
+use crate::market::suggest::Suggestion;
+use crate::market::trades::Trade;
+use crate::market::watchlists::{Watchlist, WatchlistId, WatchlistQuote};
+use crate::order::new_order::NewOrder;
+use crate::order::place::OrderResponse;
+use crate::portfolio::deals_and_orders::DealsAndOrdersResponse;
+use crate::portfolio::overview::Overview;
+use crate::portfolio::positions::PositionsResponse;
+use crate::stock::dividends::Dividend;
+use crate::stock::key_ratios::KeyRatios;
+use crate::stock::short_positions::ShortPositions;
+use crate::types::{AccountId, OrderbookId};
+
+macro_rules! mock_endpoints {
+    ($($name:ident, $on:ident, $queue:ident, ($($arg:ident: $ty:ty),*) -> $ret:ty;)*) => {
+        #[derive(Default)]
+        struct Responders {
+            $($name: Responder<Box<dyn FnMut($($ty),*) -> Result<$ret, RequestError> + Send>, $ret>,)*
+        }
+
+        impl MockClient {
+            $(
+                pub fn $on(
+                    mut self,
+                    responder: impl FnMut($($ty),*) -> Result<$ret, RequestError> + Send + 'static,
+                ) -> Self {
+                    self.responders.get_mut().unwrap().$name.on = Some(Box::new(responder));
+                    self
+                }
+
+                pub fn $queue(mut self, response: Result<$ret, RequestError>) -> Self {
+                    self.responders.get_mut().unwrap().$name.queue.push_back(response);
+                    self
+                }
+            )*
+        }
+
+        #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+        impl AvanzaApi for MockClient {
+            $(
+                async fn $name(&self, $($arg: $ty),*) -> Result<$ret, RequestError> {
+                    self.calls.lock().unwrap().push(Call {
+                        method: stringify!($name),
+                        args: vec![$(format!("{:?}", $arg)),*],
+                    });
+                    let mut responders = self.responders.lock().unwrap();
+                    let responder = &mut responders.$name;
+                    if let Some(response) = responder.queue.pop_front() {
+                        return response;
+                    }
+                    match responder.on.as_mut() {
+                        Some(on) => on($($arg),*),
+                        None => panic!("no response set for {}", stringify!($name)),
+                    }
+                }
+            )*
+        }
+    };
+}
+
+struct Responder<F, T> {
+    queue: VecDeque<Result<T, RequestError>>,
+    on: Option<F>,
+}
+
+impl<F, T> Default for Responder<F, T> {
+    fn default() -> Self {
+        Self {
+            queue: VecDeque::new(),
+            on: None,
+        }
+    }
+}
+
+/// An endpoint called on a [`MockClient`], with its arguments formatted with `Debug`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call {
+    pub method: &'static str,
+    pub args: Vec<String>,
+}
+
+pub struct MockClient {
+    responders: Mutex<Responders>,
+    calls: Mutex<Vec<Call>>,
+}
+
+impl MockClient {
+    pub fn new() -> Self {
+        let mock = Self {
+            responders: Mutex::new(Responders::default()),
+            calls: Mutex::new(Vec::new()),
+        };
+        mock.on_authenticate(|| {
+            Ok(serde_json::from_value(json!({
+                "twoFactorLogin": {"method": "TOTP", "transactionId": "mock-transaction"}
+            }))?)
+        })
+    }
+
+    /// The endpoints called so far, in call order.
+    pub fn calls(&self) -> Vec<Call> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    /// Names of the endpoints called so far, in call order.
+    pub fn methods(&self) -> Vec<&'static str> {
+        self.calls
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|call| call.method)
+            .collect()
+    }
+}
+
+impl Default for MockClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mock_endpoints! {
+    authenticate, on_authenticate, queue_authenticate, () -> AuthenticateResponse;
+    get_positions, on_get_positions, queue_get_positions, () -> PositionsResponse;
+    get_overview, on_get_overview, queue_get_overview, () -> Overview;
+    get_deals_and_orders, on_get_deals_and_orders, queue_get_deals_and_orders, () -> DealsAndOrdersResponse;
+    get_accounts, on_get_accounts, queue_get_accounts, () -> Vec<Account>;
+    get_cash_balance, on_get_cash_balance, queue_get_cash_balance, (account_id: &AccountId) -> CashBalance;
+    get_courtage_info, on_get_courtage_info, queue_get_courtage_info, (account_id: &AccountId) -> Option<CourtageInfo>;
+    get_pending_transfers, on_get_pending_transfers, queue_get_pending_transfers, (account_id: &AccountId) -> Vec<Transfer>;
+    get_transactions, on_get_transactions, queue_get_transactions,
+        (account_id: &AccountId, options: &TransactionOptions) -> Vec<Transaction>;
+    get_fund_holdings, on_get_fund_holdings, queue_get_fund_holdings, (orderbook_id: &OrderbookId) -> FundHoldings;
+    portfolio_look_through, on_portfolio_look_through, queue_portfolio_look_through, (positions: &PositionsResponse) -> LookThrough;
+    get_markets, on_get_markets, queue_get_markets, () -> Vec<Market>;
+    get_offers, on_get_offers, queue_get_offers, () -> Vec<Offer>;
+    get_exchange_rates, on_get_exchange_rates, queue_get_exchange_rates, () -> Vec<ExchangeRate>;
+    get_orderbooks, on_get_orderbooks, queue_get_orderbooks, (orderbook_ids: &[OrderbookId]) -> BatchResult<Quote>;
+    get_orderbook, on_get_orderbook, queue_get_orderbook, (orderbook_id: &OrderbookId) -> Orderbook;
+    get_order_depth, on_get_order_depth, queue_get_order_depth, (orderbook_id: &OrderbookId) -> OrderDepth;
+    get_trades, on_get_trades, queue_get_trades, (orderbook_id: &OrderbookId) -> Vec<Trade>;
+    get_ownership, on_get_ownership, queue_get_ownership, (orderbook_id: &OrderbookId) -> Ownership;
+    get_price_history, on_get_price_history, queue_get_price_history,
+        (orderbook_id: &OrderbookId, from: NaiveDate, to: NaiveDate, resolution: Resolution) -> Vec<Candle>;
+    get_chart_data, on_get_chart_data, queue_get_chart_data,
+        (orderbook_id: &OrderbookId, from: NaiveDate, to: NaiveDate) -> ChartData;
+    suggest, on_suggest, queue_suggest, (prefix: &str, limit: usize) -> Vec<Suggestion>;
+    get_watchlists, on_get_watchlists, queue_get_watchlists, () -> Vec<Watchlist>;
+    get_watchlist, on_get_watchlist, queue_get_watchlist, (watchlist_id: &WatchlistId) -> Watchlist;
+    get_watchlist_quotes, on_get_watchlist_quotes, queue_get_watchlist_quotes, (watchlist_id: &WatchlistId) -> Vec<WatchlistQuote>;
+    get_dividend_history, on_get_dividend_history, queue_get_dividend_history, (orderbook_id: &OrderbookId) -> Vec<Dividend>;
+    get_key_ratios, on_get_key_ratios, queue_get_key_ratios, (orderbook_id: &OrderbookId) -> KeyRatios;
+    get_short_positions, on_get_short_positions, queue_get_short_positions, (orderbook_id: &OrderbookId) -> ShortPositions;
+    place_order, on_place_order, queue_place_order, (order: &NewOrder, options: CallOptions) -> OrderResponse;
+    delete_order, on_delete_order, queue_delete_order, (account_id: &AccountId, order_id: &str) -> OrderResponse;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio_test::{assert_err, assert_ok};
+
+    #[tokio::test]
+    async fn authenticates_by_default() {
+        let mock = MockClient::new();
+        assert_ok!(mock.authenticate().await);
+    }
+
+    #[tokio::test]
+    async fn responders_see_arguments() {
+        let mock = MockClient::new()
+            .on_suggest(|prefix, limit| {
+                assert_eq!(prefix, "vol");
+                assert_eq!(limit, 5);
+                Ok(Vec::new())
+            })
+            .on_get_accounts(|| Err(RequestError::NotAuthenticatedError()));
+
+        assert_ok!(mock.suggest("vol", 5).await);
+        assert_err!(mock.get_accounts().await);
+        assert_eq!(mock.methods(), ["suggest", "get_accounts"]);
+    }
+
+    #[tokio::test]
+    async fn records_arguments() {
+        let mock =
+            MockClient::new().on_get_cash_balance(|_| Err(RequestError::NotAuthenticatedError()));
+
+        assert_err!(mock.get_cash_balance(&AccountId::from("1234")).await);
+        assert_ok!(mock.authenticate().await);
+
+        assert_eq!(
+            mock.calls(),
+            [
+                Call {
+                    method: "get_cash_balance",
+                    args: vec![String::from("AccountId(\"1234\")")],
+                },
+                Call {
+                    method: "authenticate",
+                    args: Vec::new(),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn queued_responses_come_first_and_in_order() {
+        let mock = MockClient::new()
+            .queue_get_accounts(Err(RequestError::UnknownAuthenticationMethod()))
+            .queue_get_accounts(Ok(Vec::new()))
+            .on_get_accounts(|| Err(RequestError::NotAuthenticatedError()));
+
+        assert!(matches!(
+            mock.get_accounts().await,
+            Err(RequestError::UnknownAuthenticationMethod())
+        ));
+        assert_ok!(mock.get_accounts().await);
+        assert!(matches!(
+            mock.get_accounts().await,
+            Err(RequestError::NotAuthenticatedError())
+        ));
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "no response set for get_markets")]
+    async fn panics_when_queue_runs_out() {
+        let mock = MockClient::new().queue_get_markets(Ok(Vec::new()));
+        let _ = mock.get_markets().await;
+        let _ = mock.get_markets().await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "no response set for get_positions")]
+    async fn panics_without_response() {
+        let mock = MockClient::new();
+        let _ = mock.get_positions().await;
+    }
+}
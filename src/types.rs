@@ -0,0 +1,153 @@
+use std::borrow::Cow;
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AccountId(pub String);
+
+impl fmt::Display for AccountId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<&str> for AccountId {
+    fn from(value: &str) -> Self {
+        AccountId(String::from(value))
+    }
+}
+
+/// A percentage as Avanza returns it, i.e. `0.25` means 0.25 %.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Percent(pub f64);
+
+impl Percent {
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+
+    pub fn as_fraction(&self) -> f64 {
+        self.0 / 100.0
+    }
+}
+
+impl fmt::Display for Percent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} %", self.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct OrderbookId(pub String);
+
+impl fmt::Display for OrderbookId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<&str> for OrderbookId {
+    fn from(value: &str) -> Self {
+        OrderbookId(String::from(value))
+    }
+}
+
+/// An ISO 4217 currency code.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Currency(Cow<'static, str>);
+
+impl Currency {
+    pub const SEK: Currency = Currency(Cow::Borrowed("SEK"));
+    pub const NOK: Currency = Currency(Cow::Borrowed("NOK"));
+    pub const DKK: Currency = Currency(Cow::Borrowed("DKK"));
+    pub const EUR: Currency = Currency(Cow::Borrowed("EUR"));
+    pub const USD: Currency = Currency(Cow::Borrowed("USD"));
+    pub const GBP: Currency = Currency(Cow::Borrowed("GBP"));
+
+    pub fn new(code: &str) -> Self {
+        Currency(Cow::Owned(code.to_uppercase()))
+    }
+
+    pub fn code(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<&str> for Currency {
+    fn from(value: &str) -> Self {
+        Currency::new(value)
+    }
+}
+
+/// An ISO 3166-1 alpha-2 country code.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CountryCode(pub String);
+
+impl fmt::Display for CountryCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<&str> for CountryCode {
+    fn from(value: &str) -> Self {
+        CountryCode(value.to_uppercase())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum InstrumentType {
+    Stock,
+    Fund,
+    Certificate,
+    Warrant,
+    ExchangeTradedFund,
+    Bond,
+    Index,
+    Option,
+    FutureForward,
+    #[serde(other)]
+    Other,
+}
+
+/// The names Avanza uses, in any case. Unknown ones are [`InstrumentType::Other`].
+impl FromStr for InstrumentType {
+    type Err = Infallible;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(match value.to_uppercase().as_str() {
+            "STOCK" => InstrumentType::Stock,
+            "FUND" => InstrumentType::Fund,
+            "CERTIFICATE" => InstrumentType::Certificate,
+            "WARRANT" => InstrumentType::Warrant,
+            "EXCHANGE_TRADED_FUND" => InstrumentType::ExchangeTradedFund,
+            "BOND" => InstrumentType::Bond,
+            "INDEX" => InstrumentType::Index,
+            "OPTION" => InstrumentType::Option,
+            "FUTURE_FORWARD" => InstrumentType::FutureForward,
+            _ => InstrumentType::Other,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Side {
+    Buy,
+    Sell,
+}